@@ -7,16 +7,18 @@
 
 use std::collections::BTreeMap;
 
-use fj_math::Point;
+use fj_math::{Aabb, Point};
 
 use crate::{
-    geometry::{CurveBoundary, GlobalPath, SurfacePath},
+    geometry::{CurveBoundary, GlobalPath, SurfaceGeometry, SurfacePath},
     objects::{Curve, Edge, Surface, Vertex},
     storage::{Handle, HandleWrapper},
 };
 
 use super::{
     curve::{CurveApprox, CurveApproxCache, CurveApproxSegment},
+    path::PathApproxParams,
+    surface::SurfaceApproxParams,
     Approx, ApproxPoint, Tolerance,
 };
 
@@ -70,7 +72,7 @@ impl Approx for (&Edge, &Surface) {
                         // For now, let's just approximate *all* we need and
                         // insert that into the cache. The cache takes care of
                         // merging that with whatever is already there.
-                        cache.insert_curve_approx(
+                        let mut segment = cache.insert_curve_approx(
                             edge.curve().clone(),
                             approx_curve(
                                 &edge.path(),
@@ -78,7 +80,18 @@ impl Approx for (&Edge, &Surface) {
                                 edge.boundary(),
                                 tolerance,
                             ),
-                        )
+                        );
+
+                        // What we get back from the cache is normalized, and
+                        // might have been merged with other segments. We only
+                        // need the part that belongs to this edge, in the
+                        // direction of this edge.
+                        segment.make_subset(edge.boundary().normalize());
+                        if !edge.boundary().is_normalized() {
+                            segment.reverse();
+                        }
+
+                        segment
                     }
                 }
             };
@@ -127,19 +140,44 @@ fn approx_curve(
     boundary: CurveBoundary<Point<1>>,
     tolerance: impl Into<Tolerance>,
 ) -> CurveApproxSegment {
-    // There are different cases of varying complexity. Circles are the hard
-    // part here, as they need to be approximated, while lines don't need to be.
+    // There are different cases of varying complexity. Circles, ellipses, and
+    // NURBS curves are the hard part here, as they need to be approximated,
+    // while lines don't need to be.
     //
     // This will probably all be unified eventually, as `SurfacePath` and
     // `GlobalPath` grow APIs that are better suited to implementing this code
     // in a more abstract way.
-    let points = match (path, surface.geometry().u) {
-        (SurfacePath::Circle(_), GlobalPath::Circle(_)) => {
+    let points = match (path, surface.geometry()) {
+        (
+            SurfacePath::Circle(_)
+            | SurfacePath::Ellipse(_)
+            | SurfacePath::Nurbs(_),
+            SurfaceGeometry::Swept {
+                u:
+                    GlobalPath::Circle(_)
+                    | GlobalPath::Ellipse(_)
+                    | GlobalPath::Helix(_)
+                    | GlobalPath::Nurbs(_),
+                ..
+            }
+            | SurfaceGeometry::Sphere(_)
+            | SurfaceGeometry::Cone(_)
+            | SurfaceGeometry::Torus(_)
+            | SurfaceGeometry::Nurbs(_),
+        ) => {
             todo!(
-                "Approximating a circle on a curved surface not supported yet."
+                "Approximating a curve on a curved surface not supported yet."
             )
         }
-        (SurfacePath::Circle(_), GlobalPath::Line(_)) => {
+        (
+            SurfacePath::Circle(_)
+            | SurfacePath::Ellipse(_)
+            | SurfacePath::Nurbs(_),
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Line(_),
+                ..
+            },
+        ) => {
             (path, boundary)
                 .approx_with_cache(tolerance, &mut ())
                 .into_iter()
@@ -166,26 +204,56 @@ fn approx_curve(
                 })
                 .collect()
         }
-        (SurfacePath::Line(line), _) => {
+        (SurfacePath::Line(line), SurfaceGeometry::Swept { u, .. }) => {
             let range_u =
                 CurveBoundary::from(boundary.inner.map(|point_curve| {
                     [path.point_from_path_coords(point_curve).u]
                 }));
 
-            let approx_u = (surface.geometry().u, range_u)
-                .approx_with_cache(tolerance, &mut ());
+            let approx_u = (u, range_u).approx_with_cache(tolerance, &mut ());
 
             let mut points = Vec::new();
             for (u, _) in approx_u {
-                let t = (u.t - line.origin().u) / line.direction().u;
-                let point_surface = path.point_from_path_coords([t]);
+                // The approximation is in terms of the u-axis, but we need
+                // the curve coordinates of the line.
+                let t =
+                    Point::from([(u.t - line.origin().u) / line.direction().u]);
+                let point_surface = path.point_from_path_coords(t);
                 let point_global =
                     surface.geometry().point_from_surface_coords(point_surface);
-                points.push((u, point_global));
+                points.push((t, point_global));
             }
 
             points
         }
+        (
+            SurfacePath::Line(line),
+            geometry @ (SurfaceGeometry::Sphere(_)
+            | SurfaceGeometry::Cone(_)
+            | SurfaceGeometry::Torus(_)
+            | SurfaceGeometry::Nurbs(_)),
+        ) => {
+            // The curvature of these surfaces isn't limited to the direction
+            // of either surface axis, so we approximate the line directly.
+            let aabb =
+                Aabb::<2>::from_points(boundary.inner.map(|point_curve| {
+                    path.point_from_path_coords(point_curve)
+                }));
+            let params =
+                SurfaceApproxParams::for_region(&geometry, &aabb, tolerance)
+                    .expect("Surface is curved, must have parameters");
+
+            PathApproxParams::for_line_in_surface(line, &params)
+                .points(boundary)
+                .map(|point_curve| {
+                    let point_surface =
+                        path.point_from_path_coords(point_curve);
+                    let point_global =
+                        geometry.point_from_surface_coords(point_surface);
+                    (point_curve, point_global)
+                })
+                .collect()
+        }
     };
 
     let points = points
@@ -245,13 +313,14 @@ impl EdgeApproxCache {
 mod tests {
     use std::{f64::consts::TAU, ops::Deref};
 
+    use fj_math::Scalar;
     use pretty_assertions::assert_eq;
 
     use crate::{
         algorithms::approx::{Approx, ApproxPoint},
         geometry::{CurveBoundary, GlobalPath, SurfaceGeometry},
         objects::{Edge, Surface},
-        operations::BuildEdge,
+        operations::{BuildEdge, BuildSurface},
         services::Services,
     };
 
@@ -273,7 +342,7 @@ mod tests {
     fn approx_line_on_curved_surface_but_not_along_curve() {
         let mut services = Services::new();
 
-        let surface = Surface::new(SurfaceGeometry {
+        let surface = Surface::new(SurfaceGeometry::Swept {
             u: GlobalPath::circle_from_radius(1.),
             v: [0., 0., 1.].into(),
         });
@@ -293,7 +362,7 @@ mod tests {
         let path = GlobalPath::circle_from_radius(1.);
         let boundary = CurveBoundary::from([[0.], [TAU]]);
 
-        let surface = Surface::new(SurfaceGeometry {
+        let surface = Surface::new(SurfaceGeometry::Swept {
             u: path,
             v: [0., 0., 1.].into(),
         });
@@ -343,4 +412,31 @@ mod tests {
                 .collect::<Vec<_>>();
         assert_eq!(approx.rest, expected_approx);
     }
+
+    #[test]
+    fn approx_line_on_sphere() {
+        let mut services = Services::new();
+
+        let surface = Surface::sphere_from_center_and_radius([0., 0., 0.], 1.);
+        let edge =
+            Edge::line_segment([[0., 0.5], [TAU, 0.5]], None, &mut services);
+
+        let tolerance = 0.1;
+        let approx = (&edge, &surface).approx(tolerance);
+
+        // The line follows a circle of latitude. The points of the
+        // approximation must be on the sphere, and the segments between them
+        // must not stray further from it than the tolerance allows.
+        assert!(approx.rest.len() > 3);
+        for points in approx.points().windows(2) {
+            let [a, b] = [points[0], points[1]].map(|point| point.global_form);
+            let center_of_segment = a + (b - a) / 2.;
+
+            assert!((a.coords.magnitude() - 1.).abs() < Scalar::from(1e-12));
+            assert!(
+                Scalar::ONE - center_of_segment.coords.magnitude()
+                    <= Scalar::from(tolerance)
+            );
+        }
+    }
 }
@@ -4,15 +4,19 @@
 
 use std::{collections::BTreeSet, ops::Deref};
 
-use fj_interop::mesh::Color;
+use fj_interop::{ext::SliceExt, mesh::Color};
+use fj_math::{Aabb, Point, Scalar, Segment};
 
 use crate::{
+    algorithms::intersect::{face_point::FacePointIntersection, Intersect},
+    geometry::SurfaceGeometry,
     objects::{Face, Handedness, Handles},
     validate::ValidationConfig,
 };
 
 use super::{
-    cycle::CycleApprox, edge::EdgeApproxCache, Approx, ApproxPoint, Tolerance,
+    cycle::CycleApprox, edge::EdgeApproxCache, surface::SurfaceApproxParams,
+    Approx, ApproxPoint, Tolerance,
 };
 
 impl Approx for &Handles<Face> {
@@ -72,18 +76,15 @@ impl Approx for &Face {
     ) -> Self::Approximation {
         let tolerance = tolerance.into();
 
-        // Curved faces whose curvature is not fully defined by their edges
-        // are not supported yet. For that reason, we can fully ignore `face`'s
-        // `surface` field and just pass the edges to `Self::for_edges`.
+        // Some curved faces have their curvature fully defined by their edges.
+        // An example of that is the cylinder. Its curvature is fully defined
+        // by the edges (circles) that border it. The circle approximations are
+        // sufficient to triangulate the surface.
         //
-        // An example of a curved face that is supported, is the cylinder. Its
-        // curvature is fully defined be the edges (circles) that border it. The
-        // circle approximations are sufficient to triangulate the surface.
-        //
-        // An example of a curved face that is currently not supported, and thus
-        // doesn't need to be handled here, is a sphere. A spherical face would
-        // would need to provide its own approximation, as the edges that bound
-        // it have nothing to do with its curvature.
+        // An example of a curved face where that is not the case, is a sphere.
+        // A spherical face needs to provide points from the inside of the face
+        // too, as the edges that bound it have nothing to do with its
+        // curvature.
 
         let exterior =
             (self.region().exterior().deref(), self.surface().deref())
@@ -96,9 +97,17 @@ impl Approx for &Face {
             interiors.insert(cycle);
         }
 
+        let inner_points = approx_inner_points(
+            self,
+            &self.surface().geometry(),
+            Some(&exterior).into_iter().chain(&interiors),
+            tolerance,
+        );
+
         FaceApprox {
             exterior,
             interiors,
+            inner_points,
             color: self.region().color(),
             coord_handedness: self.coord_handedness(),
         }
@@ -114,6 +123,12 @@ pub struct FaceApprox {
     /// Approximations of the interior cycles
     pub interiors: BTreeSet<CycleApprox>,
 
+    /// Points from the inside of the face
+    ///
+    /// Only faces whose curvature is not defined by their edges, like faces on
+    /// a sphere, cone, or torus, need those.
+    pub inner_points: BTreeSet<ApproxPoint<2>>,
+
     /// The color of the approximated face
     pub color: Option<Color>,
 
@@ -132,6 +147,99 @@ impl FaceApprox {
             points.extend(cycle_approx.points());
         }
 
+        points.extend(self.inner_points.iter().copied());
+
         points
     }
 }
+
+/// Approximate the inside of a face
+///
+/// Places points on a regular grid in surface coordinates, using the same
+/// increments that the approximation of lines in the surface uses. Points that
+/// are too close to the boundary of the face are left out, to prevent
+/// degenerate triangles.
+///
+/// Returns no points, if the surface doesn't need them.
+fn approx_inner_points<'r>(
+    face: &Face,
+    surface: &SurfaceGeometry,
+    cycles: impl Iterator<Item = &'r CycleApprox>,
+    tolerance: Tolerance,
+) -> BTreeSet<ApproxPoint<2>> {
+    let boundary = cycles
+        .flat_map(|cycle| {
+            let points = cycle
+                .points()
+                .into_iter()
+                .map(|point| point.local_form)
+                .collect::<Vec<_>>();
+
+            points
+                .as_slice()
+                .array_windows_ext()
+                .map(|&[a, b]| Segment::from_points([a, b]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let aabb =
+        Aabb::<2>::from_points(boundary.iter().flat_map(Segment::points));
+    let Some(params) =
+        SurfaceApproxParams::for_region(surface, &aabb, tolerance)
+    else {
+        return BTreeSet::new();
+    };
+
+    // Distances are easier to judge in units of the increments, as those can
+    // differ between the axes.
+    let [increment_u, increment_v] = params.increment();
+    let to_grid = |point: Point<2>| {
+        Point::from([point.u / increment_u, point.v / increment_v])
+    };
+    let boundary = boundary
+        .into_iter()
+        .map(|segment| Segment::from_points(segment.points().map(to_grid)))
+        .collect::<Vec<_>>();
+    let [min, max] = [aabb.min, aabb.max].map(to_grid);
+
+    let mut points = BTreeSet::new();
+
+    let mut i = min.u.ceil();
+    while i <= max.u {
+        let mut j = min.v.ceil();
+        while j <= max.v {
+            let point_grid = Point::from([i, j]);
+            let point = Point::from([i * increment_u, j * increment_v]);
+            j += Scalar::ONE;
+
+            let is_inside = matches!(
+                (face, &point).intersect(),
+                Some(FacePointIntersection::PointIsInsideFace)
+            );
+            let is_clear_of_boundary = boundary.iter().all(|&segment| {
+                distance_to_segment(point_grid, segment) >= Scalar::from(0.5)
+            });
+
+            if is_inside && is_clear_of_boundary {
+                points.insert(ApproxPoint::new(
+                    point,
+                    surface.point_from_surface_coords(point),
+                ));
+            }
+        }
+
+        i += Scalar::ONE;
+    }
+
+    points
+}
+
+fn distance_to_segment(point: Point<2>, segment: Segment<2>) -> Scalar {
+    let [a, b] = segment.points();
+    let ab = b - a;
+
+    let t =
+        ((point - a).dot(&ab) / ab.dot(&ab)).clamp(Scalar::ZERO, Scalar::ONE);
+    (point - (a + ab * t)).magnitude()
+}
@@ -10,6 +10,8 @@ pub mod sketch;
 pub mod solid;
 pub mod tolerance;
 
+mod surface;
+
 use std::{
     cmp::Ordering,
     fmt::Debug,
@@ -27,14 +27,26 @@
 //! As a result, path approximation is guaranteed to generate points that can
 //! fit together in a valid mesh, no matter which ranges of a path are being
 //! approximated, and how many times.
+//!
+//! Ellipses are approximated just like circles, with points that are evenly
+//! spaced in curve coordinates. The increment is the one a circle with the
+//! ellipse's major radius would get, so the set of points only depends on the
+//! ellipse and the tolerance too.
+//!
+//! NURBS curves are approximated with a finite set of points, since they are
+//! only defined within their domain. Each knot span is divided into segments
+//! of equal length, whose number depends only on the curve within that span
+//! and the tolerance. The range that is being approximated selects a subset of
+//! those points, as described above.
 
 use std::iter;
 
-use fj_math::{Circle, Point, Scalar, Sign};
+use fj_interop::ext::ArrayExt;
+use fj_math::{Circle, Ellipse, Helix, Line, NurbsCurve, Point, Scalar, Sign};
 
 use crate::geometry::{CurveBoundary, GlobalPath, SurfacePath};
 
-use super::{Approx, Tolerance};
+use super::{surface::SurfaceApproxParams, Approx, Tolerance};
 
 impl Approx for (&SurfacePath, CurveBoundary<Point<1>>) {
     type Approximation = Vec<(Point<1>, Point<2>)>;
@@ -51,7 +63,13 @@ impl Approx for (&SurfacePath, CurveBoundary<Point<1>>) {
             SurfacePath::Circle(circle) => {
                 approx_circle(circle, range, tolerance.into())
             }
+            SurfacePath::Ellipse(ellipse) => {
+                approx_ellipse(ellipse, range, tolerance.into())
+            }
             SurfacePath::Line(_) => vec![],
+            SurfacePath::Nurbs(curve) => {
+                approx_nurbs(curve, range, tolerance.into())
+            }
         }
     }
 }
@@ -71,7 +89,16 @@ impl Approx for (GlobalPath, CurveBoundary<Point<1>>) {
             GlobalPath::Circle(circle) => {
                 approx_circle(&circle, range, tolerance.into())
             }
+            GlobalPath::Ellipse(ellipse) => {
+                approx_ellipse(&ellipse, range, tolerance.into())
+            }
+            GlobalPath::Helix(helix) => {
+                approx_helix(&helix, range, tolerance.into())
+            }
             GlobalPath::Line(_) => vec![],
+            GlobalPath::Nurbs(curve) => {
+                approx_nurbs(&curve, range, tolerance.into())
+            }
         }
     }
 }
@@ -98,7 +125,140 @@ fn approx_circle<const D: usize>(
     points
 }
 
-struct PathApproxParams {
+/// Approximate a helix
+///
+/// A helix is less curved than the circle that is its projection along the
+/// axis. Spacing the points like they would be for that circle keeps the
+/// approximation within the tolerance.
+fn approx_helix(
+    helix: &Helix,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<3>)> {
+    let boundary = boundary.into();
+
+    let params = PathApproxParams::for_circle(&helix.circle(), tolerance);
+    let mut points = Vec::new();
+
+    for point_curve in params.points(boundary) {
+        let point_global = helix.point_from_helix_coords(point_curve);
+        points.push((point_curve, point_global));
+    }
+
+    points
+}
+
+/// Approximate an ellipse
+///
+/// `tolerance` specifies how much the approximation is allowed to deviate
+/// from the ellipse.
+fn approx_ellipse<const D: usize>(
+    ellipse: &Ellipse<D>,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<D>)> {
+    let boundary = boundary.into();
+
+    let params = PathApproxParams::for_ellipse(ellipse, tolerance);
+    let mut points = Vec::new();
+
+    for point_curve in params.points(boundary) {
+        let point_global = ellipse.point_from_ellipse_coords(point_curve);
+        points.push((point_curve, point_global));
+    }
+
+    points
+}
+
+/// Approximate a NURBS curve
+///
+/// `tolerance` specifies how much the approximation is allowed to deviate
+/// from the curve.
+fn approx_nurbs<const D: usize>(
+    curve: &NurbsCurve<D>,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<D>)> {
+    let boundary = boundary.into();
+
+    let [a, b] = boundary.inner.map(|point| point.t);
+    let [min, max] = if a < b { [a, b] } else { [b, a] };
+
+    // We can't generate a point exactly at the boundaries of the range as part
+    // of the approximation. Make sure we stay inside the range.
+    let mut points = nurbs_curve_coords(curve, tolerance)
+        .into_iter()
+        .filter(|t| min < *t && *t < max)
+        .map(|t| {
+            let point_curve = Point::from([t]);
+            let point_global = curve.point_from_nurbs_coords(point_curve);
+            (point_curve, point_global)
+        })
+        .collect::<Vec<_>>();
+
+    if a > b {
+        points.reverse();
+    }
+
+    points
+}
+
+/// Compute the curve coordinates of all points that approximate a NURBS curve
+///
+/// Returns the coordinates in ascending order, including the limits of the
+/// curve's domain.
+fn nurbs_curve_coords<const D: usize>(
+    curve: &NurbsCurve<D>,
+    tolerance: Tolerance,
+) -> Vec<Scalar> {
+    let [start, end] = curve.domain();
+
+    let mut knots = curve
+        .knots()
+        .iter()
+        .copied()
+        .filter(|knot| start <= *knot && *knot <= end)
+        .collect::<Vec<_>>();
+    knots.dedup();
+
+    let mut coords = Vec::new();
+
+    for span in knots.windows(2) {
+        let [a, b] = [span[0], span[1]];
+        let length = b - a;
+
+        // The distance between a segment of the curve and its chord is no
+        // larger than `length^2 / 8 * max(|C''|)`, where `length` is the length
+        // of the segment in curve coordinates. We estimate the maximum of the
+        // second derivative by sampling it within the span.
+        let num_samples = curve.degree() as u64 + 1;
+        let max_second_derivative = (0..=num_samples)
+            .map(|i| {
+                let t = a + length * Scalar::from_u64(i)
+                    / Scalar::from_u64(num_samples);
+                curve.derivatives([t], 2)[1].magnitude()
+            })
+            .max()
+            .expect("Sampled at least one point");
+
+        let num_segments = (length
+            * (max_second_derivative / (tolerance.inner() * 8.)).sqrt())
+        .ceil()
+        .max(Scalar::ONE);
+
+        let mut i = Scalar::ZERO;
+        while i < num_segments {
+            coords.push(a + length * i / num_segments);
+            i += Scalar::ONE;
+        }
+    }
+
+    coords.push(end);
+
+    coords
+}
+
+pub(super) struct PathApproxParams {
     increment: Scalar,
 }
 
@@ -107,8 +267,44 @@ impl PathApproxParams {
         circle: &Circle<D>,
         tolerance: impl Into<Tolerance>,
     ) -> Self {
-        let radius = circle.a().magnitude();
+        Self::for_radius(circle.a().magnitude(), tolerance)
+    }
 
+    /// Compute parameters for approximating an ellipse
+    ///
+    /// An ellipse is an affine image of a circle with its major radius, one
+    /// that doesn't stretch any distance. The distance between the ellipse and
+    /// the approximation is no larger than it would be for that circle.
+    pub fn for_ellipse<const D: usize>(
+        ellipse: &Ellipse<D>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
+        Self::for_radius(ellipse.major_radius(), tolerance)
+    }
+
+    /// Compute parameters for approximating a line in a curved surface
+    ///
+    /// The line is defined in surface coordinates. Each increment along the
+    /// line covers no more than one increment of the surface approximation,
+    /// along either axis of the surface.
+    pub fn for_line_in_surface(
+        line: &Line<2>,
+        surface: &SurfaceApproxParams,
+    ) -> Self {
+        let direction = line.direction();
+
+        let increment = [direction.u, direction.v]
+            .zip_ext(surface.increment())
+            .into_iter()
+            .filter(|(component, _)| *component != Scalar::ZERO)
+            .map(|(component, increment)| increment / component.abs())
+            .min()
+            .expect("Line direction must not be zero");
+
+        Self { increment }
+    }
+
+    pub fn for_radius(radius: Scalar, tolerance: impl Into<Tolerance>) -> Self {
         let num_vertices_to_approx_full_circle = Scalar::max(
             Scalar::PI
                 / (Scalar::ONE - (tolerance.into().inner() / radius)).acos(),
@@ -168,9 +364,12 @@ impl PathApproxParams {
 mod tests {
     use std::f64::consts::TAU;
 
-    use fj_math::{Circle, Point, Scalar};
+    use fj_math::{Circle, Ellipse, NurbsCurve, Point, Scalar};
 
-    use crate::algorithms::approx::{path::CurveBoundary, Tolerance};
+    use crate::{
+        algorithms::approx::{path::CurveBoundary, Approx, Tolerance},
+        geometry::SurfacePath,
+    };
 
     use super::PathApproxParams;
 
@@ -241,4 +440,83 @@ mod tests {
             assert_eq!(points, expected_points);
         }
     }
+
+    #[test]
+    fn points_for_ellipse() {
+        let tolerance = 0.1;
+
+        // `a` and `b` are not perpendicular, which makes this a bit more
+        // interesting.
+        let ellipse = Ellipse::new([1., 2.], [4., 0.], [1., 1.]);
+        let path = SurfacePath::Ellipse(ellipse);
+
+        let boundary = CurveBoundary::from([[0.], [TAU]]);
+        let points = (&path, boundary).approx(tolerance);
+
+        // The approximation must stay within the tolerance of the ellipse.
+        let points_with_boundary = [Point::from([0.])]
+            .into_iter()
+            .chain(points.iter().map(|(point_curve, _)| *point_curve))
+            .chain([Point::from([TAU])])
+            .collect::<Vec<_>>();
+        for segment in points_with_boundary.windows(2) {
+            let [a, b] = [segment[0], segment[1]];
+            let middle = Point::from([(a.t + b.t) / 2.]);
+
+            let [a, b, middle] =
+                [a, b, middle].map(|point| path.point_from_path_coords(point));
+            let distance = (a + (b - a) / 2. - middle).magnitude();
+
+            assert!(distance <= Scalar::from(tolerance));
+        }
+
+        // Approximating part of the ellipse must result in a subset of the
+        // points from approximating the whole.
+        let part = (&path, CurveBoundary::from([[1.], [3.]])).approx(tolerance);
+        assert!(!part.is_empty());
+        for point in part {
+            assert!(points.contains(&point));
+        }
+    }
+
+    #[test]
+    fn points_for_nurbs() {
+        let tolerance = 0.01;
+
+        let curve = NurbsCurve::from_control_points(
+            3,
+            [[0., 0.], [1., 2.], [2., -1.], [3., 3.], [4., 0.]],
+        );
+        let path = SurfacePath::Nurbs(curve.into());
+
+        let boundary = CurveBoundary::from([[0.], [1.]]);
+        let points = (&path, boundary).approx(tolerance);
+
+        // The approximation must stay within the tolerance of the curve.
+        let points_with_boundary = [Point::from([0.])]
+            .into_iter()
+            .chain(points.iter().map(|(point_curve, _)| *point_curve))
+            .chain([Point::from([1.])])
+            .collect::<Vec<_>>();
+        for segment in points_with_boundary.windows(2) {
+            let [a, b] = [segment[0], segment[1]];
+            let middle = Point::from([(a.t + b.t) / 2.]);
+
+            let [a, b, middle] =
+                [a, b, middle].map(|point| path.point_from_path_coords(point));
+            let distance = (a + (b - a) / 2. - middle).magnitude();
+
+            assert!(distance <= Scalar::from(tolerance));
+        }
+
+        // Approximating part of the curve must result in a subset of the
+        // points from approximating the whole, in the requested direction.
+        let part =
+            (&path, CurveBoundary::from([[0.8], [0.2]])).approx(tolerance);
+        assert!(!part.is_empty());
+        for point in &part {
+            assert!(points.contains(point));
+        }
+        assert!(part.windows(2).all(|pair| pair[0].0 > pair[1].0));
+    }
 }
@@ -2,19 +2,169 @@
 
 use std::collections::BTreeSet;
 
-use crate::objects::Sketch;
+use crate::{
+    objects::{Face, Sketch, Surface},
+    storage::Handle,
+};
 
 use super::{edge::EdgeApproxCache, face::FaceApprox, Approx, Tolerance};
 
-impl Approx for &Sketch {
+impl Approx for (&Sketch, Handle<Surface>) {
     type Approximation = BTreeSet<FaceApprox>;
     type Cache = EdgeApproxCache;
 
     fn approx_with_cache(
         self,
-        _tolerance: impl Into<Tolerance>,
-        _cache: &mut Self::Cache,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut Self::Cache,
     ) -> Self::Approximation {
-        todo!()
+        let (sketch, surface) = self;
+        let tolerance = tolerance.into();
+
+        // A sketch doesn't have a surface of its own. Each of its regions is
+        // approximated like a face, as it would be defined on the surface.
+        sketch
+            .regions()
+            .iter()
+            .map(|region| {
+                Face::new(surface.clone(), region.clone())
+                    .approx_with_cache(tolerance, cache)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        algorithms::{
+            approx::{Approx, Tolerance},
+            triangulate::Triangulate,
+        },
+        objects::{Cycle, Region, Sketch},
+        operations::{
+            BuildCycle, BuildRegion, BuildSketch, Insert, UpdateRegion,
+            UpdateSketch,
+        },
+        services::Services,
+    };
+
+    #[test]
+    fn triangulate_sketch() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let square = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut services,
+        )
+        .insert(&mut services);
+        let circle =
+            Region::circle([3., 0.], 1., &mut services).insert(&mut services);
+        let sketch = Sketch::empty()
+            .add_region(square)
+            .add_region(circle)
+            .insert(&mut services);
+        let surface = services.objects.surfaces.xz_plane();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let approx = (&*sketch, surface.clone()).approx(tolerance);
+        let num_points = approx
+            .iter()
+            .map(|face| face.points().len())
+            .collect::<Vec<_>>();
+        let mesh = ((&*sketch, surface), tolerance).triangulate();
+
+        // Each region is a polygon without holes, which is split into two
+        // triangles less than it has points.
+        assert_eq!(approx.len(), 2);
+        assert!(num_points.contains(&4));
+        assert_eq!(
+            mesh.triangles().count(),
+            num_points.iter().map(|num| num - 2).sum::<usize>()
+        );
+        for triangle in mesh.triangles() {
+            for point in triangle.inner.points() {
+                assert_eq!(point.y, Scalar::ZERO);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn triangulate_sketch_with_hole() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let region = Region::polygon(
+            [[0., 0.], [4., 0.], [4., 4.], [0., 4.]],
+            &mut services,
+        )
+        .add_interiors([Cycle::polygon(
+            [[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+            &mut services,
+        )
+        .insert(&mut services)])
+        .insert(&mut services);
+        let sketch = Sketch::empty().add_region(region).insert(&mut services);
+        let surface = services.objects.surfaces.xy_plane();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let mesh = ((&*sketch, surface), tolerance).triangulate();
+
+        // A polygon with 8 points and one hole is split into 8 triangles, none
+        // of which cover the hole.
+        assert_eq!(mesh.triangles().count(), 8);
+        for triangle in mesh.triangles() {
+            let center = triangle
+                .inner
+                .points()
+                .into_iter()
+                .fold(Point::origin(), |center, point| {
+                    center + point.coords / 3.
+                });
+
+            let is_in_hole = [center.x, center.y]
+                .into_iter()
+                .all(|coord| coord > Scalar::ONE && coord < Scalar::from(3.));
+            assert!(!is_in_hole);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn approx_circle_within_tolerance() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let circle =
+            Region::circle([3., 0.], 1., &mut services).insert(&mut services);
+        let sketch = Sketch::empty().add_region(circle).insert(&mut services);
+        let surface = services.objects.surfaces.xz_plane();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let approx = (&*sketch, surface).approx(tolerance);
+        let face = approx.first().expect("Sketch has one region");
+
+        // The points are located on the circle, and the segments between them
+        // deviate from it by no more than the tolerance.
+        let center = Point::from([3., 0., 0.]);
+        let segments = face.exterior.segments();
+        assert!(segments.len() > 4);
+        for segment in segments {
+            let [a, b] = segment.points();
+
+            for point in [a, b] {
+                let distance = point.distance_to(&center) - Scalar::ONE;
+                assert!(distance.abs() < Scalar::from(1e-12));
+            }
+
+            let midpoint = a + (b - a) / 2.;
+            let deviation = Scalar::ONE - midpoint.distance_to(&center);
+            assert!(deviation <= tolerance.inner());
+        }
+
+        Ok(())
     }
 }
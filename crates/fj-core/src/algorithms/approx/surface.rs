@@ -0,0 +1,153 @@
+//! # Surface approximation
+//!
+//! Only surfaces whose curvature is not defined by the edges that bound their
+//! faces need to be approximated. Faces on a sphere, for example, need points
+//! from the inside of the face, while the circles that bound a face on a
+//! cylinder are all it takes to approximate that face.
+//!
+//! NURBS surfaces are approximated on a grid that covers the whole surface.
+//! The increments of that grid are derived from the largest second derivatives
+//! of the surface, which are estimated by sampling each of its knot spans.
+
+use fj_interop::ext::ArrayExt;
+use fj_math::{Aabb, NurbsSurface, Scalar};
+
+use crate::geometry::SurfaceGeometry;
+
+use super::{path::PathApproxParams, Tolerance};
+
+/// Parameters for approximating part of a surface
+///
+/// The approximation places points on a grid in surface coordinates, using the
+/// increments that these parameters define for both axes.
+pub(super) struct SurfaceApproxParams {
+    increment: [Scalar; 2],
+}
+
+impl SurfaceApproxParams {
+    /// Compute parameters for approximating a region of the surface
+    ///
+    /// `aabb` bounds the region in surface coordinates. Returns `None`, if the
+    /// surface doesn't need to be approximated.
+    pub fn for_region(
+        surface: &SurfaceGeometry,
+        aabb: &Aabb<2>,
+        tolerance: impl Into<Tolerance>,
+    ) -> Option<Self> {
+        let tolerance = tolerance.into();
+
+        // A triangle between points that are one increment apart along both
+        // axes spans a larger angle than an increment along either axis does.
+        // Halving the increment that a circle of the same radius would get,
+        // keeps those triangles within the tolerance too.
+        let increment_for_radius = |radius: Scalar| {
+            let radius = radius.max(tolerance.inner());
+            PathApproxParams::for_radius(radius, tolerance).increment() / 2.
+        };
+
+        let increment = match surface {
+            SurfaceGeometry::Swept { .. } => return None,
+            SurfaceGeometry::Sphere(sphere) => {
+                let increment = increment_for_radius(sphere.radius());
+                [increment, increment]
+            }
+            SurfaceGeometry::Cone(cone) => {
+                // The cone is widest at one of the ends of the region.
+                let radius =
+                    cone.radius_at(aabb.min.v).max(cone.radius_at(aabb.max.v));
+                let increment_u = increment_for_radius(radius);
+
+                // The cone is straight along its v-axis, so the increment
+                // along that axis doesn't affect the tolerance. It only serves
+                // to keep the triangles of the approximation well-shaped.
+                let increment_v = increment_u * radius.max(tolerance.inner())
+                    / cone.slant_height();
+
+                [increment_u, increment_v]
+            }
+            SurfaceGeometry::Torus(torus) => [
+                increment_for_radius(
+                    torus.major_radius() + torus.minor_radius(),
+                ),
+                increment_for_radius(torus.minor_radius()),
+            ],
+            SurfaceGeometry::Nurbs(surface) => {
+                // The distance between a triangle and the surface is no larger
+                // than `(h_u^2 * M_uu + 2 * h_u * h_v * M_uv + h_v^2 * M_vv) / 8`,
+                // where `h_u` and `h_v` are the increments, and the `M`s are
+                // the maxima of the second derivatives. Choosing the increments
+                // like this keeps that within the tolerance.
+                let [max_uu, max_uv, max_vv] = max_second_derivatives(surface);
+
+                [max_uu.max(max_uv), max_vv.max(max_uv)]
+                    .zip_ext(surface.domain())
+                    .map(|(max, [min_coord, max_coord])| {
+                        let length = max_coord - min_coord;
+
+                        if max == Scalar::ZERO {
+                            // The surface is flat along this axis.
+                            return length;
+                        }
+
+                        let increment = (tolerance.inner() * 2. / max).sqrt();
+                        increment.min(length)
+                    })
+            }
+        };
+
+        Some(Self { increment })
+    }
+
+    /// Access the increments along both axes
+    pub fn increment(&self) -> [Scalar; 2] {
+        self.increment
+    }
+}
+
+/// Estimate the maxima of the second derivatives of a NURBS surface
+///
+/// Returns the maximum magnitude of the derivative along the u-axis, along both
+/// axes, and along the v-axis, in that order.
+fn max_second_derivatives(surface: &NurbsSurface) -> [Scalar; 3] {
+    let [coords_u, coords_v] = [0, 1].map(|axis| {
+        let [min, max] = surface.domain()[axis];
+        let num_samples = surface.degrees()[axis] as u64 + 1;
+
+        let mut knots = surface.knots()[axis]
+            .iter()
+            .copied()
+            .filter(|knot| min <= *knot && *knot <= max)
+            .collect::<Vec<_>>();
+        knots.dedup();
+
+        let mut coords = Vec::new();
+        for span in knots.windows(2) {
+            let [a, b] = [span[0], span[1]];
+
+            for i in 0..=num_samples {
+                coords.push(
+                    a + (b - a) * Scalar::from_u64(i)
+                        / Scalar::from_u64(num_samples),
+                );
+            }
+        }
+
+        coords
+    });
+
+    let mut maxima = [Scalar::ZERO; 3];
+
+    for u in &coords_u {
+        for v in &coords_v {
+            let derivatives = surface.derivatives([*u, *v], 2);
+            let second =
+                [derivatives[2][0], derivatives[1][1], derivatives[0][2]];
+
+            for (max, derivative) in maxima.iter_mut().zip(second) {
+                *max = (*max).max(derivative.magnitude());
+            }
+        }
+    }
+
+    maxima
+}
@@ -17,6 +17,15 @@ impl super::BoundingVolume<2> for Edge {
                     max: circle.center() + center_to_min_max,
                 })
             }
+            SurfacePath::Ellipse(ellipse) => {
+                // Same as for circles, this is the AABB of the whole ellipse.
+                Some(ellipse.aabb())
+            }
+            SurfacePath::Nurbs(curve) => {
+                // The curve is contained in the convex hull of its control
+                // points, so their AABB is good enough.
+                Some(curve.aabb())
+            }
             SurfacePath::Line(_) => {
                 let points = self.boundary().inner.map(|point_curve| {
                     self.path().point_from_path_coords(point_curve)
@@ -1,29 +1,76 @@
 use fj_math::Aabb;
 
-use crate::{geometry::GlobalPath, objects::Face};
+use crate::{
+    geometry::{GlobalPath, SurfaceGeometry},
+    objects::Face,
+};
 
 impl super::BoundingVolume<3> for Face {
     fn aabb(&self) -> Option<Aabb<3>> {
         self.region().exterior().aabb().map(|aabb2| {
             let surface = self.surface().geometry();
 
-            match surface.u {
-                GlobalPath::Circle(circle) => {
+            match surface {
+                SurfaceGeometry::Swept {
+                    u:
+                        u @ (GlobalPath::Circle(_)
+                        | GlobalPath::Ellipse(_)
+                        | GlobalPath::Helix(_)
+                        | GlobalPath::Nurbs(_)),
+                    v,
+                } => {
                     // This is not the most precise way to calculate the AABB,
-                    // doing it for the whole circle, but it should do.
+                    // doing it for the whole curve, but it should do.
 
-                    let aabb_bottom = circle.aabb();
-                    let aabb_top = Aabb {
-                        min: aabb_bottom.min + surface.v,
-                        max: aabb_bottom.max + surface.v,
+                    let aabb_u = match u {
+                        GlobalPath::Circle(circle) => circle.aabb(),
+                        GlobalPath::Ellipse(ellipse) => ellipse.aabb(),
+                        GlobalPath::Helix(helix) => {
+                            helix.aabb([aabb2.min.u, aabb2.max.u])
+                        }
+                        GlobalPath::Nurbs(curve) => curve.aabb(),
+                        GlobalPath::Line(_) => {
+                            unreachable!("Only matching curves in this arm")
+                        }
                     };
+                    let [aabb_bottom, aabb_top] = [aabb2.min.v, aabb2.max.v]
+                        .map(|coord| Aabb {
+                            min: aabb_u.min + v * coord,
+                            max: aabb_u.max + v * coord,
+                        });
 
                     aabb_bottom.merged(&aabb_top)
                 }
-                GlobalPath::Line(_) => Aabb {
-                    min: surface.point_from_surface_coords(aabb2.min),
-                    max: surface.point_from_surface_coords(aabb2.max),
-                },
+                SurfaceGeometry::Swept {
+                    u: GlobalPath::Line(_),
+                    ..
+                } => {
+                    // The corners of the 2D AABB don't necessarily map to the
+                    // corners of the 3D one, so we need to consider all of
+                    // them.
+                    let [min, max] = [aabb2.min, aabb2.max];
+                    let corners = [
+                        [min.u, min.v],
+                        [min.u, max.v],
+                        [max.u, min.v],
+                        [max.u, max.v],
+                    ];
+
+                    Aabb::<3>::from_points(
+                        corners.map(|point| {
+                            surface.point_from_surface_coords(point)
+                        }),
+                    )
+                }
+                SurfaceGeometry::Sphere(sphere) => {
+                    // Same as for the circle above, this is not very precise.
+                    sphere.aabb()
+                }
+                SurfaceGeometry::Cone(cone) => {
+                    cone.aabb([aabb2.min.v, aabb2.max.v])
+                }
+                SurfaceGeometry::Torus(torus) => torus.aabb(),
+                SurfaceGeometry::Nurbs(surface) => surface.aabb(),
             }
         })
     }
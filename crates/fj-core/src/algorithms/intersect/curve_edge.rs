@@ -1,6 +1,12 @@
-use fj_math::{Point, Segment};
+use fj_interop::ext::ArrayExt;
+use fj_math::{
+    Circle, Ellipse, Line, NurbsCurve, Point, Scalar, Segment, Vector,
+};
 
-use crate::{geometry::SurfacePath, objects::Edge};
+use crate::{
+    geometry::{CurveBoundary, SurfacePath},
+    objects::Edge,
+};
 
 use super::LineSegmentIntersection;
 
@@ -21,55 +27,711 @@ pub enum CurveEdgeIntersection {
 }
 
 impl CurveEdgeIntersection {
-    /// Compute the intersection
+    /// Compute the intersections
     ///
-    /// # Panics
+    /// A curve can intersect an [`Edge`] more than once, if either of them is
+    /// curved. All intersections are returned, in no particular order. The
+    /// curve coordinates of intersections on a circle or ellipse are between
+    /// `0.` (inclusive) and `PI * 2.` (exclusive), unless the edge is a full
+    /// circle that lies on the curve.
     ///
-    /// Currently, only intersections between lines and line segments can be
-    /// computed. Panics, if a different type of curve or [`Edge`] is passed.
-    pub fn compute(path: &SurfacePath, edge: &Edge) -> Option<Self> {
-        let path_as_line = match path {
-            SurfacePath::Line(line) => line,
-            _ => todo!("Curve-edge intersection only supports lines"),
-        };
+    /// Intersections between lines, circles, and ellipses are computed
+    /// directly, where possible. All other combinations, including all that
+    /// involve NURBS curves, are found numerically. Places where the curve
+    /// only touches the edge, without crossing it, might be missed by that. An
+    /// edge that is a NURBS curve is not detected as coincident with the curve.
+    pub fn compute(path: &SurfacePath, edge: &Edge) -> Vec<Self> {
+        match (path, edge.path()) {
+            (SurfacePath::Line(line), SurfacePath::Line(edge_line)) => {
+                let edge_as_segment = Segment::from_points(
+                    edge.boundary()
+                        .inner
+                        .map(|point| edge_line.point_from_line_coords(point)),
+                );
+
+                let Some(intersection) =
+                    LineSegmentIntersection::compute(line, &edge_as_segment)
+                else {
+                    return Vec::new();
+                };
+
+                let intersection = match intersection {
+                    LineSegmentIntersection::Point { point_on_line } => {
+                        Self::Point {
+                            point_on_curve: point_on_line,
+                        }
+                    }
+                    LineSegmentIntersection::Coincident { points_on_line } => {
+                        Self::Coincident {
+                            points_on_curve: points_on_line,
+                        }
+                    }
+                };
+
+                vec![intersection]
+            }
+            (SurfacePath::Line(line), SurfacePath::Circle(edge_circle)) => {
+                line_circle_intersections(line, &edge_circle)
+                    .into_iter()
+                    .filter(|t| {
+                        let point = line.point_from_line_coords([*t]);
+                        let angle = edge_circle.point_to_circle_coords(point);
+                        arc_contains(angle, edge.boundary())
+                    })
+                    .map(|t| Self::Point {
+                        point_on_curve: Point::from([t]),
+                    })
+                    .collect()
+            }
+            (SurfacePath::Circle(circle), SurfacePath::Line(edge_line)) => {
+                let edge_as_line = edge_as_line(&edge_line, edge);
+
+                line_circle_intersections(&edge_as_line, circle)
+                    .into_iter()
+                    .filter(|s| {
+                        *s >= -epsilon() && *s <= Scalar::ONE + epsilon()
+                    })
+                    .map(|s| Self::Point {
+                        point_on_curve: circle.point_to_circle_coords(
+                            edge_as_line.point_from_line_coords([s]),
+                        ),
+                    })
+                    .collect()
+            }
+            (SurfacePath::Circle(circle), SurfacePath::Circle(edge_circle)) => {
+                if (circle.center() - edge_circle.center()).magnitude()
+                    <= epsilon()
+                    && (circle.radius() - edge_circle.radius()).abs()
+                        <= epsilon()
+                {
+                    let [start, end] = edge.boundary().inner;
+
+                    let points_on_curve = if (end - start).magnitude()
+                        >= Scalar::TAU
+                    {
+                        [Scalar::ZERO, Scalar::TAU].map(|t| Point::from([t]))
+                    } else {
+                        [start, end].map(|point| {
+                            circle.point_to_circle_coords(
+                                edge_circle.point_from_circle_coords(point),
+                            )
+                        })
+                    };
 
-        let edge_as_segment = {
-            let edge_path_as_line = match edge.path() {
-                SurfacePath::Line(line) => line,
-                _ => {
-                    todo!("Curve-edge intersection only supports line segments")
+                    return vec![Self::Coincident { points_on_curve }];
                 }
+
+                circle_circle_intersections(circle, &edge_circle)
+                    .into_iter()
+                    .filter(|point| {
+                        let angle = edge_circle.point_to_circle_coords(*point);
+                        arc_contains(angle, edge.boundary())
+                    })
+                    .map(|point| Self::Point {
+                        point_on_curve: circle.point_to_circle_coords(point),
+                    })
+                    .collect()
+            }
+            (SurfacePath::Line(line), SurfacePath::Ellipse(edge_ellipse)) => {
+                line_ellipse_intersections(line, &edge_ellipse)
+                    .into_iter()
+                    .filter(|t| {
+                        let point = line.point_from_line_coords([*t]);
+                        let angle = edge_ellipse.point_to_ellipse_coords(point);
+                        arc_contains(angle, edge.boundary())
+                    })
+                    .map(|t| Self::Point {
+                        point_on_curve: Point::from([t]),
+                    })
+                    .collect()
+            }
+            (SurfacePath::Ellipse(ellipse), SurfacePath::Line(edge_line)) => {
+                let edge_as_line = edge_as_line(&edge_line, edge);
+
+                line_ellipse_intersections(&edge_as_line, ellipse)
+                    .into_iter()
+                    .filter(|s| {
+                        *s >= -epsilon() && *s <= Scalar::ONE + epsilon()
+                    })
+                    .map(|s| Self::Point {
+                        point_on_curve: ellipse.point_to_ellipse_coords(
+                            edge_as_line.point_from_line_coords([s]),
+                        ),
+                    })
+                    .collect()
+            }
+            (SurfacePath::Nurbs(curve), SurfacePath::Line(edge_line)) => {
+                let edge_as_line = edge_as_line(&edge_line, edge);
+
+                nurbs_line_intersections(curve, &edge_as_line)
+                    .into_iter()
+                    .filter(|t| {
+                        let point = curve.point_from_nurbs_coords([*t]);
+                        let s = edge_as_line.point_to_line_coords(point).t;
+                        s >= -epsilon() && s <= Scalar::ONE + epsilon()
+                    })
+                    .map(|t| Self::Point {
+                        point_on_curve: Point::from([t]),
+                    })
+                    .collect()
+            }
+            _ => Self::compute_numerically(path, edge),
+        }
+    }
+
+    /// Compute the intersections numerically
+    ///
+    /// If the curve can be represented implicitly, the edge is sampled, to
+    /// find the places where it crosses the curve. Otherwise, the curve is a
+    /// NURBS curve, and it is sampled against the edge instead.
+    fn compute_numerically(path: &SurfacePath, edge: &Edge) -> Vec<Self> {
+        let edge_path = edge.path();
+        let boundary = edge.boundary();
+
+        if let Some(curve) = ImplicitCurve::from_path(path) {
+            let samples =
+                path_samples(&edge_path, boundary.inner.map(|point| point.t));
+            let distance = |s: Scalar| {
+                curve.distance(edge_path.point_from_path_coords([s]))
             };
 
-            let edge_vertices = edge
-                .boundary()
-                .inner
-                .map(|point| edge_path_as_line.point_from_line_coords(point));
+            if samples.iter().all(|&s| distance(s).abs() <= epsilon()) {
+                let [start, end] = boundary.inner;
+                let is_closed = |path: &SurfacePath| {
+                    matches!(
+                        path,
+                        SurfacePath::Circle(_) | SurfacePath::Ellipse(_)
+                    )
+                };
 
-            Segment::from_points(edge_vertices)
+                let points_on_curve = if is_closed(path)
+                    && is_closed(&edge_path)
+                    && (end - start).magnitude() >= Scalar::TAU
+                {
+                    [Scalar::ZERO, Scalar::TAU].map(|t| Point::from([t]))
+                } else {
+                    [start, end].map(|point| {
+                        curve.point_to_curve_coords(
+                            edge_path.point_from_path_coords(point),
+                        )
+                    })
+                };
+
+                return vec![Self::Coincident { points_on_curve }];
+            }
+
+            return find_roots(&samples, distance)
+                .into_iter()
+                .map(|s| Self::Point {
+                    point_on_curve: curve.point_to_curve_coords(
+                        edge_path.point_from_path_coords([s]),
+                    ),
+                })
+                .collect();
+        }
+
+        let SurfacePath::Nurbs(curve) = path else {
+            unreachable!("Only NURBS curves have no implicit representation");
         };
 
-        let intersection =
-            LineSegmentIntersection::compute(path_as_line, &edge_as_segment)?;
+        let intersections = match ImplicitCurve::from_path(&edge_path) {
+            Some(edge_curve) => {
+                let samples = path_samples(path, curve.domain());
+                let distance = |t: Scalar| {
+                    edge_curve.distance(curve.point_from_nurbs_coords([t]))
+                };
 
-        let intersection = match intersection {
-            LineSegmentIntersection::Point { point_on_line } => Self::Point {
-                point_on_curve: point_on_line,
-            },
-            LineSegmentIntersection::Coincident { points_on_line } => {
-                Self::Coincident {
-                    points_on_curve: points_on_line,
-                }
+                find_roots(&samples, distance)
+                    .into_iter()
+                    .filter(|&t| {
+                        edge_curve.contains(
+                            curve.point_from_nurbs_coords([t]),
+                            boundary,
+                        )
+                    })
+                    .collect()
+            }
+            None => {
+                let SurfacePath::Nurbs(edge_curve) = edge_path else {
+                    unreachable!(
+                        "Only NURBS curves have no implicit representation"
+                    );
+                };
+
+                nurbs_nurbs_intersections(
+                    curve,
+                    &edge_curve,
+                    boundary.inner.map(|point| point.t),
+                )
             }
         };
 
-        Some(intersection)
+        intersections
+            .into_iter()
+            .map(|t| Self::Point {
+                point_on_curve: Point::from([t]),
+            })
+            .collect()
     }
 }
 
+/// The tolerance used when checking, whether an intersection is on an edge
+///
+/// Intersections with a vertex that is shared between two edges might come
+/// out as slightly outside of both edges, due to floating point inaccuracies.
+/// This allows for that, so they aren't missed.
+fn epsilon() -> Scalar {
+    Scalar::from_f64(1e-12)
+}
+
+/// Compute the line coordinates where a line intersects a circle
+pub(super) fn line_circle_intersections(
+    line: &Line<2>,
+    circle: &Circle<2>,
+) -> Vec<Scalar> {
+    // Solve `|origin + direction * t - center| = radius` for `t`.
+    let to_origin = line.origin() - circle.center();
+
+    let a = line.direction().dot(&line.direction());
+    let b = line.direction().dot(&to_origin) * 2.;
+    let c = to_origin.dot(&to_origin) - circle.radius() * circle.radius();
+
+    let discriminant = b * b - a * c * 4.;
+
+    if discriminant < Scalar::ZERO {
+        return Vec::new();
+    }
+    if discriminant == Scalar::ZERO {
+        return vec![-b / (a * 2.)];
+    }
+
+    let root = discriminant.sqrt();
+    vec![(-b - root) / (a * 2.), (-b + root) / (a * 2.)]
+}
+
+/// Compute the points where two circles intersect
+///
+/// Circles that are identical are not handled here, and result in no
+/// intersections.
+pub(super) fn circle_circle_intersections(
+    a: &Circle<2>,
+    b: &Circle<2>,
+) -> Vec<Point<2>> {
+    let center_to_center = b.center() - a.center();
+    let distance = center_to_center.magnitude();
+
+    if distance == Scalar::ZERO
+        || distance > a.radius() + b.radius()
+        || distance < (a.radius() - b.radius()).abs()
+    {
+        return Vec::new();
+    }
+
+    // The distance from the center of `a` to the line through both
+    // intersections, and the distance of the intersections from that line.
+    let along = (a.radius() * a.radius() - b.radius() * b.radius()
+        + distance * distance)
+        / (distance * 2.);
+    let across = (a.radius() * a.radius() - along * along)
+        .max(Scalar::ZERO)
+        .sqrt();
+
+    let direction = center_to_center / distance;
+    let base = a.center() + direction * along;
+
+    if across == Scalar::ZERO {
+        return vec![base];
+    }
+
+    let perpendicular = Vector::from([-direction.v, direction.u]) * across;
+    vec![base + perpendicular, base - perpendicular]
+}
+
+/// Represent an edge as a line, from coordinate `0` at its start to `1` at its end
+///
+/// This makes it easy to check which intersections are located within the
+/// edge.
+fn edge_as_line(edge_line: &Line<2>, edge: &Edge) -> Line<2> {
+    Line::from_points_with_line_coords(
+        [[0.], [1.]]
+            .zip_ext(edge.boundary().inner)
+            .map(|(coord, point)| {
+                (coord, edge_line.point_from_line_coords(point))
+            }),
+    )
+}
+
+/// Compute the line coordinates where a line intersects an ellipse
+fn line_ellipse_intersections(
+    line: &Line<2>,
+    ellipse: &Ellipse<2>,
+) -> Vec<Scalar> {
+    // In the coordinate system that is spanned by the axes of the ellipse, the
+    // ellipse is the unit circle. Lines stay lines, with the same coordinates.
+    let to_ellipse_coords =
+        |vector: Vector<2>| vector_to_ellipse_coords(ellipse, vector);
+
+    let line = Line::from_origin_and_direction(
+        Point::origin() + to_ellipse_coords(line.origin() - ellipse.center()),
+        to_ellipse_coords(line.direction()),
+    );
+    let unit_circle =
+        Circle::from_center_and_radius(Point::origin(), Scalar::ONE);
+
+    line_circle_intersections(&line, &unit_circle)
+}
+
+/// Express a vector in the coordinate system spanned by the axes of an ellipse
+///
+/// In that coordinate system, the ellipse is the unit circle.
+fn vector_to_ellipse_coords(
+    ellipse: &Ellipse<2>,
+    vector: Vector<2>,
+) -> Vector<2> {
+    let [a, b] = [ellipse.a(), ellipse.b()];
+    let det = a.u * b.v - a.v * b.u;
+
+    Vector::from([
+        (vector.u * b.v - vector.v * b.u) / det,
+        (a.u * vector.v - a.v * vector.u) / det,
+    ])
+}
+
+/// Compute the curve coordinates where a NURBS curve intersects a line
+///
+/// The curve is sampled, to find the places where it crosses the line. Those
+/// are then refined using bisection.
+fn nurbs_line_intersections(
+    curve: &NurbsCurve<2>,
+    line: &Line<2>,
+) -> Vec<Scalar> {
+    let normal = Vector::from([-line.direction().v, line.direction().u]);
+    let distance = |t: Scalar| {
+        (curve.point_from_nurbs_coords([t]) - line.origin()).dot(&normal)
+    };
+
+    find_roots(&nurbs_samples(curve, curve.domain()), distance)
+}
+
+/// Compute the curve coordinates where two NURBS curves intersect
+///
+/// Both curves are approximated by polylines, to find the places where they
+/// cross. Those are then refined using Newton's method. Only intersections
+/// within `range` of the second curve are returned.
+fn nurbs_nurbs_intersections(
+    curve: &NurbsCurve<2>,
+    edge_curve: &NurbsCurve<2>,
+    range: [Scalar; 2],
+) -> Vec<Scalar> {
+    const MAX_ITERATIONS: usize = 32;
+
+    let polyline = |curve: &NurbsCurve<2>, range: [Scalar; 2]| {
+        nurbs_samples(curve, range)
+            .into_iter()
+            .map(|t| (t, curve.point_from_nurbs_coords([t])))
+            .collect::<Vec<_>>()
+    };
+    let derivative = |curve: &NurbsCurve<2>, t: Scalar| {
+        let [derivative] = curve.derivatives([t], 1)[..] else {
+            unreachable!("Requested exactly one derivative");
+        };
+        derivative
+    };
+
+    let [min_t, max_t] = curve.domain();
+    let [min_s, max_s] = if range[0] <= range[1] {
+        range
+    } else {
+        [range[1], range[0]]
+    };
+
+    let mut intersections: Vec<Scalar> = Vec::new();
+    for a in polyline(curve, curve.domain()).windows(2) {
+        for b in polyline(edge_curve, range).windows(2) {
+            let Some([u, v]) = segment_segment_intersection(
+                [a[0].1, a[1].1],
+                [b[0].1, b[1].1],
+            ) else {
+                continue;
+            };
+
+            let mut t = a[0].0 + (a[1].0 - a[0].0) * u;
+            let mut s = b[0].0 + (b[1].0 - b[0].0) * v;
+
+            // Solve `curve(t) - edge_curve(s) = 0` for `t` and `s`.
+            for _ in 0..MAX_ITERATIONS {
+                let difference = curve.point_from_nurbs_coords([t])
+                    - edge_curve.point_from_nurbs_coords([s]);
+                let [c1, c2] =
+                    [derivative(curve, t), -derivative(edge_curve, s)];
+
+                let det = c1.u * c2.v - c1.v * c2.u;
+                if det == Scalar::ZERO {
+                    break;
+                }
+
+                let dt = (-difference.u * c2.v + difference.v * c2.u) / det;
+                let ds = (-c1.u * difference.v + c1.v * difference.u) / det;
+
+                t = (t + dt).max(min_t).min(max_t);
+                s = (s + ds).max(min_s).min(max_s);
+
+                if dt.abs() <= epsilon() && ds.abs() <= epsilon() {
+                    break;
+                }
+            }
+
+            let difference = curve.point_from_nurbs_coords([t])
+                - edge_curve.point_from_nurbs_coords([s]);
+            if difference.magnitude() > epsilon() {
+                continue;
+            }
+
+            // Neighboring segments can share an intersection, if it is
+            // located at one of their end points.
+            if intersections
+                .iter()
+                .any(|&other| (other - t).abs() <= epsilon() * 1000.)
+            {
+                continue;
+            }
+
+            intersections.push(t);
+        }
+    }
+
+    intersections
+}
+
+/// Compute where two line segments intersect
+///
+/// Returns the coordinates of the intersection on both segments, with `0` at
+/// their start and `1` at their end. Parallel segments result in no
+/// intersection.
+fn segment_segment_intersection(
+    [a_start, a_end]: [Point<2>; 2],
+    [b_start, b_end]: [Point<2>; 2],
+) -> Option<[Scalar; 2]> {
+    let [a, b] = [a_end - a_start, b_end - b_start];
+    let to_b = b_start - a_start;
+
+    let det = a.u * b.v - a.v * b.u;
+    if det == Scalar::ZERO {
+        return None;
+    }
+
+    let u = (to_b.u * b.v - to_b.v * b.u) / det;
+    let v = (to_b.u * a.v - to_b.v * a.u) / det;
+
+    let is_within = |x: Scalar| x >= -epsilon() && x <= Scalar::ONE + epsilon();
+    (is_within(u) && is_within(v)).then_some([u, v])
+}
+
+/// Sample a path within the given range of path coordinates
+///
+/// The samples are dense enough to find places where the path crosses another
+/// curve, as long as that is not curved much more tightly than the path.
+fn path_samples(path: &SurfacePath, range: [Scalar; 2]) -> Vec<Scalar> {
+    match path {
+        SurfacePath::Nurbs(curve) => nurbs_samples(curve, range),
+        _ => {
+            const NUM_SAMPLES: u64 = 64;
+
+            let [start, end] = range;
+            (0..=NUM_SAMPLES)
+                .map(|i| {
+                    start
+                        + (end - start) * Scalar::from_u64(i)
+                            / Scalar::from_u64(NUM_SAMPLES)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Sample a NURBS curve within the given range of curve coordinates
+///
+/// Each knot span within the range is sampled a number of times.
+fn nurbs_samples(curve: &NurbsCurve<2>, range: [Scalar; 2]) -> Vec<Scalar> {
+    const SAMPLES_PER_SPAN: u64 = 16;
+
+    // The curve can cross another curve multiple times per knot span, but if
+    // it does, it's curved enough that sampling each span a number of times
+    // finds those crossings.
+    let [start, end] = range;
+    let [min, max] = if start <= end {
+        [start, end]
+    } else {
+        [end, start]
+    };
+
+    let mut breaks = vec![start];
+    let mut knots = curve
+        .knots()
+        .iter()
+        .copied()
+        .filter(|knot| *knot > min && *knot < max)
+        .collect::<Vec<_>>();
+    knots.dedup();
+    if start > end {
+        knots.reverse();
+    }
+    breaks.extend(knots);
+    breaks.push(end);
+
+    let mut samples = Vec::new();
+    for span in breaks.windows(2) {
+        let [start, end] = [span[0], span[1]];
+        for i in 0..SAMPLES_PER_SPAN {
+            samples.push(
+                start
+                    + (end - start) * Scalar::from_u64(i)
+                        / Scalar::from_u64(SAMPLES_PER_SPAN),
+            );
+        }
+    }
+    samples.push(end);
+
+    samples
+}
+
+/// Find the places where a function changes its sign
+///
+/// Each pair of neighboring samples, at which the function has different
+/// signs, is refined using bisection.
+fn find_roots(
+    samples: &[Scalar],
+    function: impl Fn(Scalar) -> Scalar,
+) -> Vec<Scalar> {
+    const MAX_ITERATIONS: usize = 64;
+
+    let mut roots = Vec::new();
+    for window in samples.windows(2) {
+        let [mut a, mut b] = [window[0], window[1]];
+        let [mut value_a, value_b] = [a, b].map(&function);
+
+        if value_a == Scalar::ZERO {
+            roots.push(a);
+            continue;
+        }
+        if value_a.sign() == value_b.sign() || value_b == Scalar::ZERO {
+            continue;
+        }
+
+        for _ in 0..MAX_ITERATIONS {
+            let middle = a + (b - a) / 2.;
+            if middle == a || middle == b {
+                break;
+            }
+
+            let value_middle = function(middle);
+            if value_middle.sign() == value_a.sign() {
+                a = middle;
+                value_a = value_middle;
+            } else {
+                b = middle;
+            }
+        }
+
+        roots.push(a + (b - a) / 2.);
+    }
+    if let Some(&last) = samples.last() {
+        if function(last) == Scalar::ZERO {
+            roots.push(last);
+        }
+    }
+
+    roots
+}
+
+/// A curve that can be represented by an implicit equation
+#[derive(Clone, Copy)]
+enum ImplicitCurve {
+    Line(Line<2>),
+    Circle(Circle<2>),
+    Ellipse(Ellipse<2>),
+}
+
+impl ImplicitCurve {
+    fn from_path(path: &SurfacePath) -> Option<Self> {
+        match path {
+            SurfacePath::Line(line) => Some(Self::Line(*line)),
+            SurfacePath::Circle(circle) => Some(Self::Circle(*circle)),
+            SurfacePath::Ellipse(ellipse) => Some(Self::Ellipse(*ellipse)),
+            SurfacePath::Nurbs(_) => None,
+        }
+    }
+
+    /// Compute the signed distance of a point from the curve
+    ///
+    /// For ellipses, this is only an approximation of the distance, but it has
+    /// the right sign, and is zero for points on the ellipse.
+    fn distance(&self, point: Point<2>) -> Scalar {
+        match self {
+            Self::Line(line) => {
+                let direction = line.direction();
+                let normal = Vector::from([-direction.v, direction.u]);
+                (point - line.origin()).dot(&normal) / direction.magnitude()
+            }
+            Self::Circle(circle) => {
+                (point - circle.center()).magnitude() - circle.radius()
+            }
+            Self::Ellipse(ellipse) => {
+                let radius =
+                    ellipse.a().magnitude().min(ellipse.b().magnitude());
+                let vector =
+                    vector_to_ellipse_coords(ellipse, point - ellipse.center());
+                (vector.magnitude() - Scalar::ONE) * radius
+            }
+        }
+    }
+
+    fn point_to_curve_coords(&self, point: Point<2>) -> Point<1> {
+        match self {
+            Self::Line(line) => line.point_to_line_coords(point),
+            Self::Circle(circle) => circle.point_to_circle_coords(point),
+            Self::Ellipse(ellipse) => ellipse.point_to_ellipse_coords(point),
+        }
+    }
+
+    /// Check whether a point on the curve lies within the given boundary
+    fn contains(
+        &self,
+        point: Point<2>,
+        boundary: CurveBoundary<Point<1>>,
+    ) -> bool {
+        let point = self.point_to_curve_coords(point);
+
+        match self {
+            Self::Line(_) => {
+                let [min, max] = boundary.normalize().inner;
+                point.t >= min.t - epsilon() && point.t <= max.t + epsilon()
+            }
+            Self::Circle(_) | Self::Ellipse(_) => arc_contains(point, boundary),
+        }
+    }
+}
+
+/// Check whether a point on a circle or ellipse lies within an arc of it
+///
+/// Expects the angle of the point, in the curve coordinates of the circle or
+/// ellipse.
+fn arc_contains(angle: Point<1>, boundary: CurveBoundary<Point<1>>) -> bool {
+    let [min, max] = boundary.normalize().inner.map(|point| point.t);
+    let angle = angle.t;
+
+    // Move the angle into the full turn that starts at the start of the arc.
+    let turns = ((angle - min) / Scalar::TAU).floor();
+    let angle = angle - turns * Scalar::TAU;
+
+    angle <= max + epsilon() || angle >= min + Scalar::TAU - epsilon()
+}
+
 #[cfg(test)]
 mod tests {
-    use fj_math::Point;
+    use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+    use fj_math::{Ellipse, NurbsCurve, Point, Scalar};
 
     use crate::{
         geometry::SurfacePath, objects::Edge, operations::BuildEdge,
@@ -90,9 +752,9 @@ mod tests {
 
         assert_eq!(
             intersection,
-            Some(CurveEdgeIntersection::Point {
+            vec![CurveEdgeIntersection::Point {
                 point_on_curve: Point::from([1.])
-            })
+            }]
         );
     }
 
@@ -108,9 +770,9 @@ mod tests {
 
         assert_eq!(
             intersection,
-            Some(CurveEdgeIntersection::Point {
+            vec![CurveEdgeIntersection::Point {
                 point_on_curve: Point::from([-1.])
-            })
+            }]
         );
     }
 
@@ -124,7 +786,7 @@ mod tests {
 
         let intersection = CurveEdgeIntersection::compute(&path, &edge);
 
-        assert!(intersection.is_none());
+        assert!(intersection.is_empty());
     }
 
     #[test]
@@ -139,9 +801,267 @@ mod tests {
 
         assert_eq!(
             intersection,
-            Some(CurveEdgeIntersection::Coincident {
+            vec![CurveEdgeIntersection::Coincident {
                 points_on_curve: [Point::from([-1.]), Point::from([1.]),]
-            })
+            }]
+        );
+    }
+
+    #[test]
+    fn compute_circle_edge_crossing_line() {
+        let mut services = Services::new();
+
+        let path = SurfacePath::u_axis();
+        let edge = Edge::circle([0., 0.], 1., &mut services);
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        assert_points_on_curve(intersection, [-1., 1.]);
+    }
+
+    #[test]
+    fn compute_arc_crossing_line() {
+        let mut services = Services::new();
+
+        // Only one of the two points where the line crosses the circle is
+        // part of the arc.
+        let path = SurfacePath::u_axis();
+        let edge = Edge::unjoined(
+            SurfacePath::circle_from_center_and_radius([0., 0.], 1.),
+            [[-FRAC_PI_2], [FRAC_PI_2]],
+            &mut services,
+        );
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        assert_points_on_curve(intersection, [1.]);
+    }
+
+    #[test]
+    fn compute_edge_crossing_circle() {
+        let mut services = Services::new();
+
+        let path = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+        let edge =
+            Edge::line_segment([[0., -2.], [0., 2.]], None, &mut services);
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        assert_points_on_curve(intersection, [FRAC_PI_2, FRAC_PI_2 * 3.]);
+    }
+
+    #[test]
+    fn compute_circle_edge_crossing_circle() {
+        let mut services = Services::new();
+
+        let path = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+        let edge = Edge::circle([1., 0.], 1., &mut services);
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        assert_points_on_curve(intersection, [PI / 3., PI * 5. / 3.]);
+    }
+
+    #[test]
+    fn compute_arc_on_circle() {
+        let mut services = Services::new();
+
+        let path = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+        let edge = Edge::unjoined(path, [[1.], [2.]], &mut services);
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        let [CurveEdgeIntersection::Coincident { points_on_curve }] =
+            intersection.as_slice()
+        else {
+            panic!("Expected arc to be coincident with circle");
+        };
+        for (point, expected) in points_on_curve.iter().zip([1., 2.]) {
+            assert!((point.t - expected).abs() < Scalar::from(1e-12));
+        }
+    }
+
+    #[test]
+    fn compute_edge_crossing_ellipse() {
+        let mut services = Services::new();
+
+        let path = SurfacePath::Ellipse(Ellipse::from_center_and_radii(
+            [0., 0.],
+            [2., 1.],
+        ));
+        let edge =
+            Edge::line_segment([[1., -2.], [1., 2.]], None, &mut services);
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        assert_points_on_curve(intersection, [PI / 3., PI * 5. / 3.]);
+    }
+
+    #[test]
+    fn compute_ellipse_edge_crossing_line() {
+        let mut services = Services::new();
+
+        let path = SurfacePath::u_axis();
+        let ellipse = SurfacePath::Ellipse(Ellipse::from_center_and_radii(
+            [0., 0.],
+            [2., 1.],
+        ));
+        let edge = Edge::unjoined(ellipse, [[0.], [PI]], &mut services);
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        assert_points_on_curve(intersection, [-2., 2.]);
+    }
+
+    #[test]
+    fn compute_edge_crossing_nurbs() {
+        let mut services = Services::new();
+
+        // A parabola, which crosses the u-axis twice, where `6t(1 - t) = 1`.
+        let path = SurfacePath::Nurbs(
+            NurbsCurve::from_control_points(
+                2,
+                [[0., -1.], [1., 2.], [2., -1.]],
+            )
+            .into(),
+        );
+        let [a, b] = [0.5 - 3f64.sqrt() / 6., 0.5 + 3f64.sqrt() / 6.];
+
+        let edge =
+            Edge::line_segment([[-1., 0.], [3., 0.]], None, &mut services);
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+        assert_points_on_curve(intersection, [a, b]);
+
+        let edge =
+            Edge::line_segment([[-1., 0.], [1., 0.]], None, &mut services);
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+        assert_points_on_curve(intersection, [a]);
+    }
+
+    #[test]
+    fn compute_circle_edge_on_nearly_identical_circle() {
+        let mut services = Services::new();
+
+        // `0.1 + 0.2` is not exactly `0.3`, due to floating point inaccuracy.
+        let path =
+            SurfacePath::circle_from_center_and_radius([0.1 + 0.2, 0.], 1.);
+        let edge = Edge::circle([0.3, 0.], 1., &mut services);
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        assert_eq!(
+            intersection,
+            vec![CurveEdgeIntersection::Coincident {
+                points_on_curve: [Point::from([0.]), Point::from([TAU])]
+            }]
+        );
+    }
+
+    #[test]
+    fn compute_ellipse_edge_crossing_circle() {
+        let mut services = Services::new();
+
+        // The circle crosses the ellipse where `u^2 = 2` and `v^2 = 0.5`.
+        let path =
+            SurfacePath::circle_from_center_and_radius([0., 0.], 2.5f64.sqrt());
+        let ellipse = SurfacePath::Ellipse(Ellipse::from_center_and_radii(
+            [0., 0.],
+            [2., 1.],
+        ));
+        let edge = Edge::unjoined(ellipse, [[0.], [TAU]], &mut services);
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        let angle = 0.5f64.atan();
+        assert_points_on_curve(
+            intersection,
+            [angle, PI - angle, PI + angle, TAU - angle],
         );
     }
+
+    #[test]
+    fn compute_circle_edge_crossing_nurbs() {
+        let mut services = Services::new();
+
+        // A parabola, which crosses the circle twice, symmetrically.
+        let path = SurfacePath::Nurbs(
+            NurbsCurve::from_control_points(
+                2,
+                [[0., -1.], [1., 2.], [2., -1.]],
+            )
+            .into(),
+        );
+        let edge = Edge::circle([1., 0.], 1., &mut services);
+
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+
+        let [CurveEdgeIntersection::Point { point_on_curve: a }, CurveEdgeIntersection::Point { point_on_curve: b }] =
+            intersection.as_slice()
+        else {
+            panic!("Expected two intersection points, got {intersection:?}");
+        };
+        assert!((a.t + b.t - Scalar::ONE).abs() < Scalar::from(1e-12));
+        for point in [a, b] {
+            let point = path.point_from_path_coords(*point);
+            let distance = (point - Point::from([1., 0.])).magnitude();
+            assert!((distance - Scalar::ONE).abs() < Scalar::from(1e-12));
+        }
+    }
+
+    #[test]
+    fn compute_nurbs_edge_crossing_nurbs() {
+        let mut services = Services::new();
+
+        // Two parabolas, mirrored at the u-axis, which cross where
+        // `6t(1 - t) = 1`.
+        let path = SurfacePath::Nurbs(
+            NurbsCurve::from_control_points(
+                2,
+                [[0., -1.], [1., 2.], [2., -1.]],
+            )
+            .into(),
+        );
+        let mirrored = SurfacePath::Nurbs(
+            NurbsCurve::from_control_points(2, [[0., 1.], [1., -2.], [2., 1.]])
+                .into(),
+        );
+        let [a, b] = [0.5 - 3f64.sqrt() / 6., 0.5 + 3f64.sqrt() / 6.];
+
+        let edge = Edge::unjoined(mirrored, [[0.], [1.]], &mut services);
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+        assert_points_on_curve(intersection, [a, b]);
+
+        let edge = Edge::unjoined(mirrored, [[0.], [0.5]], &mut services);
+        let intersection = CurveEdgeIntersection::compute(&path, &edge);
+        assert_points_on_curve(intersection, [a]);
+    }
+
+    /// Check that the intersections are points at the expected coordinates
+    ///
+    /// The expected coordinates must be sorted.
+    fn assert_points_on_curve<const N: usize>(
+        intersections: Vec<CurveEdgeIntersection>,
+        expected: [f64; N],
+    ) {
+        let mut points = intersections
+            .into_iter()
+            .map(|intersection| {
+                let CurveEdgeIntersection::Point { point_on_curve } =
+                    intersection
+                else {
+                    panic!("Expected intersection to be a point");
+                };
+                point_on_curve.t
+            })
+            .collect::<Vec<_>>();
+        points.sort();
+
+        assert_eq!(points.len(), N, "Unexpected intersections: {points:?}");
+        for (point, expected) in points.into_iter().zip(expected) {
+            assert!(
+                (point - expected).abs() < Scalar::from(1e-12),
+                "Expected {expected}, got {point:?}"
+            );
+        }
+    }
 }
@@ -1,11 +1,11 @@
 use std::vec;
 
 use fj_interop::ext::SliceExt;
-use fj_math::Point;
+use fj_math::{Point, Scalar};
 
 use crate::{geometry::SurfacePath, objects::Face};
 
-use super::CurveEdgeIntersection;
+use super::{CurveEdgeIntersection, Intersect};
 
 /// The intersections between a curve and a [`Face`], in curve coordinates
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -28,15 +28,18 @@ impl CurveFaceIntersection {
     }
 
     /// Compute the intersection
+    ///
+    /// If the curve is a circle or an ellipse, the intervals are located between
+    /// `0.` and `PI * 2.`. An interval that wraps around the start of the curve
+    /// is split in two. If the curve is a NURBS curve, the intervals are
+    /// located within its domain.
     pub fn compute(path: &SurfacePath, face: &Face) -> Self {
         let edges = face.region().all_cycles().flat_map(|cycle| cycle.edges());
 
         let mut intersections = Vec::new();
 
         for edge in edges {
-            let intersection = CurveEdgeIntersection::compute(path, edge);
-
-            if let Some(intersection) = intersection {
+            for intersection in CurveEdgeIntersection::compute(path, edge) {
                 match intersection {
                     CurveEdgeIntersection::Point { point_on_curve } => {
                         intersections.push(point_on_curve);
@@ -48,15 +51,80 @@ impl CurveFaceIntersection {
             }
         }
 
-        assert!(intersections.len() % 2 == 0);
+        let is_closed =
+            matches!(path, SurfacePath::Circle(_) | SurfacePath::Ellipse(_));
+        if is_closed {
+            // The start and the end of the curve are the same point. Let's
+            // only keep one of them.
+            for point in &mut intersections {
+                if point.t >= Scalar::TAU {
+                    point.t -= Scalar::TAU;
+                }
+            }
+        }
 
         intersections.sort();
+        intersections.dedup();
 
-        let intervals = intersections
+        // Where the curve passes through a vertex of the face, or runs along
+        // one of its edges, the boundary is hit more than once at the same
+        // point. Instead of trying to interpret those hits, we check for each
+        // interval between two hits, whether it's actually within the face.
+        let mut candidates = intersections
             .as_slice()
-            .array_chunks_ext()
-            .map(|&[start, end]| CurveFaceIntersectionInterval { start, end })
-            .collect();
+            .array_windows_ext()
+            .copied()
+            .collect::<Vec<_>>();
+
+        // A circle or ellipse is closed, so there's another interval from the
+        // last hit, around the start of the curve, to the first hit. A NURBS
+        // curve has a start and an end, and there are intervals from its start
+        // to the first hit, and from the last hit to its end. If there are no
+        // hits, the curve is either fully inside of the face, or fully outside
+        // of it.
+        let range = match path {
+            SurfacePath::Circle(_) | SurfacePath::Ellipse(_) => {
+                Some([Scalar::ZERO, Scalar::TAU])
+            }
+            SurfacePath::Nurbs(curve) => Some(curve.domain()),
+            SurfacePath::Line(_) => None,
+        };
+        if let Some([start, end]) = range {
+            let [start, end] = [start, end].map(|t| Point::from([t]));
+
+            match (intersections.first(), intersections.last()) {
+                (Some(&first), Some(&last)) => {
+                    candidates.insert(0, [start, first]);
+                    candidates.push([last, end]);
+                }
+                _ => candidates.push([start, end]),
+            }
+        }
+
+        let mut intervals: Vec<CurveFaceIntersectionInterval> = Vec::new();
+
+        for [start, end] in candidates {
+            if start == end {
+                continue;
+            }
+
+            let middle = start + (end - start) / 2.;
+            let middle = path.point_from_path_coords(middle);
+
+            if (face, &middle).intersect().is_none() {
+                continue;
+            }
+
+            match intervals.last_mut() {
+                Some(interval) if interval.end == start => {
+                    interval.end = end;
+                }
+                _ => {
+                    intervals
+                        .push(CurveFaceIntersectionInterval { start, end });
+                }
+            }
+        }
 
         Self { intervals }
     }
@@ -149,10 +217,16 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::f64::consts::{FRAC_PI_2, TAU};
+
+    use fj_math::Scalar;
+
     use crate::{
         geometry::SurfacePath,
         objects::{Cycle, Face},
-        operations::{BuildCycle, BuildFace, Insert, UpdateFace, UpdateRegion},
+        operations::{
+            BuildCycle, BuildFace, Insert, Reverse, UpdateFace, UpdateRegion,
+        },
         services::Services,
     };
 
@@ -202,6 +276,61 @@ mod tests {
         services.only_validate(face);
     }
 
+    #[test]
+    fn compute_circle() {
+        let mut services = Services::new();
+
+        let face = square_face(&mut services);
+
+        // Circles that don't cross the boundary of the face are either fully
+        // inside of it, or fully outside.
+        let inside = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
+        let outside = SurfacePath::circle_from_center_and_radius([0., 0.], 3.);
+        assert_eq!(
+            CurveFaceIntersection::compute(&inside, &face),
+            CurveFaceIntersection::from_intervals([[[0.], [TAU]]]),
+        );
+        assert!(CurveFaceIntersection::compute(&outside, &face).is_empty());
+
+        // Half of these circles is inside of the face. For the second one,
+        // that half wraps around the start of the circle.
+        let on_right_edge =
+            SurfacePath::circle_from_center_and_radius([2., 0.], 1.);
+        let on_left_edge =
+            SurfacePath::circle_from_center_and_radius([-2., 0.], 1.);
+        assert_intervals(
+            CurveFaceIntersection::compute(&on_right_edge, &face),
+            [[FRAC_PI_2, FRAC_PI_2 * 3.]],
+        );
+        assert_intervals(
+            CurveFaceIntersection::compute(&on_left_edge, &face),
+            [[0., FRAC_PI_2], [FRAC_PI_2 * 3., TAU]],
+        );
+
+        services.only_validate(face);
+    }
+
+    #[test]
+    fn compute_with_round_hole() {
+        let mut services = Services::new();
+
+        let (path, _) = SurfacePath::line_from_points([[-3., 0.], [-2., 0.]]);
+
+        let face = square_face(&mut services).update_region(|region| {
+            region
+                .add_interiors([Cycle::circle([0., 0.], 1., &mut services)
+                    .reverse(&mut services)
+                    .insert(&mut services)])
+                .insert(&mut services)
+        });
+
+        let expected =
+            CurveFaceIntersection::from_intervals([[[1.], [2.]], [[4.], [5.]]]);
+        assert_eq!(CurveFaceIntersection::compute(&path, &face), expected);
+
+        services.only_validate(face);
+    }
+
     #[test]
     fn merge() {
         let a = CurveFaceIntersection::from_intervals([
@@ -271,4 +400,38 @@ mod tests {
         ]);
         assert_eq!(merged, expected);
     }
+
+    fn square_face(services: &mut Services) -> Face {
+        #[rustfmt::skip]
+        let exterior_points = [
+            [-2., -2.],
+            [ 2., -2.],
+            [ 2.,  2.],
+            [-2.,  2.],
+        ];
+
+        Face::unbound(services.objects.surfaces.xy_plane(), services)
+            .update_region(|region| {
+                region
+                    .update_exterior(|_| {
+                        Cycle::polygon(exterior_points, services)
+                            .insert(services)
+                    })
+                    .insert(services)
+            })
+    }
+
+    fn assert_intervals<const N: usize>(
+        intersection: CurveFaceIntersection,
+        expected: [[f64; 2]; N],
+    ) {
+        assert_eq!(intersection.intervals.len(), N, "{intersection:?}");
+
+        for (interval, [start, end]) in
+            intersection.intervals.into_iter().zip(expected)
+        {
+            assert!((interval.start.t - start).abs() < Scalar::from(1e-12));
+            assert!((interval.end.t - end).abs() < Scalar::from(1e-12));
+        }
+    }
 }
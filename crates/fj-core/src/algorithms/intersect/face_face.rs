@@ -1,7 +1,9 @@
 use fj_interop::ext::ArrayExt;
 use iter_fixed::IntoIteratorFixed;
 
-use crate::{geometry::SurfacePath, objects::Face};
+use crate::{
+    algorithms::approx::Tolerance, geometry::SurfacePath, objects::Face,
+};
 
 use super::{CurveFaceIntersection, SurfaceSurfaceIntersection};
 
@@ -24,46 +26,62 @@ pub struct FaceFaceIntersection {
 
 impl FaceFaceIntersection {
     /// Compute the intersections between two faces
-    pub fn compute(faces: [&Face; 2]) -> Option<Self> {
+    ///
+    /// Returns one intersection for each curve along which the surfaces of the
+    /// faces intersect, as long as the faces themselves overlap on that curve.
+    ///
+    /// Intersection curves that can't be represented exactly are approximated
+    /// within the given tolerance. See [`SurfaceSurfaceIntersection::compute`].
+    pub fn compute(
+        faces: [&Face; 2],
+        tolerance: impl Into<Tolerance>,
+    ) -> Vec<Self> {
         let surfaces = faces.map(|face| face.surface().clone());
 
-        let intersection_curves =
-            match SurfaceSurfaceIntersection::compute(surfaces) {
-                Some(intersection) => intersection.intersection_curves,
-                None => return None,
-            };
-
-        let curve_face_intersections = intersection_curves
-            .each_ref_ext()
-            .into_iter_fixed()
-            .zip(faces)
-            .map(|(curve, face)| CurveFaceIntersection::compute(curve, face))
-            .collect::<[_; 2]>();
-
-        let intersection_intervals = {
-            let [a, b] = curve_face_intersections;
-            a.merge(&b)
-        };
+        SurfaceSurfaceIntersection::compute(surfaces, tolerance)
+            .into_iter()
+            .filter_map(|intersection| {
+                let intersection_curves = intersection.intersection_curves;
+
+                let curve_face_intersections = intersection_curves
+                    .each_ref_ext()
+                    .into_iter_fixed()
+                    .zip(faces)
+                    .map(|(curve, face)| {
+                        CurveFaceIntersection::compute(curve, face)
+                    })
+                    .collect::<[_; 2]>();
 
-        if intersection_intervals.is_empty() {
-            return None;
-        }
+                let intersection_intervals = {
+                    let [a, b] = curve_face_intersections;
+                    a.merge(&b)
+                };
 
-        Some(Self {
-            intersection_curves,
-            intersection_intervals,
-        })
+                if intersection_intervals.is_empty() {
+                    return None;
+                }
+
+                Some(Self {
+                    intersection_curves,
+                    intersection_intervals,
+                })
+            })
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use fj_math::{Scalar, Transform, Vector};
     use pretty_assertions::assert_eq;
 
     use crate::{
-        algorithms::intersect::CurveFaceIntersection,
-        geometry::SurfacePath,
-        objects::{Cycle, Face},
+        algorithms::{
+            approx::Tolerance, intersect::CurveFaceIntersection,
+            transform::TransformObject,
+        },
+        geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+        objects::{Cycle, Face, Region, Surface},
         operations::{BuildCycle, BuildFace, Insert, UpdateFace, UpdateRegion},
         services::Services,
     };
@@ -96,8 +114,9 @@ mod tests {
             })
         });
 
-        let intersection = FaceFaceIntersection::compute([&a, &b]);
-        assert!(intersection.is_none());
+        let intersections =
+            FaceFaceIntersection::compute([&a, &b], tolerance());
+        assert!(intersections.is_empty());
 
         services.only_validate([a, b]);
     }
@@ -128,7 +147,8 @@ mod tests {
             })
         });
 
-        let intersection = FaceFaceIntersection::compute([&a, &b]);
+        let intersections =
+            FaceFaceIntersection::compute([&a, &b], tolerance());
 
         let expected_curves = surfaces.map(|_| {
             let (path, _) = SurfacePath::line_from_points([[0., 0.], [1., 0.]]);
@@ -137,13 +157,80 @@ mod tests {
         let expected_intervals =
             CurveFaceIntersection::from_intervals([[[-1.], [1.]]]);
         assert_eq!(
-            intersection,
-            Some(FaceFaceIntersection {
+            intersections,
+            vec![FaceFaceIntersection {
                 intersection_curves: expected_curves,
                 intersection_intervals: expected_intervals
-            })
+            }]
         );
 
         services.only_validate([a, b]);
     }
+
+    #[test]
+    fn compute_oblique_plane_and_cylinder() {
+        let mut services = Services::new();
+
+        // The cylinder has a radius of `1`, and its face goes around it once,
+        // from `z = 0` to `z = 1`.
+        let cylinder = Surface::new(SurfaceGeometry::Swept {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::unit_z(),
+        })
+        .insert(&mut services);
+        let cylinder_face = {
+            let tau = Scalar::TAU.into_f64();
+            let exterior = Cycle::polygon(
+                [[0., 0.], [tau, 0.], [tau, 1.], [0., 1.]],
+                &mut services,
+            )
+            .insert(&mut services);
+            let region = Region::new(exterior, [], None).insert(&mut services);
+            Face::new(cylinder, region)
+        };
+
+        // The plane is tilted, so it intersects the cylinder in an ellipse,
+        // along which `z = 0.5 + sin(u)`.
+        let plane = services.objects.surfaces.xy_plane().transform(
+            &(Transform::translation([0., 0., 0.5])
+                * Transform::rotation(Vector::unit_x() * (Scalar::PI / 4.))),
+            &mut services,
+        );
+        let plane_face = {
+            let exterior = Cycle::polygon(
+                [[-2., -2.], [2., -2.], [2., 2.], [-2., 2.]],
+                &mut services,
+            )
+            .insert(&mut services);
+            let region = Region::new(exterior, [], None).insert(&mut services);
+            Face::new(plane, region)
+        };
+
+        let intersections = FaceFaceIntersection::compute(
+            [&plane_face, &cylinder_face],
+            tolerance(),
+        );
+        assert_eq!(intersections.len(), 1);
+
+        // The intersection is within the cylinder face, where `sin(u)` is
+        // between `-0.5` and `0.5`.
+        let pi = Scalar::PI;
+        let expected = [
+            [Scalar::ZERO, pi / 6.],
+            [pi * 5. / 6., pi * 7. / 6.],
+            [pi * 11. / 6., Scalar::TAU],
+        ];
+        let intervals = &intersections[0].intersection_intervals.intervals;
+        assert_eq!(intervals.len(), expected.len());
+        for (interval, [start, end]) in intervals.iter().zip(expected) {
+            assert!((interval.start.t - start).abs() < Scalar::from(1e-5));
+            assert!((interval.end.t - end).abs() < Scalar::from(1e-5));
+        }
+
+        services.only_validate([plane_face, cylinder_face]);
+    }
+
+    fn tolerance() -> Tolerance {
+        Tolerance::from_scalar(1e-6).unwrap()
+    }
 }
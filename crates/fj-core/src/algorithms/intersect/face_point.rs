@@ -8,7 +8,8 @@ use crate::{
 };
 
 use super::{
-    ray_segment::RaySegmentIntersection, HorizontalRayToTheRight, Intersect,
+    ray_edge::EdgePart, ray_segment::RaySegmentIntersection,
+    HorizontalRayToTheRight, Intersect,
 };
 
 impl Intersect for (&Face, &Point<2>) {
@@ -22,19 +23,39 @@ impl Intersect for (&Face, &Point<2>) {
         let mut num_hits = 0;
 
         for cycle in face.region().all_cycles() {
+            // Curved edges can be hit by the ray more than once. We split
+            // them into parts that can't, and then treat those parts like we
+            // would treat the segments of a polygon.
+            //
+            // The points where an edge is split are not vertices, so we need
+            // to keep track of whether each part starts or ends at one.
+            let parts = cycle
+                .edges()
+                .pairs()
+                .flat_map(|(edge, next_edge)| {
+                    let parts =
+                        EdgePart::split_edge(edge, next_edge.start_position());
+                    let last = parts.len() - 1;
+
+                    parts.into_iter().enumerate().map(move |(i, part)| {
+                        (edge, next_edge, part, i == 0, i == last)
+                    })
+                })
+                .collect::<Vec<_>>();
+
             // We need to properly detect the ray passing the boundary at the
             // "seam" of the polygon, i.e. the vertex between the last and the
             // first segment. The logic in the loop properly takes care of that,
             // as long as we initialize the `previous_hit` variable with the
             // result of the last segment.
-            let mut previous_hit = cycle
-                .edges()
-                .iter()
+            let mut previous_hit = parts
                 .last()
-                .and_then(|edge| (&ray, edge).intersect());
+                .and_then(|(_, _, part, _, _)| (&ray, part).intersect());
 
-            for (edge, next_edge) in cycle.edges().pairs() {
-                let hit = (&ray, edge).intersect();
+            for (edge, next_edge, part, starts_at_vertex, ends_at_vertex) in
+                &parts
+            {
+                let hit = (&ray, part).intersect();
 
                 let count_hit = match (hit, previous_hit) {
                     (
@@ -44,21 +65,38 @@ impl Intersect for (&Face, &Point<2>) {
                         // If the ray starts on the boundary of the face,
                         // there's nothing to else check.
                         return Some(FacePointIntersection::PointIsOnEdge(
-                            edge.clone()
+                            (*edge).clone()
                         ));
                     }
-                    (Some(RaySegmentIntersection::RayStartsOnOnFirstVertex), _) => {
+                    (Some(RaySegmentIntersection::RayStartsOnOnFirstVertex), _)
+                        if *starts_at_vertex =>
+                    {
                         let vertex = edge.start_position();
                         return Some(
                             FacePointIntersection::PointIsOnVertex(vertex)
                         );
                     }
-                    (Some(RaySegmentIntersection::RayStartsOnSecondVertex), _) => {
+                    (Some(RaySegmentIntersection::RayStartsOnSecondVertex), _)
+                        if *ends_at_vertex =>
+                    {
                         let vertex = next_edge.start_position();
                         return Some(
                             FacePointIntersection::PointIsOnVertex(vertex)
                         );
                     }
+                    (
+                        Some(
+                            RaySegmentIntersection::RayStartsOnOnFirstVertex
+                            | RaySegmentIntersection::RayStartsOnSecondVertex,
+                        ),
+                        _,
+                    ) => {
+                        // The ray starts where a curved edge was split, which
+                        // is not a vertex.
+                        return Some(FacePointIntersection::PointIsOnEdge(
+                            (*edge).clone(),
+                        ));
+                    }
                     (Some(RaySegmentIntersection::RayHitsSegment), _) => {
                         // We're hitting a segment right-on. Clear case.
                         true
@@ -136,7 +174,9 @@ mod tests {
     use crate::{
         algorithms::intersect::{face_point::FacePointIntersection, Intersect},
         objects::{Cycle, Face},
-        operations::{BuildCycle, BuildFace, Insert, UpdateFace, UpdateRegion},
+        operations::{
+            BuildCycle, BuildFace, Insert, Reverse, UpdateFace, UpdateRegion,
+        },
         services::Services,
     };
 
@@ -383,4 +423,110 @@ mod tests {
 
         services.only_validate(face);
     }
+
+    #[test]
+    fn point_is_inside_circle() {
+        let mut services = Services::new();
+
+        let face =
+            Face::unbound(services.objects.surfaces.xy_plane(), &mut services)
+                .update_region(|region| {
+                    region
+                        .update_exterior(|_| {
+                            Cycle::circle([0., 0.], 1., &mut services)
+                                .insert(&mut services)
+                        })
+                        .insert(&mut services)
+                });
+
+        // The ray passes through the start of the circle here, where its
+        // first and last edge parts meet.
+        let point = Point::from([0., 0.]);
+        assert_eq!(
+            (&face, &point).intersect(),
+            Some(FacePointIntersection::PointIsInsideFace)
+        );
+
+        // And here it touches the circle at its lowest point.
+        let point = Point::from([-2., -1.]);
+        assert_eq!((&face, &point).intersect(), None);
+
+        let point = Point::from([0.5, 0.5]);
+        assert_eq!(
+            (&face, &point).intersect(),
+            Some(FacePointIntersection::PointIsInsideFace)
+        );
+
+        services.only_validate(face);
+    }
+
+    #[test]
+    fn point_is_inside_round_hole() {
+        let mut services = Services::new();
+
+        let face =
+            Face::unbound(services.objects.surfaces.xy_plane(), &mut services)
+                .update_region(|region| {
+                    region
+                        .update_exterior(|_| {
+                            Cycle::polygon(
+                                [[-2., -2.], [2., -2.], [2., 2.], [-2., 2.]],
+                                &mut services,
+                            )
+                            .insert(&mut services)
+                        })
+                        .add_interiors([Cycle::circle(
+                            [0., 0.],
+                            1.,
+                            &mut services,
+                        )
+                        .reverse(&mut services)
+                        .insert(&mut services)])
+                        .insert(&mut services)
+                });
+
+        let point = Point::from([0., 0.5]);
+        assert_eq!((&face, &point).intersect(), None);
+
+        let point = Point::from([-1.5, 0.5]);
+        assert_eq!(
+            (&face, &point).intersect(),
+            Some(FacePointIntersection::PointIsInsideFace)
+        );
+
+        services.only_validate(face);
+    }
+
+    #[test]
+    fn point_is_coincident_with_circle() {
+        let mut services = Services::new();
+
+        let face =
+            Face::unbound(services.objects.surfaces.xy_plane(), &mut services)
+                .update_region(|region| {
+                    region
+                        .update_exterior(|_| {
+                            Cycle::circle([0., 0.], 5., &mut services)
+                                .insert(&mut services)
+                        })
+                        .insert(&mut services)
+                });
+
+        let edge = face.region().exterior().edges().first();
+
+        // This point is on the circle, but not on its vertex.
+        let point = Point::from([-3., -4.]);
+        assert_eq!(
+            (&face, &point).intersect(),
+            Some(FacePointIntersection::PointIsOnEdge(edge.clone()))
+        );
+
+        let vertex = edge.start_position();
+        assert_eq!(
+            (&face, &vertex).intersect(),
+            Some(FacePointIntersection::PointIsOnVertex(vertex))
+        );
+
+        services.only_validate(face);
+    }
 }
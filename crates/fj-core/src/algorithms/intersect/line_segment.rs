@@ -1,4 +1,4 @@
-use fj_math::{Aabb, Line, Point, Scalar, Segment, Vector};
+use fj_math::{Line, Point, Scalar, Segment, Vector};
 
 /// An intersection between a [`Line`] and a [`Segment`]
 #[derive(Debug, Eq, PartialEq)]
@@ -23,12 +23,10 @@ impl LineSegmentIntersection {
         // Ericson. See section 5.1.9.1, 2D Segment Intersection.
 
         let [a, b] = segment.points();
+        let ab = b - a;
 
         // Find vector that is orthogonal to `segment`.
-        let n = {
-            let ab = b - a;
-            Vector::from([ab.v, ab.u])
-        };
+        let n = Vector::from([-ab.v, ab.u]);
 
         let n_dot_origin = n.dot(&(b - line.origin()));
         let n_dot_direction = n.dot(&line.direction());
@@ -52,9 +50,15 @@ impl LineSegmentIntersection {
         // line defined by `segment`'s points.
         let t = n_dot_origin / n_dot_direction;
 
-        let point_is_on_segment = Aabb::<2>::from_points(segment.points())
-            .contains(line.point_from_line_coords([t]));
-        if !point_is_on_segment {
+        // Compute the same point in segment coordinates, where the segment
+        // goes from 0 to 1. If `line` passes through one of the segment's end
+        // points, this might come out as slightly out of range, due to
+        // floating point inaccuracies. Allow for that, so hits on vertices
+        // that are shared between segments aren't missed.
+        let s = (line.origin() - a).cross2d(&line.direction())
+            / ab.cross2d(&line.direction());
+        let epsilon = Scalar::from_f64(1e-12);
+        if s < -epsilon || s > Scalar::ONE + epsilon {
             return None;
         }
 
@@ -86,6 +90,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compute_one_hit_diagonal() {
+        let line =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_u());
+
+        assert_eq!(
+            LineSegmentIntersection::compute(
+                &line,
+                &Segment::from_points([[1., -1.], [4., 2.]]),
+            ),
+            Some(LineSegmentIntersection::Point {
+                point_on_line: Point::from([2.])
+            }),
+        );
+    }
+
     #[test]
     fn compute_coincident() {
         let line =
@@ -1,35 +1,313 @@
 //! Intersection between a ray and an edge in 2D
 
-use fj_math::Segment;
+use std::f64::consts::PI;
+
+use fj_math::{Circle, Point, Scalar, Segment};
 
 use crate::{
     algorithms::intersect::{HorizontalRayToTheRight, Intersect},
     geometry::SurfacePath,
     objects::Edge,
-    storage::Handle,
 };
 
 use super::ray_segment::RaySegmentIntersection;
 
-impl Intersect for (&HorizontalRayToTheRight<2>, &Handle<Edge>) {
+/// A part of an edge, along which the v-coordinate is monotone
+///
+/// A horizontal ray can hit an edge on a curved path more than once. A part of
+/// an edge that only goes up or down can be hit at most once though, which
+/// means it can be treated just like a line segment.
+#[derive(Clone, Copy, Debug)]
+pub enum EdgePart {
+    /// The part is a line segment
+    Segment(Segment<2>),
+
+    /// The part is an arc
+    Arc {
+        /// The circle that the arc is on
+        circle: Circle<2>,
+
+        /// The boundary of the arc, in circle coordinates
+        boundary: [Point<1>; 2],
+
+        /// The start and end points of the arc, in surface coordinates
+        points: [Point<2>; 2],
+    },
+}
+
+impl EdgePart {
+    /// Split the edge into parts, along which the v-coordinate is monotone
+    ///
+    /// The parts are returned in order, from the start of the edge to its end.
+    /// The end of an edge is expected to be the start of the next edge in its
+    /// cycle. It is passed in, so the parts of both edges connect exactly, even
+    /// if computing the end point from the edge's path would result in a
+    /// slightly different point.
+    pub fn split_edge(edge: &Edge, end_position: Point<2>) -> Vec<Self> {
+        match edge.path() {
+            SurfacePath::Line(line) => {
+                let points = edge
+                    .boundary()
+                    .inner
+                    .map(|point| line.point_from_line_coords(point));
+
+                vec![Self::Segment(Segment::from_points(points))]
+            }
+            SurfacePath::Circle(circle) => {
+                let [start, end] = edge.boundary().inner.map(|point| point.t);
+                let [min, max] = if start <= end {
+                    [start, end]
+                } else {
+                    [end, start]
+                };
+
+                // The circle has its highest and lowest points, where its
+                // tangent is horizontal. Those are half a turn apart, and we
+                // need to split the arc at each one of them that it contains.
+                let extremum = circle.b().v.atan2(circle.a().v);
+                let half_turn = Scalar::from(PI);
+
+                let mut splits = Vec::new();
+                let mut t = extremum
+                    + half_turn * ((min - extremum) / half_turn).floor();
+                while t < max {
+                    if t > min {
+                        splits.push(t);
+                    }
+                    t += half_turn;
+                }
+                if start > end {
+                    splits.reverse();
+                }
+
+                let mut parts = Vec::new();
+                let mut previous = (start, edge.start_position());
+                for t in splits {
+                    let point = circle.point_from_circle_coords([t]);
+                    parts.push(Self::arc(circle, previous, (t, point)));
+                    previous = (t, point);
+                }
+                parts.push(Self::arc(circle, previous, (end, end_position)));
+
+                parts
+            }
+            SurfacePath::Ellipse(_) | SurfacePath::Nurbs(_) => {
+                todo!(
+                    "Casting rays against ellipses and NURBS curves is not \
+                    supported yet"
+                )
+            }
+        }
+    }
+
+    /// Access the start and end points of the part
+    pub fn points(&self) -> [Point<2>; 2] {
+        match self {
+            Self::Segment(segment) => segment.points(),
+            Self::Arc { points, .. } => *points,
+        }
+    }
+
+    fn arc(
+        circle: Circle<2>,
+        (start, start_position): (Scalar, Point<2>),
+        (end, end_position): (Scalar, Point<2>),
+    ) -> Self {
+        Self::Arc {
+            circle,
+            boundary: [start, end].map(|t| Point::from([t])),
+            points: [start_position, end_position],
+        }
+    }
+}
+
+impl Intersect for (&HorizontalRayToTheRight<2>, &EdgePart) {
     type Intersection = RaySegmentIntersection;
 
     fn intersect(self) -> Option<Self::Intersection> {
-        let (ray, edge) = self;
+        let (ray, part) = self;
+
+        let (circle, boundary) = match part {
+            EdgePart::Segment(segment) => return (ray, segment).intersect(),
+            EdgePart::Arc {
+                circle, boundary, ..
+            } => (circle, boundary),
+        };
 
-        let line = match edge.path() {
-            SurfacePath::Line(line) => line,
-            SurfacePath::Circle(_) => {
-                todo!("Casting rays against circles is not supported yet")
+        let [a, b] = part.points();
+        let [lower, upper] = if a.v <= b.v { [a, b] } else { [b, a] };
+
+        if ray.origin.v > upper.v {
+            // ray is above arc
+            return None;
+        }
+        if ray.origin.v < lower.v {
+            // ray is below arc
+            return None;
+        }
+
+        // Figure out where the arc passes through the height of the ray. Since
+        // the arc is monotone along the v-axis, there's exactly one such
+        // point, and it lies on the same side of the circle's center as the
+        // rest of the arc.
+        let u = if ray.origin.v == a.v {
+            a.u
+        } else if ray.origin.v == b.v {
+            b.u
+        } else {
+            let [start, end] = boundary.map(|point| point.t);
+            let middle = circle.point_from_circle_coords([(start + end) / 2.]);
+
+            let center = circle.center();
+            let dv = ray.origin.v - center.v;
+            let du = (circle.radius() * circle.radius() - dv * dv)
+                .max(Scalar::ZERO)
+                .sqrt();
+
+            if middle.u < center.u {
+                center.u - du
+            } else {
+                center.u + du
             }
         };
 
-        let points = edge
-            .boundary()
-            .inner
-            .map(|point| line.point_from_line_coords(point));
-        let segment = Segment::from_points(points);
+        if ray.origin.u == u {
+            // ray starts on the arc
+
+            if ray.origin.v == a.v {
+                return Some(RaySegmentIntersection::RayStartsOnOnFirstVertex);
+            }
+            if ray.origin.v == b.v {
+                return Some(RaySegmentIntersection::RayStartsOnSecondVertex);
+            }
+
+            return Some(RaySegmentIntersection::RayStartsOnSegment);
+        }
+
+        if ray.origin.u < u {
+            // ray starts left of the arc
+
+            if ray.origin.v == upper.v {
+                return Some(RaySegmentIntersection::RayHitsUpperVertex);
+            }
+            if ray.origin.v == lower.v {
+                return Some(RaySegmentIntersection::RayHitsLowerVertex);
+            }
+
+            return Some(RaySegmentIntersection::RayHitsSegment);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+    use fj_math::{Circle, Point};
+
+    use crate::{
+        algorithms::intersect::{
+            ray_segment::RaySegmentIntersection, HorizontalRayToTheRight,
+            Intersect,
+        },
+        geometry::{CurveBoundary, SurfacePath},
+        objects::{Curve, Edge, Vertex},
+        operations::Insert,
+        services::Services,
+    };
+
+    use super::EdgePart;
+
+    #[test]
+    fn split_edge() {
+        let mut services = Services::new();
+
+        let edge = Edge::new(
+            SurfacePath::circle_from_center_and_radius([0., 0.], 1.),
+            CurveBoundary::from([[0.], [TAU]]),
+            Curve::new().insert(&mut services),
+            Vertex::new().insert(&mut services),
+        );
+
+        let boundaries = EdgePart::split_edge(&edge, edge.start_position())
+            .into_iter()
+            .map(|part| match part {
+                EdgePart::Arc { boundary, .. } => boundary.map(|point| point.t),
+                EdgePart::Segment(_) => unreachable!("Expected arc"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            boundaries,
+            [
+                [0., FRAC_PI_2],
+                [FRAC_PI_2, PI + FRAC_PI_2],
+                [PI + FRAC_PI_2, TAU]
+            ]
+            .map(|boundary| boundary.map(Into::into))
+        );
+    }
+
+    #[test]
+    fn ray_hits_arc() {
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+        let arc = |boundary| arc(circle, boundary);
+
+        let ray = HorizontalRayToTheRight::from([-2., 0.5]);
+
+        assert_eq!(
+            (&ray, &arc([0., FRAC_PI_2])).intersect(),
+            Some(RaySegmentIntersection::RayHitsSegment)
+        );
+        assert_eq!(
+            (&ray, &arc([FRAC_PI_2, PI + FRAC_PI_2])).intersect(),
+            Some(RaySegmentIntersection::RayHitsSegment)
+        );
+        assert_eq!((&ray, &arc([PI, PI + FRAC_PI_2])).intersect(), None);
+
+        let ray = HorizontalRayToTheRight::from([0., 0.5]);
+
+        assert_eq!(
+            (&ray, &arc([0., FRAC_PI_2])).intersect(),
+            Some(RaySegmentIntersection::RayHitsSegment)
+        );
+        assert_eq!((&ray, &arc([FRAC_PI_2, PI + FRAC_PI_2])).intersect(), None);
+
+        let ray = HorizontalRayToTheRight::from([-2., 1.]);
+
+        assert_eq!(
+            (&ray, &arc([0., FRAC_PI_2])).intersect(),
+            Some(RaySegmentIntersection::RayHitsUpperVertex)
+        );
+        assert_eq!(
+            (&ray, &arc([FRAC_PI_2, PI + FRAC_PI_2])).intersect(),
+            Some(RaySegmentIntersection::RayHitsUpperVertex)
+        );
+    }
+
+    #[test]
+    fn ray_starts_on_arc() {
+        let circle = Circle::from_center_and_radius([0., 0.], 5.);
+        let arc = arc(circle, [0., FRAC_PI_2]);
+
+        let ray = HorizontalRayToTheRight::from([5., 0.]);
+        assert_eq!(
+            (&ray, &arc).intersect(),
+            Some(RaySegmentIntersection::RayStartsOnOnFirstVertex)
+        );
+
+        let ray = HorizontalRayToTheRight::from([3., 4.]);
+        assert_eq!(
+            (&ray, &arc).intersect(),
+            Some(RaySegmentIntersection::RayStartsOnSegment)
+        );
+    }
 
-        (ray, &segment).intersect()
+    fn arc(circle: Circle<2>, boundary: [f64; 2]) -> EdgePart {
+        let [start, end] = boundary.map(|t| {
+            (t.into(), circle.point_from_circle_coords(Point::from([t])))
+        });
+        EdgePart::arc(circle, start, end)
     }
 }
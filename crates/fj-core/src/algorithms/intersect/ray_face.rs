@@ -1,10 +1,15 @@
 //! Intersection between a ray and a face, in 3D
 
-use fj_math::{Plane, Point, Scalar};
+use std::f64::consts::TAU;
+
+use fj_math::{Cone, Ellipse, Plane, Point, Scalar, Sphere, Transform, Vector};
 
 use crate::{
-    algorithms::intersect::face_point::FacePointIntersection,
-    geometry::GlobalPath,
+    algorithms::{
+        bounding_volume::BoundingVolume,
+        intersect::face_point::FacePointIntersection,
+    },
+    geometry::{GlobalPath, SurfaceGeometry},
     objects::{Edge, Face},
     storage::Handle,
 };
@@ -16,112 +21,519 @@ impl Intersect for (&HorizontalRayToTheRight<3>, &Face) {
 
     fn intersect(self) -> Option<Self::Intersection> {
         let (ray, face) = self;
+        all_hits(ray, face, &Transform::identity())
+            .into_iter()
+            .next()
+    }
+}
 
-        let plane = match face.surface().geometry().u {
-            GlobalPath::Circle(_) => todo!(
-                "Casting a ray against a swept circle is not supported yet"
-            ),
-            GlobalPath::Line(line) => Plane::from_parametric(
-                line.origin(),
-                line.direction(),
-                face.surface().geometry().v,
-            ),
-        };
+/// Compute all hits between a ray and a face
+///
+/// A ray can hit a curved face more than once. Where the [`Intersect`]
+/// implementation only returns the hit closest to the ray's origin, this
+/// returns all of them, in the order in which the ray passes through them.
+///
+/// The face is transformed by the provided transform, before the ray is cast.
+/// This makes it possible to cast rays in directions other than along the
+/// x-axis, by rotating the face instead.
+pub fn all_hits(
+    ray: &HorizontalRayToTheRight<3>,
+    face: &Face,
+    transform: &Transform,
+) -> Vec<RayFaceIntersection> {
+    let geometry = face.surface().geometry().transform(transform);
 
-        if plane.is_parallel_to_vector(&ray.direction()) {
-            let a = plane.origin();
-            let b = plane.origin() + plane.u();
-            let c = plane.origin() + plane.v();
-            let d = ray.origin;
-
-            let [a, b, c, d] = [a, b, c, d]
-                .map(|point| [point.x, point.y, point.z])
-                .map(|point| point.map(Scalar::into_f64))
-                .map(|[x, y, z]| robust::Coord3D { x, y, z });
-
-            if robust::orient3d(a, b, c, d) == 0. {
-                return Some(RayFaceIntersection::RayHitsFaceAndAreParallel);
-            } else {
-                return None;
-            }
+    let plane = match geometry {
+        SurfaceGeometry::Swept {
+            u: GlobalPath::Circle(circle),
+            v,
+        } => {
+            return intersect_swept_ellipse(
+                ray,
+                face,
+                &Ellipse::from(circle),
+                &v,
+            );
+        }
+        SurfaceGeometry::Swept {
+            u: GlobalPath::Ellipse(ellipse),
+            v,
+        } => {
+            return intersect_swept_ellipse(ray, face, &ellipse, &v);
+        }
+        SurfaceGeometry::Swept {
+            u: GlobalPath::Helix(_) | GlobalPath::Nurbs(_),
+            ..
+        }
+        | SurfaceGeometry::Torus(_)
+        | SurfaceGeometry::Nurbs(_) => {
+            return intersect_numerically(ray, face, &geometry);
         }
+        SurfaceGeometry::Swept {
+            u: GlobalPath::Line(line),
+            v,
+        } => Plane::from_parametric(line.origin(), line.direction(), v),
+        SurfaceGeometry::Sphere(sphere) => {
+            return intersect_sphere(ray, face, &sphere);
+        }
+        SurfaceGeometry::Cone(cone) => {
+            return intersect_cone(ray, face, &cone);
+        }
+    };
 
-        // The pattern in this assertion resembles `ax*by = ay*bx`, which holds
-        // true if the vectors `a = (ax, ay)` and `b = (bx, by)` are parallel.
-        //
-        // We're looking at the plane's direction vectors here, but we're
-        // ignoring their x-components. By doing that, we're essentially
-        // projecting those vectors into the yz-plane.
+    intersect_plane(ray, face, &plane).into_iter().collect()
+}
+
+/// Intersect a ray with a face whose surface is a plane
+fn intersect_plane(
+    ray: &HorizontalRayToTheRight<3>,
+    face: &Face,
+    plane: &Plane,
+) -> Option<RayFaceIntersection> {
+    if plane.is_parallel_to_vector(&ray.direction()) {
+        let a = plane.origin();
+        let b = plane.origin() + plane.u();
+        let c = plane.origin() + plane.v();
+        let d = ray.origin;
+
+        let [a, b, c, d] = [a, b, c, d]
+            .map(|point| [point.x, point.y, point.z])
+            .map(|point| point.map(Scalar::into_f64))
+            .map(|[x, y, z]| robust::Coord3D { x, y, z });
+
+        if robust::orient3d(a, b, c, d) == 0. {
+            return Some(RayFaceIntersection::RayHitsFaceAndAreParallel);
+        } else {
+            return None;
+        }
+    }
+
+    // The pattern in this assertion resembles `ax*by = ay*bx`, which holds
+    // true if the vectors `a = (ax, ay)` and `b = (bx, by)` are parallel.
+    //
+    // We're looking at the plane's direction vectors here, but we're
+    // ignoring their x-components. By doing that, we're essentially
+    // projecting those vectors into the yz-plane.
+    //
+    // This means that the following assertion verifies that the projections
+    // of the plane's direction vectors into the yz-plane are not parallel.
+    // If they were, then the plane could only be parallel to the x-axis,
+    // and thus our ray.
+    //
+    // We already handled the case of the ray and plane being parallel
+    // above. The following assertion should thus never be triggered.
+    assert_ne!(
+        plane.u().y * plane.v().z,
+        plane.u().z * plane.v().y,
+        "Plane and ray are parallel; should have been ruled out previously"
+    );
+
+    // Let's figure out the intersection between the ray and the plane.
+    let (t, u, v) = {
+        // The following math would get *very* unwieldy with those
+        // full-length variable names. Let's define some short-hands.
+        let orx = ray.origin.x;
+        let ory = ray.origin.y;
+        let orz = ray.origin.z;
+        let opx = plane.origin().x;
+        let opy = plane.origin().y;
+        let opz = plane.origin().z;
+        let d1x = plane.u().x;
+        let d1y = plane.u().y;
+        let d1z = plane.u().z;
+        let d2x = plane.v().x;
+        let d2y = plane.v().y;
+        let d2z = plane.v().z;
+
+        // Let's figure out where the intersection between the ray and the
+        // plane is. By equating the parametric equations of the ray and the
+        // plane, we get a vector equation, which in turn gives us a system
+        // of three equations with three unknowns: `t` (for the ray) and
+        // `u`/`v` (for the plane).
         //
-        // This means that the following assertion verifies that the projections
-        // of the plane's direction vectors into the yz-plane are not parallel.
-        // If they were, then the plane could only be parallel to the x-axis,
-        // and thus our ray.
+        // Since the ray's direction vector is `(1, 0, 0)`, it works out
+        // such that `t` is not in the equations for y and z, meaning we can
+        // solve those equations for `u` and `v` independently.
         //
-        // We already handled the case of the ray and plane being parallel
-        // above. The following assertion should thus never be triggered.
-        assert_ne!(
-            plane.u().y * plane.v().z,
-            plane.u().z * plane.v().y,
-            "Plane and ray are parallel; should have been ruled out previously"
-        );
+        // By doing some math, we get the following solutions:
+        let v =
+            (d1y * (orz - opz) + (opy - ory) * d1z) / (d1y * d2z - d2y * d1z);
+        let u =
+            ((ory - opy) * d2z - d2y * (orz - opz)) / (d1y * d2z - d2y * d1z);
+        let t = opx - orx + d1x * u + d2x * v;
 
-        // Let's figure out the intersection between the ray and the plane.
-        let (t, u, v) = {
-            // The following math would get *very* unwieldy with those
-            // full-length variable names. Let's define some short-hands.
-            let orx = ray.origin.x;
-            let ory = ray.origin.y;
-            let orz = ray.origin.z;
-            let opx = plane.origin().x;
-            let opy = plane.origin().y;
-            let opz = plane.origin().z;
-            let d1x = plane.u().x;
-            let d1y = plane.u().y;
-            let d1z = plane.u().z;
-            let d2x = plane.v().x;
-            let d2y = plane.v().y;
-            let d2z = plane.v().z;
-
-            // Let's figure out where the intersection between the ray and the
-            // plane is. By equating the parametric equations of the ray and the
-            // plane, we get a vector equation, which in turn gives us a system
-            // of three equations with three unknowns: `t` (for the ray) and
-            // `u`/`v` (for the plane).
-            //
-            // Since the ray's direction vector is `(1, 0, 0)`, it works out
-            // such that `t` is not in the equations for y and z, meaning we can
-            // solve those equations for `u` and `v` independently.
-            //
-            // By doing some math, we get the following solutions:
-            let v = (d1y * (orz - opz) + (opy - ory) * d1z)
-                / (d1y * d2z - d2y * d1z);
-            let u = (ory - opy - d2y * v) / d1y;
-            let t = opx - orx + d1x * u + d2x * v;
-
-            (t, u, v)
-        };
+        (t, u, v)
+    };
 
-        if t < Scalar::ZERO {
-            // Ray points away from plane.
-            return None;
+    if t < Scalar::ZERO {
+        // Ray points away from plane.
+        return None;
+    }
+
+    let point = Point::from([u, v]);
+    intersect_face_at_point(face, &point)
+}
+
+/// Intersect a ray with a face whose surface is a swept ellipse
+///
+/// This covers cylinders, which are swept circles. A ray can hit such a
+/// surface twice.
+fn intersect_swept_ellipse(
+    ray: &HorizontalRayToTheRight<3>,
+    face: &Face,
+    ellipse: &Ellipse<3>,
+    v: &Vector<3>,
+) -> Vec<RayFaceIntersection> {
+    let center = ellipse.center();
+    let a = ellipse.a();
+    let b = ellipse.b();
+    let normal = a.cross(&b);
+
+    // Any point on the surface can be moved along `v` into the plane of the
+    // ellipse, where it then lies on the ellipse itself. We do this to all
+    // points on the ray, which gives us the ray's projection into the plane of
+    // the ellipse, `center + w0 + t * w1`.
+    let v_per_distance = v.dot(&normal);
+    let project = |vector: Vector<3>| {
+        vector - *v * (vector.dot(&normal) / v_per_distance)
+    };
+    let w0 = project(ray.origin - center);
+    let w1 = project(ray.direction());
+
+    // Express the projected ray in terms of the ellipse's axes, as
+    // `alpha * a + beta * b`. Points on the ellipse satisfy
+    // `alpha^2 + beta^2 = 1`.
+    let aa = a.dot(&a);
+    let bb = b.dot(&b);
+    let ab = a.dot(&b);
+    let det = aa * bb - ab * ab;
+    let to_ellipse_coords = |w: Vector<3>| {
+        let wa = w.dot(&a);
+        let wb = w.dot(&b);
+        ((wa * bb - wb * ab) / det, (wb * aa - wa * ab) / det)
+    };
+    let (alpha0, beta0) = to_ellipse_coords(w0);
+    let (alpha1, beta1) = to_ellipse_coords(w1);
+
+    let qa = alpha1 * alpha1 + beta1 * beta1;
+    let qb = (alpha0 * alpha1 + beta0 * beta1) * 2.;
+    let qc = alpha0 * alpha0 + beta0 * beta0 - Scalar::ONE;
+
+    if qa == Scalar::ZERO {
+        // The ray is parallel to the direction that the ellipse is swept in.
+        if qc == Scalar::ZERO {
+            return vec![RayFaceIntersection::RayHitsFaceAndAreParallel];
+        } else {
+            return Vec::new();
         }
+    }
 
-        let point = Point::from([u, v]);
-        let intersection = match (face, &point).intersect()? {
-            FacePointIntersection::PointIsInsideFace => {
-                RayFaceIntersection::RayHitsFace
-            }
-            FacePointIntersection::PointIsOnEdge(edge) => {
-                RayFaceIntersection::RayHitsEdge(edge)
-            }
-            FacePointIntersection::PointIsOnVertex(vertex) => {
-                RayFaceIntersection::RayHitsVertex(vertex)
+    let discriminant = qb * qb - qa * qc * 4.;
+    if discriminant < Scalar::ZERO {
+        // Ray misses surface.
+        return Vec::new();
+    }
+
+    let root = discriminant.sqrt();
+    let mut ts = [(-qb - root) / (qa * 2.), (-qb + root) / (qa * 2.)];
+    ts.sort();
+
+    ts.into_iter()
+        .filter(|&t| t >= Scalar::ZERO)
+        .filter_map(|t| {
+            let alpha = alpha0 + alpha1 * t;
+            let beta = beta0 + beta1 * t;
+
+            let u = angle_within_face(face, beta.atan2(alpha));
+
+            let point_global = ray.origin + ray.direction() * t;
+            let v = (point_global - center).dot(&normal) / v_per_distance;
+
+            intersect_face_at_point(face, &Point::from([u, v]))
+        })
+        .collect()
+}
+
+/// Intersect a ray with a face whose surface is a sphere
+///
+/// A ray can hit a sphere twice.
+fn intersect_sphere(
+    ray: &HorizontalRayToTheRight<3>,
+    face: &Face,
+    sphere: &Sphere,
+) -> Vec<RayFaceIntersection> {
+    let to_origin = ray.origin - sphere.center();
+    let direction = ray.direction();
+
+    let ts = quadratic_roots(
+        direction.dot(&direction),
+        direction.dot(&to_origin) * 2.,
+        to_origin.dot(&to_origin) - sphere.radius() * sphere.radius(),
+    );
+
+    ts.into_iter()
+        .filter(|&t| t >= Scalar::ZERO)
+        .filter_map(|t| {
+            let point =
+                sphere.point_to_sphere_coords(ray.origin + direction * t);
+            let u = angle_within_face(face, point.u);
+
+            intersect_face_at_point(face, &Point::from([u, point.v]))
+        })
+        .collect()
+}
+
+/// Intersect a ray with a face whose surface is a cone
+///
+/// A ray can hit a cone twice.
+fn intersect_cone(
+    ray: &HorizontalRayToTheRight<3>,
+    face: &Face,
+    cone: &Cone,
+) -> Vec<RayFaceIntersection> {
+    let circle = cone.circle();
+    let axis = cone.axis();
+    let normal = circle.a().cross(&circle.b());
+    let radius = circle.radius();
+
+    // Split a vector into a part along the axis and a part within the plane of
+    // the circle. A point is on the cone, if the length of the latter is the
+    // radius of the cone at the position along the axis.
+    let split = |vector: Vector<3>| {
+        let v = vector.dot(&normal) / axis.dot(&normal);
+        (vector - axis * v, v)
+    };
+    let (w0, v0) = split(ray.origin - circle.center());
+    let (w1, v1) = split(ray.direction());
+
+    // Solve `|w0 + w1 * t| = radius * (1 - v0 - v1 * t)` for `t`, after
+    // squaring both sides.
+    let s0 = Scalar::ONE - v0;
+    let r2 = radius * radius;
+    let qa = w1.dot(&w1) - r2 * v1 * v1;
+    let qb = (w0.dot(&w1) + r2 * s0 * v1) * 2.;
+    let qc = w0.dot(&w0) - r2 * s0 * s0;
+
+    if qa == Scalar::ZERO && qb == Scalar::ZERO {
+        // The ray runs along a straight line through the apex.
+        if qc == Scalar::ZERO {
+            return vec![RayFaceIntersection::RayHitsFaceAndAreParallel];
+        } else {
+            return Vec::new();
+        }
+    }
+
+    let ts = quadratic_roots(qa, qb, qc);
+
+    ts.into_iter()
+        .filter(|&t| t >= Scalar::ZERO)
+        .filter_map(|t| {
+            let point =
+                cone.point_to_cone_coords(ray.origin + ray.direction() * t);
+            let u = angle_within_face(face, point.u);
+
+            intersect_face_at_point(face, &Point::from([u, point.v]))
+        })
+        .collect()
+}
+
+/// Intersect a ray with a face whose surface has no closed-form solution
+///
+/// The part of the surface that is covered by the face is approximated by
+/// triangles, to find approximate hits. Those are then refined using Newton's
+/// method.
+///
+/// A ray that runs within the surface is not detected as parallel to it, and
+/// places where the ray only touches the surface might be missed.
+fn intersect_numerically(
+    ray: &HorizontalRayToTheRight<3>,
+    face: &Face,
+    geometry: &SurfaceGeometry,
+) -> Vec<RayFaceIntersection> {
+    const NUM_SAMPLES: u64 = 32;
+
+    let Some(aabb) = face.region().exterior().aabb() else {
+        return Vec::new();
+    };
+    let size = aabb.max - aabb.min;
+
+    let sample = |i: u64, j: u64| {
+        let [i, j] =
+            [i, j].map(|x| Scalar::from_u64(x) / Scalar::from_u64(NUM_SAMPLES));
+        aabb.min + Vector::from([size.u * i, size.v * j])
+    };
+
+    let mut hits = Vec::new();
+    for i in 0..NUM_SAMPLES {
+        for j in 0..NUM_SAMPLES {
+            let [a, b, c, d] = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)]
+                .map(|(i, j)| sample(i, j));
+
+            for triangle in [[a, b, c], [a, c, d]] {
+                let Some((t, [s, r])) = ray_triangle_intersection(
+                    ray,
+                    triangle
+                        .map(|point| geometry.point_from_surface_coords(point)),
+                ) else {
+                    continue;
+                };
+
+                let [a, b, c] = triangle;
+                let guess = a + (b - a) * s + (c - a) * r;
+
+                hits.extend(refine_hit(ray, geometry, t, guess, size));
             }
+        }
+    }
+
+    // Neighboring triangles can share the same hit. After refining, those
+    // end up in the same place.
+    hits.sort_by_key(|(t, _)| *t);
+    hits.dedup_by(|(t, _), (prev, _)| (*t - *prev).abs() <= Scalar::from(1e-9));
+
+    hits.into_iter()
+        .filter(|(t, _)| *t >= Scalar::ZERO)
+        .filter_map(|(_, point)| intersect_face_at_point(face, &point))
+        .collect()
+}
+
+/// Intersect a ray with a triangle
+///
+/// Returns the ray coordinate of the hit, and its position within the
+/// triangle, relative to the edges that start at its first point.
+fn ray_triangle_intersection(
+    ray: &HorizontalRayToTheRight<3>,
+    [a, b, c]: [Point<3>; 3],
+) -> Option<(Scalar, [Scalar; 2])> {
+    let epsilon = Scalar::from(1e-9);
+
+    let direction = ray.direction();
+    let [ab, ac] = [b - a, c - a];
+
+    let p = direction.cross(&ac);
+    let det = ab.dot(&p);
+    if det == Scalar::ZERO {
+        return None;
+    }
+
+    let to_origin = ray.origin - a;
+    let s = to_origin.dot(&p) / det;
+    let q = to_origin.cross(&ab);
+    let r = direction.dot(&q) / det;
+    let t = ac.dot(&q) / det;
+
+    let is_within =
+        s >= -epsilon && r >= -epsilon && s + r <= Scalar::ONE + epsilon;
+    is_within.then_some((t, [s, r]))
+}
+
+/// Refine an approximate hit between a ray and a surface
+///
+/// Solves `surface(u, v) = origin + direction * t` using Newton's method,
+/// starting from the provided guess. The partial derivatives of the surface
+/// are approximated using finite differences, scaled by the size of the area
+/// of the surface that is being searched.
+fn refine_hit(
+    ray: &HorizontalRayToTheRight<3>,
+    geometry: &SurfaceGeometry,
+    mut t: Scalar,
+    mut point: Point<2>,
+    size: Vector<2>,
+) -> Option<(Scalar, Point<2>)> {
+    const MAX_ITERATIONS: usize = 32;
+
+    let epsilon = Scalar::from(1e-12);
+    let [h_u, h_v] = [size.u, size.v].map(|size| size * 1e-6);
+
+    let residual = |t: Scalar, point: Point<2>| {
+        geometry.point_from_surface_coords(point)
+            - (ray.origin + ray.direction() * t)
+    };
+
+    for _ in 0..MAX_ITERATIONS {
+        let f = residual(t, point);
+        if f.magnitude() <= epsilon {
+            return Some((t, point));
+        }
+
+        let derivative = |offset: Vector<2>, h: Scalar| {
+            (geometry.point_from_surface_coords(point + offset)
+                - geometry.point_from_surface_coords(point - offset))
+                / (h * 2.)
         };
+        let d_u = derivative(Vector::from([h_u, Scalar::ZERO]), h_u);
+        let d_v = derivative(Vector::from([Scalar::ZERO, h_v]), h_v);
+        let d_t = -ray.direction();
+
+        // Solve `d_u * du + d_v * dv + d_t * dt = -f`, using Cramer's rule.
+        let det = d_u.dot(&d_v.cross(&d_t));
+        if det == Scalar::ZERO {
+            return None;
+        }
+        let du = -f.dot(&d_v.cross(&d_t)) / det;
+        let dv = -d_u.dot(&f.cross(&d_t)) / det;
+        let dt = -d_u.dot(&d_v.cross(&f)) / det;
 
-        Some(intersection)
+        t += dt;
+        point += Vector::from([du, dv]);
     }
+
+    let converged = residual(t, point).magnitude() <= epsilon;
+    converged.then_some((t, point))
+}
+
+/// Compute the real roots of `a * x^2 + b * x + c`, in ascending order
+///
+/// If `a` is zero, this is a linear equation, which has a single root.
+fn quadratic_roots(a: Scalar, b: Scalar, c: Scalar) -> Vec<Scalar> {
+    if a == Scalar::ZERO {
+        return vec![-c / b];
+    }
+
+    let discriminant = b * b - a * c * 4.;
+    if discriminant < Scalar::ZERO {
+        return Vec::new();
+    }
+
+    let root = discriminant.sqrt();
+    let mut roots = vec![(-b - root) / (a * 2.), (-b + root) / (a * 2.)];
+    roots.sort();
+    roots
+}
+
+/// Move an angle into the range of u-coordinates that is covered by the face
+///
+/// An angle is only defined up to full turns. This picks the one that lies
+/// within the range covered by the face, if the face doesn't span more than a
+/// full turn.
+fn angle_within_face(face: &Face, angle: Scalar) -> Scalar {
+    let min_u = face
+        .region()
+        .exterior()
+        .aabb()
+        .map(|aabb| aabb.min.u)
+        .unwrap_or(Scalar::ZERO);
+
+    angle + Scalar::from(TAU) * ((min_u - angle) / TAU).ceil()
+}
+
+fn intersect_face_at_point(
+    face: &Face,
+    point: &Point<2>,
+) -> Option<RayFaceIntersection> {
+    let intersection = match (face, point).intersect()? {
+        FacePointIntersection::PointIsInsideFace => {
+            RayFaceIntersection::RayHitsFace
+        }
+        FacePointIntersection::PointIsOnEdge(edge) => {
+            RayFaceIntersection::RayHitsEdge(edge)
+        }
+        FacePointIntersection::PointIsOnVertex(vertex) => {
+            RayFaceIntersection::RayHitsVertex(vertex)
+        }
+    };
+
+    Some(intersection)
 }
 
 /// A hit between a ray and a face
@@ -142,17 +554,22 @@ pub enum RayFaceIntersection {
 
 #[cfg(test)]
 mod tests {
-    use fj_math::Point;
+    use std::f64::consts::{PI, TAU};
+
+    use fj_math::{
+        Circle, Cone, NurbsSurface, Point, Sphere, Torus, Transform, Vector,
+    };
 
     use crate::{
         algorithms::{
             intersect::{
-                ray_face::RayFaceIntersection, HorizontalRayToTheRight,
-                Intersect,
+                ray_face::{all_hits, RayFaceIntersection},
+                HorizontalRayToTheRight, Intersect,
             },
             transform::TransformObject,
         },
-        objects::{Cycle, Face},
+        geometry::{GlobalPath, SurfaceGeometry},
+        objects::{Cycle, Face, Surface},
         operations::{BuildCycle, BuildFace, Insert, UpdateFace, UpdateRegion},
         services::Services,
     };
@@ -364,4 +781,266 @@ mod tests {
 
         services.only_validate(face);
     }
+
+    #[test]
+    fn ray_hits_cylinder() {
+        let mut services = Services::new();
+
+        let ray = HorizontalRayToTheRight::from([-2., 0., 0.5]);
+
+        // The ray would pass through both halves of the cylinder, but the
+        // closer one is the one that counts.
+        let face = cylinder_face(
+            [[0., 0.], [TAU, 0.], [TAU, 1.], [0., 1.]],
+            &mut services,
+        );
+        assert_eq!(
+            (&ray, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+
+        // Here the closer half is missing, so the ray passes through it and
+        // hits the other one.
+        let face = cylinder_face(
+            [[-PI / 2., 0.], [PI / 2., 0.], [PI / 2., 1.], [-PI / 2., 1.]],
+            &mut services,
+        );
+        assert_eq!(
+            (&ray, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+
+        // And here, the ray misses the face, since it's just a narrow strip
+        // that the ray passes by.
+        let face = cylinder_face(
+            [[PI / 4., 0.], [PI / 2., 0.], [PI / 2., 1.], [PI / 4., 1.]],
+            &mut services,
+        );
+        assert_eq!((&ray, &face).intersect(), None);
+
+        services.only_validate(face);
+    }
+
+    #[test]
+    fn ray_passes_through_cylinder() {
+        let mut services = Services::new();
+
+        // Passing through the axis, the ray would hit the seam of the face.
+        let ray = HorizontalRayToTheRight::from([-2., 0.5, 0.5]);
+        let face = cylinder_face(
+            [[0., 0.], [TAU, 0.], [TAU, 1.], [0., 1.]],
+            &mut services,
+        );
+
+        // The ray hits both halves of the cylinder.
+        assert_eq!(
+            all_hits(&ray, &face, &Transform::identity()),
+            vec![RayFaceIntersection::RayHitsFace; 2]
+        );
+
+        // Moved up, the cylinder is above the ray.
+        let transform = Transform::translation([0., 0., 2.]);
+        assert_eq!(all_hits(&ray, &face, &transform), Vec::new());
+
+        services.only_validate(face);
+    }
+
+    #[test]
+    fn ray_misses_cylinder() {
+        let mut services = Services::new();
+
+        let face = cylinder_face(
+            [[0., 0.], [TAU, 0.], [TAU, 1.], [0., 1.]],
+            &mut services,
+        );
+
+        let above = HorizontalRayToTheRight::from([-2., 0., 2.]);
+        let beside = HorizontalRayToTheRight::from([-2., 2., 0.5]);
+        let behind = HorizontalRayToTheRight::from([2., 0., 0.5]);
+        assert_eq!((&above, &face).intersect(), None);
+        assert_eq!((&beside, &face).intersect(), None);
+        assert_eq!((&behind, &face).intersect(), None);
+
+        services.only_validate(face);
+    }
+
+    #[test]
+    fn ray_hits_sphere() {
+        let mut services = Services::new();
+
+        let sphere = SurfaceGeometry::Sphere(Sphere::from_center_and_radius(
+            [0., 0., 0.],
+            1.,
+        ));
+        let ray = HorizontalRayToTheRight::from([-2., 0., 0.]);
+
+        // The whole sphere, between two latitudes.
+        let face = surface_face(
+            sphere,
+            [[0., -0.5], [TAU, -0.5], [TAU, 0.5], [0., 0.5]],
+            &mut services,
+        );
+        assert_eq!(
+            (&ray, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+
+        // Without the closer half, the ray hits the other one.
+        let face = surface_face(
+            sphere,
+            [
+                [-PI / 2., -0.5],
+                [PI / 2., -0.5],
+                [PI / 2., 0.5],
+                [-PI / 2., 0.5],
+            ],
+            &mut services,
+        );
+        assert_eq!(
+            (&ray, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+
+        // Above the face, the ray still hits the sphere, but misses the face.
+        let above = HorizontalRayToTheRight::from([-2., 0., 0.9]);
+        assert_eq!((&above, &face).intersect(), None);
+
+        services.only_validate(face);
+    }
+
+    #[test]
+    fn ray_hits_cone() {
+        let mut services = Services::new();
+
+        let cone = SurfaceGeometry::Cone(Cone::new(
+            Circle::from_center_and_radius([0., 0., 0.], 1.),
+            [0., 0., 2.],
+        ));
+        let face = surface_face(
+            cone,
+            [[0., 0.], [TAU, 0.], [TAU, 0.5], [0., 0.5]],
+            &mut services,
+        );
+
+        let through = HorizontalRayToTheRight::from([-2., 0., 0.5]);
+        let above = HorizontalRayToTheRight::from([-2., 0., 1.5]);
+        let behind = HorizontalRayToTheRight::from([2., 0., 0.5]);
+        assert_eq!(
+            (&through, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+        assert_eq!((&above, &face).intersect(), None);
+        assert_eq!((&behind, &face).intersect(), None);
+
+        services.only_validate(face);
+    }
+
+    #[test]
+    fn ray_hits_torus() {
+        let mut services = Services::new();
+
+        let torus = SurfaceGeometry::Torus(Torus::from_center_and_radii(
+            [0., 0., 0.],
+            2.,
+            0.5,
+        ));
+        let ray = HorizontalRayToTheRight::from([-3., 0., 0.]);
+
+        // The outer half of the tube, on the side that is closer to the ray.
+        let face = surface_face(
+            torus,
+            [
+                [PI / 2., -PI / 2.],
+                [PI * 3. / 2., -PI / 2.],
+                [PI * 3. / 2., PI / 2.],
+                [PI / 2., PI / 2.],
+            ],
+            &mut services,
+        );
+        assert_eq!(
+            (&ray, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+
+        // The outer half of the tube, on the far side.
+        let face = surface_face(
+            torus,
+            [
+                [-PI / 4., -PI / 2.],
+                [PI / 4., -PI / 2.],
+                [PI / 4., PI / 2.],
+                [-PI / 4., PI / 2.],
+            ],
+            &mut services,
+        );
+        assert_eq!(
+            (&ray, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+
+        let above = HorizontalRayToTheRight::from([-3., 0., 1.]);
+        assert_eq!((&above, &face).intersect(), None);
+
+        services.only_validate(face);
+    }
+
+    #[test]
+    fn ray_hits_nurbs_surface() {
+        let mut services = Services::new();
+
+        // A surface that bulges towards negative x, where it reaches
+        // `x = -0.5` in its middle.
+        let surface = SurfaceGeometry::Nurbs(
+            NurbsSurface::from_control_points(
+                [1, 2],
+                [
+                    [[0., -1., -1.], [-1., 0., -1.], [0., 1., -1.]],
+                    [[0., -1., 1.], [-1., 0., 1.], [0., 1., 1.]],
+                ],
+            )
+            .into(),
+        );
+        let face = surface_face(
+            surface,
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut services,
+        );
+
+        let in_front = HorizontalRayToTheRight::from([-2., 0., 0.]);
+        let behind = HorizontalRayToTheRight::from([0., 0., 0.]);
+        let above = HorizontalRayToTheRight::from([-2., 0., 2.]);
+        assert_eq!(
+            (&in_front, &face).intersect(),
+            Some(RayFaceIntersection::RayHitsFace)
+        );
+        assert_eq!((&behind, &face).intersect(), None);
+        assert_eq!((&above, &face).intersect(), None);
+
+        services.only_validate(face);
+    }
+
+    fn cylinder_face(points: [[f64; 2]; 4], services: &mut Services) -> Face {
+        let cylinder = SurfaceGeometry::Swept {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::unit_z(),
+        };
+
+        surface_face(cylinder, points, services)
+    }
+
+    fn surface_face(
+        geometry: SurfaceGeometry,
+        points: [[f64; 2]; 4],
+        services: &mut Services,
+    ) -> Face {
+        let surface = Surface::new(geometry).insert(services);
+
+        Face::unbound(surface, services).update_region(|region| {
+            region
+                .update_exterior(|_| {
+                    Cycle::polygon(points, services).insert(services)
+                })
+                .insert(services)
+        })
+    }
 }
@@ -1,83 +1,473 @@
-use fj_math::{Line, Plane, Point, Scalar};
+use fj_math::{
+    Circle, Ellipse, Line, NurbsCurve, Plane, Point, Scalar, Vector,
+};
 
 use crate::{
-    geometry::{GlobalPath, SurfacePath},
+    algorithms::approx::Tolerance,
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
     objects::Surface,
     storage::Handle,
 };
 
+use super::curve_edge::{
+    circle_circle_intersections, line_circle_intersections,
+};
+
 /// The intersection between two surfaces
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct SurfaceSurfaceIntersection {
     /// The intersection curves
+    ///
+    /// These curves correspond to the input surfaces, each being the local
+    /// representation of the intersection on the respective surface. They
+    /// share the same curve coordinates.
     pub intersection_curves: [SurfacePath; 2],
 }
 
 impl SurfaceSurfaceIntersection {
     /// Compute the intersection between two surfaces
-    pub fn compute(surfaces: [Handle<Surface>; 2]) -> Option<Self> {
-        // Algorithm from Real-Time Collision Detection by Christer Ericson. See
-        // section 5.4.4, Intersection of Two Planes.
-        //
-        // Adaptations were made to get the intersection curves in local
-        // coordinates for each surface.
-
-        let planes = surfaces.map(|surface| plane_from_surface(&surface));
-
-        let [(a_distance, a_normal), (b_distance, b_normal)] =
-            planes.map(|plane| plane.constant_normal_form());
-
-        let direction = a_normal.cross(&b_normal);
-
-        let denom = direction.dot(&direction);
-        if denom == Scalar::ZERO {
-            // Comparing `denom` against zero looks fishy. It's probably better
-            // to compare it against an epsilon value, but I don't know how
-            // large that epsilon should be.
-            //
-            // I'll just leave it like that, until we had the opportunity to
-            // collect some experience with this code.
-            // - @hannobraun
-            return None;
+    ///
+    /// Surfaces can intersect along more than one curve, so this returns one
+    /// intersection per curve. Coincident surfaces don't have any intersection
+    /// curves.
+    ///
+    /// Planes and cylinders are supported. Two cylinders are only supported,
+    /// if their axes are parallel, and each of them is swept along its axis.
+    ///
+    /// Some intersection curves can't be represented exactly, like that of a
+    /// cylinder with an oblique plane, as seen on the cylinder. Those are
+    /// approximated, deviating from the exact curve by no more than the
+    /// provided tolerance.
+    pub fn compute(
+        surfaces: [Handle<Surface>; 2],
+        tolerance: impl Into<Tolerance>,
+    ) -> Vec<Self> {
+        let tolerance = tolerance.into();
+        let [a, b] = surfaces.map(|surface| Kind::from_surface(&surface));
+
+        let curves = match (a, b) {
+            (Kind::Plane(a), Kind::Plane(b)) => {
+                plane_plane([a, b]).into_iter().collect()
+            }
+            (Kind::Plane(plane), Kind::Cylinder(cylinder)) => {
+                plane_cylinder(&plane, &cylinder, tolerance)
+            }
+            (Kind::Cylinder(cylinder), Kind::Plane(plane)) => {
+                plane_cylinder(&plane, &cylinder, tolerance)
+                    .into_iter()
+                    .map(|[a, b]| [b, a])
+                    .collect()
+            }
+            (Kind::Cylinder(a), Kind::Cylinder(b)) => cylinder_cylinder([a, b]),
+        };
+
+        curves
+            .into_iter()
+            .map(|intersection_curves| Self {
+                intersection_curves,
+            })
+            .collect()
+    }
+}
+
+enum Kind {
+    Plane(Plane),
+    Cylinder(Cylinder),
+}
+
+impl Kind {
+    fn from_surface(surface: &Surface) -> Self {
+        match surface.geometry() {
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Line(line),
+                v,
+            } => Self::Plane(Plane::from_parametric(
+                line.origin(),
+                line.direction(),
+                v,
+            )),
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Circle(circle),
+                v,
+            } => Self::Cylinder(Cylinder { circle, path: v }),
+            _ => todo!(
+                "Only intersections between planes and cylinders are currently \
+                supported."
+            ),
         }
+    }
+}
 
-        let origin = (b_normal * a_distance - a_normal * b_distance)
-            .cross(&direction)
-            / denom;
-        let origin = Point { coords: origin };
+/// A surface that is swept from a circle
+struct Cylinder {
+    circle: Circle<3>,
+    path: Vector<3>,
+}
 
-        let line = Line::from_origin_and_direction(origin, direction);
+impl Cylinder {
+    /// The plane that the cylinder's circle is in
+    ///
+    /// In the coordinates of this plane, the circle is the unit circle around
+    /// the origin, and its circle coordinates are the same as the angle in the
+    /// plane.
+    fn plane(&self) -> Plane {
+        Plane::from_parametric(
+            self.circle.center(),
+            self.circle.a(),
+            self.circle.b(),
+        )
+    }
 
-        let curves =
-            planes.map(|plane| SurfacePath::Line(plane.project_line(&line)));
+    /// Build a path on the cylinder that goes along `path` from `point`
+    ///
+    /// Expects the point to be located on the circle. The curve coordinates of
+    /// the path are the same as those of the global line from `point` along
+    /// `path`.
+    fn line_along_path(&self, point: Point<3>) -> SurfacePath {
+        let u = self.circle.point_to_circle_coords(point).t;
 
-        Some(Self {
-            intersection_curves: curves,
-        })
+        SurfacePath::line_from_points_with_coords([
+            ([0.], [u, Scalar::ZERO]),
+            ([1.], [u, Scalar::ONE]),
+        ])
     }
 }
 
-fn plane_from_surface(surface: &Surface) -> Plane {
-    let (line, path) = {
-        let line = match surface.geometry().u {
-            GlobalPath::Line(line) => line,
-            _ => todo!("Only plane-plane intersection is currently supported."),
+fn plane_plane(planes: [Plane; 2]) -> Option<[SurfacePath; 2]> {
+    let line = plane_plane_line(&planes)?;
+    Some(planes.map(|plane| SurfacePath::Line(plane.project_line(&line))))
+}
+
+fn plane_plane_line([a, b]: &[Plane; 2]) -> Option<Line<3>> {
+    // Algorithm from Real-Time Collision Detection by Christer Ericson. See
+    // section 5.4.4, Intersection of Two Planes.
+    //
+    // Adaptations were made to get the intersection curves in local
+    // coordinates for each surface.
+
+    let [(a_distance, a_normal), (b_distance, b_normal)] =
+        [a, b].map(|plane| plane.constant_normal_form());
+
+    let direction = a_normal.cross(&b_normal);
+
+    let denom = direction.dot(&direction);
+    if denom == Scalar::ZERO {
+        // Comparing `denom` against zero looks fishy. It's probably better
+        // to compare it against an epsilon value, but I don't know how
+        // large that epsilon should be.
+        //
+        // I'll just leave it like that, until we had the opportunity to
+        // collect some experience with this code.
+        // - @hannobraun
+        return None;
+    }
+
+    let origin = (b_normal * a_distance - a_normal * b_distance)
+        .cross(&direction)
+        / denom;
+    let origin = Point { coords: origin };
+
+    Some(Line::from_origin_and_direction(origin, direction))
+}
+
+fn plane_cylinder(
+    plane: &Plane,
+    cylinder: &Cylinder,
+    tolerance: Tolerance,
+) -> Vec<[SurfacePath; 2]> {
+    let normal = plane.normal();
+    let circle = &cylinder.circle;
+    let path = cylinder.path;
+
+    if is_perpendicular(&path, &normal) {
+        // The plane is parallel to the direction the cylinder is swept in, so
+        // it intersects the cylinder along lines in that direction. Those go
+        // through the points, where the plane intersects the cylinder's
+        // circle.
+        let circle_plane = cylinder.plane();
+        let Some(line) = plane_plane_line(&[*plane, circle_plane]) else {
+            unreachable!(
+                "Plane contains the path of the cylinder, so it can't be \
+                parallel to its circle"
+            );
         };
 
-        (line, surface.geometry().v)
+        let unit_circle =
+            Circle::from_center_and_radius(Point::origin(), Scalar::ONE);
+
+        return line_circle_intersections(
+            &circle_plane.project_line(&line),
+            &unit_circle,
+        )
+        .into_iter()
+        .map(|t| {
+            let point = line.point_from_line_coords([t]);
+            let line_global = Line::from_origin_and_direction(point, path);
+
+            [
+                SurfacePath::Line(plane.project_line(&line_global)),
+                cylinder.line_along_path(point),
+            ]
+        })
+        .collect();
+    }
+
+    // Otherwise, every line along the path of the cylinder intersects the
+    // plane in exactly one point. For a point on the circle at angle `t`, it
+    // is offset along the path by `k0 + k1 * cos(t) + k2 * sin(t)`.
+    let path_dot_normal = path.dot(&normal);
+    let [k0, k1, k2] =
+        [circle.center() - plane.origin(), circle.a(), circle.b()]
+            .map(|vector| -vector.dot(&normal) / path_dot_normal);
+
+    // Moving the circle's center and axes along the path by those offsets
+    // gives us the intersection curve in global coordinates. Then we just need
+    // to project that into the plane.
+    let center = plane.project_point(circle.center() + path * k0);
+    let a = plane.project_vector(circle.a() + path * k1);
+    let b = plane.project_vector(circle.b() + path * k2);
+
+    let curve_in_plane = if is_circle(&a, &b) {
+        SurfacePath::Circle(Circle::new(center, a, b))
+    } else {
+        SurfacePath::Ellipse(Ellipse::new(center, a, b))
+    };
+
+    let curve_on_cylinder = if is_perpendicular(&circle.a(), &normal)
+        && is_perpendicular(&circle.b(), &normal)
+    {
+        SurfacePath::line_from_points_with_coords([
+            ([0.], [Scalar::ZERO, k0]),
+            ([1.], [Scalar::ONE, k0]),
+        ])
+    } else {
+        // On the cylinder, this curve is a sine wave. It can't be
+        // represented by any of the paths we have, so we approximate it. The
+        // v-coordinate is scaled by the length of the path, when converted
+        // into global coordinates, and so is any deviation.
+        SurfacePath::Nurbs(
+            nurbs_from_graph(
+                |t| {
+                    let (sin, cos) = t.sin_cos();
+                    k0 + k1 * cos + k2 * sin
+                },
+                |t| {
+                    let (sin, cos) = t.sin_cos();
+                    k2 * cos - k1 * sin
+                },
+                (k1 * k1 + k2 * k2).sqrt(),
+                path.magnitude(),
+                tolerance,
+            )
+            .into(),
+        )
     };
 
-    Plane::from_parametric(line.origin(), line.direction(), path)
+    vec![[curve_in_plane, curve_on_cylinder]]
+}
+
+fn cylinder_cylinder(cylinders: [Cylinder; 2]) -> Vec<[SurfacePath; 2]> {
+    let [a, b] = &cylinders;
+
+    for cylinder in &cylinders {
+        let circle = &cylinder.circle;
+        if !is_parallel(&cylinder.path, &circle.a().cross(&circle.b())) {
+            todo!(
+                "Intersecting cylinders that are not swept along their axis is \
+                not supported yet"
+            );
+        }
+    }
+    if !is_parallel(&a.path, &b.path) {
+        todo!(
+            "Intersecting cylinders with axes that are not parallel is not \
+            supported yet"
+        );
+    }
+
+    // Since both cylinders are swept along their parallel axes, we can project
+    // the circle of `b` into the plane of `a`'s circle, and intersect the two
+    // circles there. In the coordinates of that plane, `a`'s circle is the
+    // unit circle.
+    let plane = a.plane();
+    let circle_a = Circle::from_center_and_radius(Point::origin(), Scalar::ONE);
+    let circle_b = Circle::from_center_and_radius(
+        plane.project_point(b.circle.center()),
+        b.circle.radius() / a.circle.radius(),
+    );
+
+    if circle_b.center().coords.magnitude() <= epsilon() {
+        // The cylinders are coaxial. They're either coincident, or they don't
+        // touch at all.
+        return Vec::new();
+    }
+
+    let b_normal = b.circle.a().cross(&b.circle.b());
+    let path_dot_normal = b.path.dot(&b_normal);
+
+    circle_circle_intersections(&circle_a, &circle_b)
+        .into_iter()
+        .map(|point| {
+            let point = a.circle.center()
+                + a.circle.a() * point.u
+                + a.circle.b() * point.v;
+
+            // On `b`, the line starts wherever `point` is along `b`'s path,
+            // and the paths of both cylinders might differ in length.
+            let v =
+                (point - b.circle.center()).dot(&b_normal) / path_dot_normal;
+            let dv = a.path.dot(&b_normal) / path_dot_normal;
+            let u = b.circle.point_to_circle_coords(point - b.path * v).t;
+
+            [
+                a.line_along_path(point),
+                SurfacePath::line_from_points_with_coords([
+                    ([0.], [u, v]),
+                    ([1.], [u, v + dv]),
+                ]),
+            ]
+        })
+        .collect()
+}
+
+/// Approximate the graph of a function with a NURBS curve
+///
+/// The curve covers a full turn of the u-coordinate, and its curve coordinates
+/// are equal to that u-coordinate. Only the v-coordinate is approximated, using
+/// cubic Hermite interpolation of `f` and its derivative `df`.
+///
+/// `max_fourth_derivative` is an upper bound for the fourth derivative of `f`.
+/// Deviations in the v-coordinate are multiplied by `scale`, before comparing
+/// them to the tolerance.
+fn nurbs_from_graph(
+    f: impl Fn(Scalar) -> Scalar,
+    df: impl Fn(Scalar) -> Scalar,
+    max_fourth_derivative: Scalar,
+    scale: Scalar,
+    tolerance: Tolerance,
+) -> NurbsCurve<2> {
+    // Even if the function is almost flat, we want enough segments to follow
+    // its general shape.
+    const MIN_SEGMENTS: u64 = 4;
+
+    // Limits the refinement, in case the tolerance can't be reached due to
+    // floating point inaccuracies.
+    const MAX_SEGMENTS: u64 = 1 << 16;
+
+    const SAMPLES_PER_SEGMENT: u64 = 4;
+
+    // The distance between the function and its cubic Hermite interpolation
+    // is no larger than `h^4 / 384`, times the maximum of the fourth
+    // derivative, where `h` is the length of a segment. That gives us a good
+    // place to start.
+    let mut num_segments = (Scalar::TAU
+        * (max_fourth_derivative * scale / (tolerance.inner() * 384.))
+            .sqrt()
+            .sqrt())
+    .ceil()
+    .into_u64()
+    .max(MIN_SEGMENTS);
+
+    loop {
+        let curve = hermite_curve(&f, &df, num_segments);
+
+        // The u-coordinate of the curve is exact, so we only need to check the
+        // v-coordinate, at a few points between the ends of each segment.
+        let num_samples = num_segments * SAMPLES_PER_SEGMENT;
+        let step = Scalar::TAU / Scalar::from_u64(num_samples);
+        let max_deviation = (0..num_samples)
+            .map(|i| {
+                let t = (Scalar::from_u64(i) + 0.5) * step;
+                let point = curve.point_from_nurbs_coords([t]);
+                (point.v - f(t)).abs() * scale
+            })
+            .max()
+            .unwrap_or(Scalar::ZERO);
+
+        if max_deviation <= tolerance.inner() || num_segments >= MAX_SEGMENTS {
+            return curve;
+        }
+
+        num_segments *= 2;
+    }
+}
+
+/// Interpolate the graph of a function with cubic Hermite segments
+///
+/// See [`nurbs_from_graph`].
+fn hermite_curve(
+    f: impl Fn(Scalar) -> Scalar,
+    df: impl Fn(Scalar) -> Scalar,
+    num_segments: u64,
+) -> NurbsCurve<2> {
+    const DEGREE: usize = 3;
+
+    let length = Scalar::TAU / Scalar::from_u64(num_segments);
+    let breaks = (0..=num_segments)
+        .map(|i| Scalar::from_u64(i) * length)
+        .collect::<Vec<_>>();
+
+    // Each segment is a cubic Bézier curve. Its control points are evenly
+    // spaced along the u-axis, which makes the u-coordinate equal to the curve
+    // coordinate.
+    let mut control_points = vec![Point::from([Scalar::ZERO, f(Scalar::ZERO)])];
+    for window in breaks.windows(2) {
+        let [start, end] = [window[0], window[1]];
+        let third = length / 3.;
+
+        control_points.extend([
+            Point::from([start + third, f(start) + df(start) * third]),
+            Point::from([end - third, f(end) - df(end) * third]),
+            Point::from([end, f(end)]),
+        ]);
+    }
+
+    let mut knots = vec![Scalar::ZERO; DEGREE + 1];
+    for &t in &breaks[1..breaks.len() - 1] {
+        knots.extend([t; DEGREE]);
+    }
+    knots.extend([Scalar::TAU; DEGREE + 1]);
+
+    let weights = vec![Scalar::ONE; control_points.len()];
+
+    NurbsCurve::new(DEGREE, control_points, weights, knots)
+}
+
+/// The tolerance used when checking vectors for being parallel or perpendicular
+///
+/// This is the same tolerance that [`Circle::new`] uses.
+fn epsilon() -> Scalar {
+    Scalar::from_f64(f64::EPSILON * 16.)
+}
+
+fn is_parallel(a: &Vector<3>, b: &Vector<3>) -> bool {
+    a.cross(b).magnitude() <= a.magnitude() * b.magnitude() * epsilon()
+}
+
+fn is_perpendicular(a: &Vector<3>, b: &Vector<3>) -> bool {
+    a.dot(b).abs() <= a.magnitude() * b.magnitude() * epsilon()
+}
+
+/// Check whether the provided axes describe a circle, as per [`Circle::new`]
+fn is_circle(a: &Vector<2>, b: &Vector<2>) -> bool {
+    (a.magnitude() - b.magnitude()).abs() <= a.magnitude() * epsilon()
+        && a.dot(b).abs()
+            < Scalar::from_f64(f64::EPSILON)
+                .max(a.magnitude() * b.magnitude() * epsilon())
 }
 
 #[cfg(test)]
 mod tests {
-    use fj_math::Transform;
+    use fj_math::{Scalar, Transform, Vector};
     use pretty_assertions::assert_eq;
 
     use crate::{
-        algorithms::transform::TransformObject, geometry::SurfacePath,
+        algorithms::{approx::Tolerance, transform::TransformObject},
+        geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+        objects::Surface,
+        operations::Insert,
         services::Services,
+        storage::Handle,
     };
 
     use super::SurfaceSurfaceIntersection;
@@ -91,24 +481,223 @@ mod tests {
 
         // Coincident and parallel planes don't have an intersection curve.
         assert_eq!(
-            SurfaceSurfaceIntersection::compute([
-                xy.clone(),
-                xy.clone().transform(
-                    &Transform::translation([0., 0., 1.],),
-                    &mut services
-                )
-            ],),
-            None,
+            SurfaceSurfaceIntersection::compute(
+                [
+                    xy.clone(),
+                    xy.clone().transform(
+                        &Transform::translation([0., 0., 1.],),
+                        &mut services
+                    )
+                ],
+                tolerance()
+            ),
+            vec![],
         );
 
         let expected_xy = SurfacePath::u_axis();
         let expected_xz = SurfacePath::u_axis();
 
         assert_eq!(
-            SurfaceSurfaceIntersection::compute([xy, xz],),
-            Some(SurfaceSurfaceIntersection {
+            SurfaceSurfaceIntersection::compute([xy, xz], tolerance()),
+            vec![SurfaceSurfaceIntersection {
                 intersection_curves: [expected_xy, expected_xz],
-            })
+            }]
+        );
+    }
+
+    #[test]
+    fn plane_cylinder() {
+        let mut services = Services::new();
+
+        let cylinder = cylinder([0., 0., 0.], 1., &mut services);
+
+        // A plane that is parallel to the axis cuts the cylinder along two
+        // lines, or touches it along one.
+        let xz = services.objects.surfaces.xz_plane();
+        let touching = xz
+            .clone()
+            .transform(&Transform::translation([0., 1., 0.]), &mut services);
+        let missing = xz
+            .clone()
+            .transform(&Transform::translation([0., 2., 0.]), &mut services);
+
+        let intersections = SurfaceSurfaceIntersection::compute(
+            [xz.clone(), cylinder.clone()],
+            tolerance(),
+        );
+        assert_eq!(intersections.len(), 2);
+        assert_on_both_surfaces(&intersections, [&xz, &cylinder], 1e-12);
+        for intersection in &intersections {
+            assert!(matches!(
+                intersection.intersection_curves,
+                [SurfacePath::Line(_), SurfacePath::Line(_)]
+            ));
+        }
+
+        let intersections = SurfaceSurfaceIntersection::compute(
+            [touching.clone(), cylinder.clone()],
+            tolerance(),
+        );
+        assert_eq!(intersections.len(), 1);
+        assert_on_both_surfaces(&intersections, [&touching, &cylinder], 1e-12);
+
+        assert_eq!(
+            SurfaceSurfaceIntersection::compute(
+                [missing, cylinder.clone()],
+                tolerance()
+            ),
+            vec![],
+        );
+
+        // A plane that is perpendicular to the axis cuts the cylinder along a
+        // circle.
+        let xy = services
+            .objects
+            .surfaces
+            .xy_plane()
+            .transform(&Transform::translation([0., 0., 0.5]), &mut services);
+
+        let intersections = SurfaceSurfaceIntersection::compute(
+            [cylinder.clone(), xy.clone()],
+            tolerance(),
+        );
+        assert_eq!(intersections.len(), 1);
+        assert_on_both_surfaces(&intersections, [&cylinder, &xy], 1e-12);
+        assert!(matches!(
+            intersections[0].intersection_curves,
+            [SurfacePath::Line(_), SurfacePath::Circle(_)]
+        ));
+
+        // And any other plane cuts it along an ellipse.
+        let oblique = services.objects.surfaces.xy_plane().transform(
+            &Transform::rotation(Vector::from([0.5, 0., 0.])),
+            &mut services,
+        );
+
+        let intersections = SurfaceSurfaceIntersection::compute(
+            [oblique.clone(), cylinder.clone()],
+            tolerance(),
         );
+        assert_eq!(intersections.len(), 1);
+        assert_on_both_surfaces(&intersections, [&oblique, &cylinder], 1e-6);
+        assert!(matches!(
+            intersections[0].intersection_curves,
+            [SurfacePath::Ellipse(_), SurfacePath::Nurbs(_)]
+        ));
+    }
+
+    #[test]
+    fn plane_cylinder_within_tolerance() {
+        let mut services = Services::new();
+
+        let cylinder = cylinder([0., 0., 0.], 1., &mut services);
+        let oblique = services.objects.surfaces.xy_plane().transform(
+            &Transform::rotation(Vector::from([1., 0., 0.])),
+            &mut services,
+        );
+
+        let mut num_control_points = Vec::new();
+        for tolerance in [1e-2, 1e-4, 1e-8] {
+            let intersections = SurfaceSurfaceIntersection::compute(
+                [oblique.clone(), cylinder.clone()],
+                tolerance,
+            );
+
+            let [SurfacePath::Ellipse(ellipse), SurfacePath::Nurbs(curve)] =
+                &intersections[0].intersection_curves
+            else {
+                panic!("Expected an ellipse and a NURBS curve");
+            };
+            num_control_points.push(curve.control_points().len());
+
+            // The ellipse is exact, so it can serve as a reference.
+            for i in 0..=100 {
+                let t = Scalar::TAU * Scalar::from(i as f64 / 100.);
+
+                let expected = oblique.geometry().point_from_surface_coords(
+                    ellipse.point_from_ellipse_coords([t]),
+                );
+                let actual = cylinder.geometry().point_from_surface_coords(
+                    curve.point_from_nurbs_coords([t]),
+                );
+
+                assert!(
+                    (expected - actual).magnitude() <= Scalar::from(tolerance)
+                );
+            }
+        }
+
+        // A finer tolerance must result in a finer approximation.
+        assert!(num_control_points.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn cylinder_cylinder() {
+        let mut services = Services::new();
+
+        let a = cylinder([0., 0., 0.], 1., &mut services);
+        let b = cylinder([1., 1., 0.], 1., &mut services);
+
+        let intersections = SurfaceSurfaceIntersection::compute(
+            [a.clone(), b.clone()],
+            tolerance(),
+        );
+        assert_eq!(intersections.len(), 2);
+        assert_on_both_surfaces(&intersections, [&a, &b], 1e-12);
+
+        // Coaxial cylinders don't have intersection curves, whether they are
+        // coincident or not.
+        let coincident = cylinder([0., 0., 0.], 1., &mut services);
+        let inner = cylinder([0., 0., 0.], 0.5, &mut services);
+        assert_eq!(
+            SurfaceSurfaceIntersection::compute(
+                [a.clone(), coincident],
+                tolerance()
+            ),
+            vec![],
+        );
+        assert_eq!(
+            SurfaceSurfaceIntersection::compute([a, inner], tolerance()),
+            vec![]
+        );
+    }
+
+    fn tolerance() -> Tolerance {
+        Tolerance::from_scalar(1e-6).unwrap()
+    }
+
+    fn cylinder(
+        center: [f64; 3],
+        radius: f64,
+        services: &mut Services,
+    ) -> Handle<Surface> {
+        let surface = Surface::new(SurfaceGeometry::Swept {
+            u: GlobalPath::circle_from_radius(radius),
+            v: Vector::unit_z(),
+        })
+        .insert(services);
+
+        surface.transform(&Transform::translation(center), services)
+    }
+
+    fn assert_on_both_surfaces(
+        intersections: &[SurfaceSurfaceIntersection],
+        surfaces: [&Handle<Surface>; 2],
+        tolerance: f64,
+    ) {
+        for intersection in intersections {
+            for t in [0., 0.5, 1., 2., 3.] {
+                let [a, b] = [0, 1].map(|i| {
+                    let point = intersection.intersection_curves[i]
+                        .point_from_path_coords([t]);
+                    surfaces[i].geometry().point_from_surface_coords(point)
+                });
+
+                assert!(
+                    (a - b).magnitude() < Scalar::from(tolerance),
+                    "Expected curves to match at {t}: {a:?} != {b:?}"
+                );
+            }
+        }
     }
 }
@@ -13,6 +13,7 @@
 pub mod approx;
 pub mod bounding_volume;
 pub mod intersect;
+pub mod revolve;
 pub mod sweep;
 pub mod transform;
 pub mod triangulate;
@@ -0,0 +1,668 @@
+use fj_interop::{ext::ArrayExt, mesh::Color};
+use fj_math::{Circle, Cone, Line, Point, Scalar, Torus, Transform, Vector};
+
+use crate::{
+    algorithms::{
+        approx::Tolerance, sweep::SweepCache, transform::TransformObject,
+    },
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{Curve, Cycle, Edge, Face, Region, Shell, Surface, Vertex},
+    operations::{Insert, Reverse},
+    services::Services,
+    storage::Handle,
+};
+
+use super::Revolve;
+
+impl Revolve for Handle<Face> {
+    type Revolved = Handle<Shell>;
+
+    fn revolve_with_cache(
+        self,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Revolved {
+        let revolution =
+            Revolution::new(&self, axis, angle.into(), tolerance.into());
+
+        // The side faces are built from the edges of the face that faces in
+        // the direction of the revolution. If the provided face doesn't, we
+        // need to reverse it.
+        let normal = self.surface().geometry().plane_normal().unwrap_or_else(
+            || todo!("Revolving faces defined in round surfaces is not supported"),
+        );
+        let is_forward = revolution.is_forward(normal);
+        let front = if is_forward {
+            self.clone()
+        } else {
+            self.reverse(services).insert(services)
+        };
+
+        let (mut faces, top_face) =
+            revolution.revolve_front_face(&front, cache, services);
+
+        if let Some(top_face) = top_face {
+            let bottom_face = if is_forward {
+                self.reverse(services).insert(services)
+            } else {
+                self.clone()
+            };
+
+            faces.push(bottom_face);
+            faces.push(top_face);
+        }
+
+        Shell::new(faces).insert(services)
+    }
+}
+
+/// The parameters of a revolution, normalized to simplify the computations
+pub(crate) struct Revolution {
+    /// A point on the axis
+    origin: Point<3>,
+
+    /// The direction of the axis, normalized
+    direction: Vector<3>,
+
+    /// The direction from the axis towards the revolved face, normalized
+    radial: Vector<3>,
+
+    /// The angle of the revolution, always positive
+    angle: Scalar,
+
+    /// Points that are closer to the axis than this are considered on it
+    tolerance: Scalar,
+}
+
+impl Revolution {
+    /// Prepare the revolution of a face around the given axis
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the angle is zero or exceeds a full turn, if the axis doesn't
+    /// lie in the plane of the face, or if the face crosses the axis.
+    pub(crate) fn new(
+        face: &Face,
+        axis: Line<3>,
+        angle: Scalar,
+        tolerance: Tolerance,
+    ) -> Self {
+        let tolerance = tolerance.inner();
+
+        assert!(
+            angle != Scalar::ZERO && angle.abs() <= Scalar::TAU,
+            "Angle of revolution must not be zero or exceed a full turn"
+        );
+
+        let surface = face.surface().geometry();
+        let normal = surface.plane_normal().unwrap_or_else(|| {
+            todo!("Revolving faces defined in round surfaces is not supported")
+        });
+
+        // Revolving by a negative angle is the same as revolving by a positive
+        // angle around the reversed axis. Let's normalize that here, so we
+        // don't have to deal with negative angles below.
+        let (direction, angle) = if angle < Scalar::ZERO {
+            (-axis.direction().normalize(), -angle)
+        } else {
+            (axis.direction().normalize(), angle)
+        };
+
+        let origin = axis.origin();
+        let surface_origin = surface.point_from_surface_coords([0., 0.]);
+        assert!(
+            direction.dot(&normal).abs() <= tolerance
+                && (origin - surface_origin).dot(&normal).abs() <= tolerance,
+            "Axis of revolution must lie in the plane of the face"
+        );
+
+        let radial = {
+            let perpendicular = normal.cross(&direction);
+
+            let distances = face
+                .region()
+                .all_cycles()
+                .flat_map(|cycle| cycle.edges())
+                .map(|edge| {
+                    let position = surface
+                        .point_from_surface_coords(edge.start_position());
+                    (position - origin).dot(&perpendicular)
+                })
+                .collect::<Vec<_>>();
+
+            if distances.iter().all(|&distance| distance >= -tolerance) {
+                perpendicular
+            } else if distances.iter().all(|&distance| distance <= tolerance) {
+                -perpendicular
+            } else {
+                panic!("Face to revolve must not cross the axis of revolution")
+            }
+        };
+
+        Self {
+            origin,
+            direction,
+            radial,
+            angle,
+            tolerance,
+        }
+    }
+
+    /// Indicate whether a face with the given normal faces forward
+    ///
+    /// A face faces forward, if its normal points in the direction that its
+    /// points move in, when they are revolved.
+    pub(crate) fn is_forward(&self, normal: Vector<3>) -> bool {
+        normal.dot(&self.direction.cross(&self.radial)) > Scalar::ZERO
+    }
+
+    /// Revolve a face that faces forward
+    ///
+    /// Returns the faces that the edges of the face sweep out. In case of a
+    /// partial revolution, also returns the face at the end of it, which
+    /// faces forward too.
+    pub(crate) fn revolve_front_face(
+        &self,
+        front: &Face,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> (Vec<Handle<Face>>, Option<Handle<Face>>) {
+        let surface = front.surface().geometry();
+        let color = front.region().color();
+
+        let mut faces = Vec::new();
+
+        for cycle in front.region().all_cycles() {
+            for (edge, next) in cycle.edges().pairs() {
+                let vertices = [edge.start_vertex(), next.start_vertex()];
+
+                if let Some(face) = self
+                    .side_face(edge, vertices, &surface, color, cache, services)
+                {
+                    faces.push(face);
+                }
+            }
+        }
+
+        if self.is_full() {
+            return (faces, None);
+        }
+
+        let mut top_cycles = Vec::new();
+        for cycle in front.region().all_cycles() {
+            let mut edges = Vec::new();
+
+            for edge in cycle.edges() {
+                let positions = positions(edge, &surface);
+                let curve =
+                    self.rotated_curve(edge, positions, cache, services);
+                let start_vertex = self.rotated_vertex(
+                    edge.start_vertex(),
+                    positions[0],
+                    cache,
+                    services,
+                );
+
+                edges.push(
+                    Edge::new(
+                        edge.path(),
+                        edge.boundary(),
+                        curve,
+                        start_vertex,
+                    )
+                    .insert(services),
+                );
+            }
+
+            top_cycles.push(Cycle::new(edges).insert(services));
+        }
+
+        let transform = Transform::translation(self.origin.coords)
+            * Transform::rotation(self.direction * self.angle)
+            * Transform::translation(-self.origin.coords);
+        let top_surface =
+            front.surface().clone().transform(&transform, services);
+
+        let mut top_cycles = top_cycles.into_iter();
+        let exterior = top_cycles
+            .next()
+            .expect("Region must have an exterior cycle");
+        let region = Region::new(exterior, top_cycles, color).insert(services);
+
+        let top_face = Face::new(top_surface, region).insert(services);
+
+        (faces, Some(top_face))
+    }
+
+    fn is_full(&self) -> bool {
+        self.angle >= Scalar::TAU
+    }
+
+    /// Compute the point on the axis that is closest to the given point
+    fn center(&self, point: Point<3>) -> Point<3> {
+        self.origin
+            + self.direction * (point - self.origin).dot(&self.direction)
+    }
+
+    /// Compute the distance of a point in the revolved face from the axis
+    fn radius(&self, point: Point<3>) -> Scalar {
+        let radius = (point - self.origin).dot(&self.radial);
+
+        if radius <= self.tolerance {
+            Scalar::ZERO
+        } else {
+            radius
+        }
+    }
+
+    /// Access the curve that the given vertex follows during the revolution
+    fn arc(
+        &self,
+        vertex: &Handle<Vertex>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Handle<Curve> {
+        cache
+            .curves
+            .entry(vertex.id())
+            .or_insert_with(|| Curve::new().insert(services))
+            .clone()
+    }
+
+    /// Access the vertex that the given vertex ends up as after the revolution
+    fn rotated_vertex(
+        &self,
+        vertex: &Handle<Vertex>,
+        position: Point<3>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Handle<Vertex> {
+        // After a full revolution, everything ends up where it started. The
+        // same is true for anything located on the axis.
+        if self.is_full() || self.radius(position) == Scalar::ZERO {
+            return vertex.clone();
+        }
+
+        cache
+            .vertices
+            .entry(vertex.id())
+            .or_insert_with(|| Vertex::new().insert(services))
+            .clone()
+    }
+
+    /// Access the curve that the given edge ends up on after the revolution
+    fn rotated_curve(
+        &self,
+        edge: &Edge,
+        positions: [Point<3>; 2],
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Handle<Curve> {
+        let is_on_axis = positions
+            .into_iter()
+            .all(|position| self.radius(position) == Scalar::ZERO);
+
+        if self.is_full() || is_on_axis {
+            return edge.curve().clone();
+        }
+
+        // The cache is also used to look up the arcs that vertices follow
+        // during the revolution, but those are keyed by the IDs of the
+        // vertices. Object IDs are unique, so they can't get mixed up.
+        cache
+            .curves
+            .entry(edge.curve().id())
+            .or_insert_with(|| Curve::new().insert(services))
+            .clone()
+    }
+
+    /// Create the face that the given edge sweeps out during the revolution
+    ///
+    /// Returns `None`, if the edge is located on the axis, as it doesn't sweep
+    /// out any area then.
+    fn side_face(
+        &self,
+        edge: &Edge,
+        vertices: [&Handle<Vertex>; 2],
+        surface: &SurfaceGeometry,
+        color: Option<Color>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Option<Handle<Face>> {
+        let positions = positions(edge, surface);
+        let [r_a, r_b] = positions.map(|position| self.radius(position));
+        let t = edge.boundary().inner.map(|point| point.t);
+        let [t_a, t_b] = t;
+
+        // Most edges sweep out a curved surface. We define it such that the
+        // u-coordinate is the angle of the revolution, while the v-coordinate
+        // depends linearly on the curve coordinate of the edge. Only lines that
+        // are perpendicular to the axis sweep out a plane.
+        let curved = match edge.path() {
+            SurfacePath::Line(line) => {
+                let origin = surface.point_from_surface_coords(line.origin());
+                let direction =
+                    surface.vector_from_surface_coords(line.direction());
+
+                let is_parallel =
+                    direction.normalize().cross(&self.direction).magnitude()
+                        <= self.tolerance;
+                let is_perpendicular =
+                    direction.normalize().dot(&self.direction).abs()
+                        <= self.tolerance;
+
+                if is_parallel && r_a == Scalar::ZERO {
+                    return None;
+                }
+
+                if is_parallel {
+                    Some(self.cylinder(origin, direction, t))
+                } else if is_perpendicular {
+                    None
+                } else {
+                    Some(self.cone(origin, direction, t, [r_a, r_b]))
+                }
+            }
+            SurfacePath::Circle(circle) => {
+                Some(self.torus(&circle, surface, t))
+            }
+            SurfacePath::Ellipse(_) | SurfacePath::Nurbs(_) => {
+                todo!(
+                    "Revolving elliptical or NURBS edges is not supported yet"
+                )
+            }
+        };
+
+        let [a, b] = vertices;
+        let [a_rotated, b_rotated] =
+            vertices.zip_ext(positions).map(|(vertex, position)| {
+                self.rotated_vertex(vertex, position, cache, services)
+            });
+        let curve_rotated =
+            self.rotated_curve(edge, positions, cache, services);
+
+        let zero = Scalar::ZERO;
+        let angle = self.angle;
+
+        let (geometry, exterior, interiors) = match curved {
+            Some((geometry, [v_a, v_b])) => {
+                // Where the edge touches the axis, the surface collapses into a
+                // single point. The face is bounded there by an edge that
+                // collapses too, and that doesn't need to be shared with any
+                // other face.
+                let [arc_a, arc_b] =
+                    [(a, r_a), (b, r_b)].map(|(vertex, radius)| {
+                        if radius > zero {
+                            self.arc(vertex, cache, services)
+                        } else {
+                            Curve::new().insert(services)
+                        }
+                    });
+
+                let edges = [
+                    ([[zero, v_a], [zero, v_b]], [t_a, t_b], edge.curve(), a),
+                    ([[zero, v_b], [angle, v_b]], [zero, angle], &arc_b, b),
+                    (
+                        [[angle, v_b], [angle, v_a]],
+                        [t_b, t_a],
+                        &curve_rotated,
+                        &b_rotated,
+                    ),
+                    (
+                        [[angle, v_a], [zero, v_a]],
+                        [angle, zero],
+                        &arc_a,
+                        &a_rotated,
+                    ),
+                ]
+                .map(|(points, boundary, curve, vertex)| {
+                    line_segment(points, boundary, curve, vertex, services)
+                });
+
+                (geometry, Cycle::new(edges).insert(services), Vec::new())
+            }
+            None => {
+                // The edge sweeps out a planar ring, or part of one. We define
+                // its surface such that its surface coordinates relate to the
+                // angle of the revolution in the same way as a circle's.
+                let geometry = SurfaceGeometry::Swept {
+                    u: GlobalPath::Line(Line::from_origin_and_direction(
+                        self.center(positions[0]),
+                        self.radial,
+                    )),
+                    v: self.direction.cross(&self.radial),
+                };
+
+                // Vertices on the axis don't move, so they don't create arcs.
+                let [arc_a, arc_b] =
+                    [(a, r_a), (b, r_b)].map(|(vertex, radius)| {
+                        (radius > zero)
+                            .then(|| self.arc(vertex, cache, services))
+                    });
+
+                if self.is_full() {
+                    // A full revolution sweeps out a circular disk, or a ring.
+                    // The edge itself is not part of the resulting face.
+                    let mut circles = [
+                        (r_a, [angle, zero], arc_a, a),
+                        (r_b, [zero, angle], arc_b, b),
+                    ]
+                    .into_iter()
+                    .filter_map(|(radius, boundary, curve, vertex)| {
+                        Some((radius, boundary, curve?, vertex))
+                    })
+                    .collect::<Vec<_>>();
+                    circles.sort_by_key(|(radius, ..)| -*radius);
+
+                    let mut cycles = circles.into_iter().map(
+                        |(radius, boundary, curve, vertex)| {
+                            let edge =
+                                arc(radius, boundary, &curve, vertex, services);
+                            Cycle::new([edge]).insert(services)
+                        },
+                    );
+
+                    let exterior = cycles
+                        .next()
+                        .expect("Edge must not be located on the axis");
+
+                    (geometry, exterior, cycles.collect())
+                } else {
+                    let (sin, cos) = angle.sin_cos();
+                    let rotated = |radius: Scalar| [radius * cos, radius * sin];
+
+                    let mut edges = vec![line_segment(
+                        [[r_a, zero], [r_b, zero]],
+                        [t_a, t_b],
+                        edge.curve(),
+                        a,
+                        services,
+                    )];
+                    if let Some(arc_b) = arc_b {
+                        edges.push(arc(
+                            r_b,
+                            [zero, angle],
+                            &arc_b,
+                            b,
+                            services,
+                        ));
+                    }
+                    edges.push(line_segment(
+                        [rotated(r_b), rotated(r_a)],
+                        [t_b, t_a],
+                        &curve_rotated,
+                        &b_rotated,
+                        services,
+                    ));
+                    if let Some(arc_a) = arc_a {
+                        edges.push(arc(
+                            r_a,
+                            [angle, zero],
+                            &arc_a,
+                            &a_rotated,
+                            services,
+                        ));
+                    }
+
+                    (geometry, Cycle::new(edges).insert(services), Vec::new())
+                }
+            }
+        };
+
+        let region = Region::new(exterior, interiors, color).insert(services);
+        let face = Face::new(Surface::new(geometry).insert(services), region)
+            .insert(services);
+
+        Some(face)
+    }
+
+    /// Compute the cylinder that a line parallel to the axis sweeps out
+    ///
+    /// Returns the surface, and the v-coordinates that correspond to the
+    /// provided curve coordinates of the line.
+    fn cylinder(
+        &self,
+        origin: Point<3>,
+        direction: Vector<3>,
+        t: [Scalar; 2],
+    ) -> (SurfaceGeometry, [Scalar; 2]) {
+        let center = self.center(origin);
+        let radius = origin - center;
+        let circle = Circle::new(center, radius, self.direction.cross(&radius));
+
+        let geometry = SurfaceGeometry::Swept {
+            u: GlobalPath::Circle(circle),
+            v: direction,
+        };
+
+        (geometry, t)
+    }
+
+    /// Compute the cone that a line at an angle to the axis sweeps out
+    ///
+    /// Returns the surface, and the v-coordinates that correspond to the
+    /// provided curve coordinates of the line, whose ends are located at the
+    /// provided distances from the axis.
+    fn cone(
+        &self,
+        origin: Point<3>,
+        direction: Vector<3>,
+        t: [Scalar; 2],
+        radii: [Scalar; 2],
+    ) -> (SurfaceGeometry, [Scalar; 2]) {
+        // The circle of the cone runs through the end of the line that is
+        // farther from the axis. Its apex is where the line meets the axis.
+        let t_circle = if radii[0] > radii[1] { t[0] } else { t[1] };
+        let t_apex = -(origin - self.origin).dot(&self.radial)
+            / direction.dot(&self.radial);
+
+        let point = origin + direction * t_circle;
+        let center = self.center(point);
+        let radius = point - center;
+        let circle = Circle::new(center, radius, self.direction.cross(&radius));
+
+        let apex = self.center(origin + direction * t_apex);
+        let v = t.map(|t| (t - t_circle) / (t_apex - t_circle));
+
+        (SurfaceGeometry::Cone(Cone::new(circle, apex)), v)
+    }
+
+    /// Compute the torus that a circle sweeps out
+    ///
+    /// Returns the surface, and the v-coordinates that correspond to the
+    /// provided curve coordinates of the circle.
+    fn torus(
+        &self,
+        circle: &Circle<2>,
+        surface: &SurfaceGeometry,
+        t: [Scalar; 2],
+    ) -> (SurfaceGeometry, [Scalar; 2]) {
+        let center = surface.point_from_surface_coords(circle.center());
+        let [a, b] = [circle.a(), circle.b()]
+            .map(|vector| surface.vector_from_surface_coords(vector));
+
+        if (a.magnitude() - b.magnitude()).abs() > self.tolerance
+            || a.dot(&b).abs() > self.tolerance
+        {
+            todo!(
+                "Revolving circles that are distorted by their surface is not \
+                supported yet"
+            )
+        }
+
+        let torus_center = self.center(center);
+        let major = center - torus_center;
+        let minor_radius = a.magnitude();
+
+        if major.magnitude() <= self.tolerance {
+            todo!("Revolving circles around their center is not supported yet")
+        }
+        assert!(
+            minor_radius < major.magnitude(),
+            "Face to revolve must not cross the axis of revolution"
+        );
+
+        // The angle around the tube must increase with the curve coordinate
+        // of the circle, so the axis of the torus must point in the direction
+        // that the circle turns towards.
+        let axis = if a.cross(&b).dot(&self.radial.cross(&self.direction))
+            > Scalar::ZERO
+        {
+            self.direction
+        } else {
+            -self.direction
+        };
+        let phase = Scalar::atan2(a.dot(&axis), a.dot(&self.radial));
+
+        let torus = Torus::new(
+            torus_center,
+            major,
+            self.direction.cross(&major),
+            axis * minor_radius,
+        );
+
+        (SurfaceGeometry::Torus(torus), t.map(|t| t + phase))
+    }
+}
+
+/// Compute the global positions of the vertices that bound an edge
+fn positions(edge: &Edge, surface: &SurfaceGeometry) -> [Point<3>; 2] {
+    edge.boundary().inner.map(|point| {
+        let point = edge.path().point_from_path_coords(point);
+        surface.point_from_surface_coords(point)
+    })
+}
+
+fn line_segment(
+    points: [impl Into<Point<2>>; 2],
+    boundary: [impl Into<Scalar>; 2],
+    curve: &Handle<Curve>,
+    start_vertex: &Handle<Vertex>,
+    services: &mut Services,
+) -> Handle<Edge> {
+    let boundary = boundary.map(|coord| Point::from([coord.into()]));
+    let path = SurfacePath::line_from_points_with_coords(
+        boundary.zip_ext(points.map(Into::into)),
+    );
+
+    Edge::new(path, boundary, curve.clone(), start_vertex.clone())
+        .insert(services)
+}
+
+fn arc(
+    radius: Scalar,
+    boundary: [impl Into<Scalar>; 2],
+    curve: &Handle<Curve>,
+    start_vertex: &Handle<Vertex>,
+    services: &mut Services,
+) -> Handle<Edge> {
+    let path =
+        SurfacePath::circle_from_center_and_radius(Point::origin(), radius);
+    let boundary = boundary.map(|coord| Point::from([coord.into()]));
+
+    Edge::new(path, boundary, curve.clone(), start_vertex.clone())
+        .insert(services)
+}
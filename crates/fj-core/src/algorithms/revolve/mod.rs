@@ -0,0 +1,54 @@
+//! Revolving objects around an axis to create new objects
+
+mod face;
+mod sketch;
+
+use fj_math::{Line, Scalar};
+
+use crate::{algorithms::approx::Tolerance, services::Services};
+
+use super::sweep::SweepCache;
+
+pub(crate) use self::face::Revolution;
+
+/// Revolve an object around an axis to create another object
+///
+/// This is the rotational counterpart to [`Sweep`]. Where sweeping moves an
+/// object along a straight path, revolving moves it along circles around an
+/// axis.
+///
+/// [`Sweep`]: super::sweep::Sweep
+pub trait Revolve: Sized {
+    /// The object that is created by revolving the implementing object
+    type Revolved;
+
+    /// Revolve the object around the given axis
+    ///
+    /// The direction of the axis and the sign of the angle define the
+    /// direction of the revolution, according to the right-hand rule. An angle
+    /// of a full turn (2π) results in a closed body of revolution. A smaller
+    /// angle results in a partial revolution, which is closed by end caps.
+    ///
+    /// Points that are closer to the axis than the provided tolerance are
+    /// considered to lie on it.
+    fn revolve(
+        self,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Self::Revolved {
+        let mut cache = SweepCache::default();
+        self.revolve_with_cache(axis, angle, tolerance, &mut cache, services)
+    }
+
+    /// Revolve the object around the given axis, using the provided cache
+    fn revolve_with_cache(
+        self,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Revolved;
+}
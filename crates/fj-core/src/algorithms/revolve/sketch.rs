@@ -0,0 +1,268 @@
+use fj_math::{Line, Scalar};
+
+use crate::{
+    algorithms::{approx::Tolerance, sweep::SweepCache},
+    objects::{Face, Sketch, Solid, Surface},
+    operations::Insert,
+    services::Services,
+    storage::Handle,
+};
+
+use super::Revolve;
+
+impl Revolve for (Handle<Sketch>, Handle<Surface>) {
+    type Revolved = Handle<Solid>;
+
+    fn revolve_with_cache(
+        self,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Revolved {
+        let (sketch, surface) = self;
+        let angle = angle.into();
+        let tolerance = tolerance.into();
+
+        let mut shells = Vec::new();
+        for region in sketch.regions() {
+            let face =
+                Face::new(surface.clone(), region.clone()).insert(services);
+            let shell = face
+                .revolve_with_cache(axis, angle, tolerance, cache, services);
+            shells.push(shell);
+        }
+
+        Solid::new(shells).insert(services)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Line, Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance, revolve::Revolve, triangulate::Triangulate,
+        },
+        geometry::SurfaceGeometry,
+        objects::{Region, Sketch, Solid},
+        operations::{
+            fixtures::{num_faces, tolerance},
+            BuildRegion, BuildSketch, Insert, UpdateSketch,
+        },
+        services::Services,
+        storage::Handle,
+    };
+
+    #[test]
+    fn full_revolution() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let tube = revolve_rectangle([1., 2.], Scalar::TAU, &mut services);
+
+        // Inner and outer cylinder, plus two rings.
+        assert_eq!(num_faces(&tube), 4);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn partial_revolution() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let positive =
+            revolve_rectangle([1., 2.], Scalar::PI / 2., &mut services);
+        let negative =
+            revolve_rectangle([1., 2.], -Scalar::PI / 2., &mut services);
+
+        // Same as for the full revolution, plus the two end caps.
+        assert_eq!(num_faces(&positive), 6);
+        assert_eq!(num_faces(&negative), 6);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn full_revolution_of_face_on_axis() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cylinder = revolve_rectangle([0., 1.], Scalar::TAU, &mut services);
+
+        // The edge on the axis doesn't create a face, and the rings are disks.
+        assert_eq!(num_faces(&cylinder), 3);
+
+        let mesh = (&*cylinder, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn partial_revolution_of_face_on_axis() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let wedge = revolve_rectangle([0., 1.], Scalar::PI, &mut services);
+
+        // The edge on the axis is shared by the end caps.
+        assert_eq!(num_faces(&wedge), 5);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn revolution_of_circle() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let full = revolve_region(
+            Region::circle([3., 0.], 1., &mut services),
+            Scalar::TAU,
+            &mut services,
+        );
+        let partial = revolve_region(
+            Region::circle([3., 0.], 1., &mut services),
+            Scalar::PI,
+            &mut services,
+        );
+
+        // A full revolution results in a torus. A partial revolution adds the
+        // two end caps.
+        assert_eq!(num_faces(&full), 1);
+        assert_eq!(num_faces(&partial), 3);
+        for solid in [&full, &partial] {
+            assert_eq!(
+                num_faces_on(solid, |surface| matches!(
+                    surface,
+                    SurfaceGeometry::Torus(_)
+                )),
+                1
+            );
+        }
+
+        let mesh = (&*full, Tolerance::from_scalar(0.1)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn revolution_of_slanted_edge() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let triangle = [[0., 0.], [1., 0.], [0., 1.]];
+        let full = revolve_region(
+            Region::polygon(triangle, &mut services),
+            Scalar::TAU,
+            &mut services,
+        );
+        let partial = revolve_region(
+            Region::polygon(triangle, &mut services),
+            Scalar::PI,
+            &mut services,
+        );
+
+        // The slanted edge sweeps out a cone, whose apex is on the axis. The
+        // edge on the axis doesn't create a face, and the other edge sweeps out
+        // a disk. A partial revolution adds the two end caps.
+        assert_eq!(num_faces(&full), 2);
+        assert_eq!(num_faces(&partial), 4);
+        for solid in [&full, &partial] {
+            assert_eq!(
+                num_faces_on(solid, |surface| matches!(
+                    surface,
+                    SurfaceGeometry::Cone(_)
+                )),
+                1
+            );
+        }
+
+        let mesh = (&*full, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn revolution_of_slanted_edge_away_from_axis() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let solid = revolve_region(
+            Region::polygon(
+                [[1., 0.], [3., 0.], [2., 1.], [1., 1.]],
+                &mut services,
+            ),
+            Scalar::TAU,
+            &mut services,
+        );
+
+        // A cylinder, two rings, and the cone.
+        assert_eq!(num_faces(&solid), 4);
+        assert_eq!(
+            num_faces_on(&solid, |surface| matches!(
+                surface,
+                SurfaceGeometry::Cone(_)
+            )),
+            1
+        );
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    fn revolve_region(
+        region: Region,
+        angle: Scalar,
+        services: &mut Services,
+    ) -> Handle<Solid> {
+        let sketch = Sketch::empty()
+            .add_region(region.insert(services))
+            .insert(services);
+        let surface = services.objects.surfaces.xz_plane();
+
+        let axis =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_z());
+
+        (sketch, surface).revolve(axis, angle, tolerance(), services)
+    }
+
+    fn revolve_rectangle(
+        [x_min, x_max]: [f64; 2],
+        angle: Scalar,
+        services: &mut Services,
+    ) -> Handle<Solid> {
+        let sketch = Sketch::empty()
+            .add_region(
+                Region::polygon(
+                    [[x_min, 0.], [x_max, 0.], [x_max, 1.], [x_min, 1.]],
+                    services,
+                )
+                .insert(services),
+            )
+            .insert(services);
+        let surface = services.objects.surfaces.xz_plane();
+
+        let axis =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_z());
+
+        (sketch, surface).revolve(axis, angle, tolerance(), services)
+    }
+
+    fn num_faces_on(
+        solid: &Solid,
+        f: impl Fn(&SurfaceGeometry) -> bool,
+    ) -> usize {
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .filter(|face| f(&face.surface().geometry()))
+            .count()
+    }
+}
@@ -0,0 +1,684 @@
+use fj_math::{Line, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::Tolerance, bounding_volume::BoundingVolume, revolve::Revolution,
+    },
+    geometry::{SurfaceGeometry, SurfacePath},
+    objects::{Cycle, Edge, Face, Shell, Sketch, Solid, Surface},
+    operations::{Insert, Reverse},
+    services::Services,
+    storage::Handle,
+};
+
+use super::{face::sweep_cycles, SweepCache};
+
+/// Sweep an object along a [`SweepPath`] to create another object
+///
+/// Where [`Sweep`] moves an object along a single straight line, this moves it
+/// along a sequence of straight and circular segments. Each segment creates
+/// its own faces, which are joined to those of the neighboring segments.
+///
+/// The object is expected to be located at the start of the path, and the path
+/// must be tangent-continuous. Sweeping along a path with a sharp corner would
+/// distort the cross-section after that corner, so such paths are rejected.
+/// Corners should be rounded using arcs instead.
+///
+/// The provided tolerance applies to points: Points that are closer to the axis
+/// of a circular segment than that are considered to lie on it, and a joint
+/// between segments is considered tangent-continuous, if the cross-section
+/// would be displaced by less than that.
+///
+/// [`Sweep`]: super::Sweep
+pub trait SweepAlongPath: Sized {
+    /// The object that is created by sweeping the implementing object
+    type Swept;
+
+    /// Sweep the object along the given path
+    ///
+    /// Returns an error, if the path is not tangent-continuous, or if the
+    /// object can't be swept along it. See [`SweepAlongPathError`].
+    fn sweep_along_path(
+        self,
+        path: &SweepPath,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self::Swept, SweepAlongPathError> {
+        let mut cache = SweepCache::default();
+        self.sweep_along_path_with_cache(path, tolerance, &mut cache, services)
+    }
+
+    /// Sweep the object along the given path, using the provided cache
+    fn sweep_along_path_with_cache(
+        self,
+        path: &SweepPath,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Result<Self::Swept, SweepAlongPathError>;
+}
+
+impl SweepAlongPath for Handle<Face> {
+    type Swept = Handle<Shell>;
+
+    fn sweep_along_path_with_cache(
+        self,
+        path: &SweepPath,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Result<Self::Swept, SweepAlongPathError> {
+        let tolerance = tolerance.into();
+
+        // Same tolerance as for circles. See `Circle::new`.
+        let epsilon = Scalar::from(f64::EPSILON * 16.);
+        let normal =
+            |face: &Face| face.normal().ok_or(SweepAlongPathError::RoundFace);
+
+        path.check_joints(&self, tolerance)?;
+
+        // The faces of each segment are built from the face that faces in the
+        // direction of the path, at the start of the segment. If the provided
+        // face doesn't, we need to reverse it.
+        let is_forward =
+            normal(&self)?.dot(&path.segments[0].tangents()[0]) > Scalar::ZERO;
+        let front = if is_forward {
+            self.clone()
+        } else {
+            self.reverse(services).insert(services)
+        };
+
+        let mut faces = Vec::new();
+        let mut station = front.clone();
+
+        for (i, segment) in path.segments.iter().enumerate() {
+            let normal = normal(&station)?;
+            if normal.dot(&segment.tangents()[0]) <= epsilon {
+                return Err(SweepAlongPathError::ParallelToFace);
+            }
+
+            // The last segment of a closed path must end where the first one
+            // started. Seeding the cache with the objects of the front face
+            // makes sure that the last segment is joined to them, instead of
+            // creating new ones.
+            if path.is_closed && i + 1 == path.segments.len() {
+                let edges = station
+                    .region()
+                    .all_cycles()
+                    .flat_map(|cycle| cycle.edges())
+                    .zip(
+                        front
+                            .region()
+                            .all_cycles()
+                            .flat_map(|cycle| cycle.edges()),
+                    );
+
+                for (edge, front_edge) in edges {
+                    cache.vertices.insert(
+                        edge.start_vertex().id(),
+                        front_edge.start_vertex().clone(),
+                    );
+                    cache
+                        .curves
+                        .insert(edge.curve().id(), front_edge.curve().clone());
+                }
+            }
+
+            let (side_faces, end) = match *segment {
+                Segment::Line { path } => {
+                    let cycles = station
+                        .region()
+                        .all_cycles()
+                        .map(|cycle| Cycle::clone(cycle));
+                    let (side_faces, top_face) = sweep_cycles(
+                        cycles,
+                        station.surface(),
+                        station.region().color(),
+                        path,
+                        cache,
+                        services,
+                    );
+
+                    (side_faces, Some(top_face))
+                }
+                Segment::Arc { axis, angle, .. } => {
+                    check_axis(&station, normal, axis, tolerance)?;
+
+                    Revolution::new(&station, axis, angle, tolerance)
+                        .revolve_front_face(&station, cache, services)
+                }
+            };
+
+            faces.extend(side_faces);
+
+            // A full revolution ends where it started.
+            if let Some(end) = end {
+                station = end;
+            }
+        }
+
+        if !path.is_closed {
+            let back = if is_forward {
+                self.reverse(services).insert(services)
+            } else {
+                self
+            };
+
+            faces.push(back);
+            faces.push(station);
+        }
+
+        Ok(Shell::new(faces).insert(services))
+    }
+}
+
+impl SweepAlongPath for (Handle<Sketch>, Handle<Surface>) {
+    type Swept = Handle<Solid>;
+
+    fn sweep_along_path_with_cache(
+        self,
+        path: &SweepPath,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Result<Self::Swept, SweepAlongPathError> {
+        let (sketch, surface) = self;
+        let tolerance = tolerance.into();
+
+        let mut shells = Vec::new();
+        for region in sketch.regions() {
+            let face =
+                Face::new(surface.clone(), region.clone()).insert(services);
+            let shell = face.sweep_along_path_with_cache(
+                path, tolerance, cache, services,
+            )?;
+            shells.push(shell);
+        }
+
+        Ok(Solid::new(shells).insert(services))
+    }
+}
+
+/// Error sweeping an object along a [`SweepPath`]
+///
+/// Returned by [`SweepAlongPath::sweep_along_path`], as well as the
+/// constructors of [`SweepPath`].
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum SweepAlongPathError {
+    /// The path has no segments
+    #[error("Path must have at least one segment")]
+    EmptyPath,
+
+    /// The path is defined in a surface that is not planar
+    #[error("Sweeping along paths in round surfaces is not supported")]
+    RoundSurface,
+
+    /// The path has a segment that is neither straight nor circular
+    #[error("Sweeping along elliptical or NURBS paths is not supported")]
+    UnsupportedSegment,
+
+    /// The swept face is not planar
+    #[error("Sweeping faces defined in round surfaces is not supported")]
+    RoundFace,
+
+    /// The path has a sharp corner
+    ///
+    /// Contains the index of the segment that starts at the corner. If the
+    /// corner is where a closed path ends and starts again, that's `0`.
+    #[error(
+        "Path must be tangent-continuous, but has a corner at segment {0}"
+    )]
+    Corner(usize),
+
+    /// The path runs parallel to the swept face, or turns back on it
+    #[error(
+        "Path must not run parallel to the swept face, or turn back on it"
+    )]
+    ParallelToFace,
+
+    /// The axis of a circular segment doesn't lie in the plane of the face
+    #[error(
+        "Axis of circular segment must lie in the plane of the face, where the \
+        segment starts"
+    )]
+    AxisNotInFacePlane,
+
+    /// The face crosses the axis of a circular segment
+    #[error("Face must not cross the axis of a circular segment")]
+    FaceCrossesAxis,
+}
+
+/// Check that an axis lies in the plane of a face, and doesn't cross the face
+fn check_axis(
+    face: &Face,
+    normal: Vector<3>,
+    axis: Line<3>,
+    tolerance: Tolerance,
+) -> Result<(), SweepAlongPathError> {
+    let tolerance = tolerance.inner();
+    let surface = face.surface().geometry();
+
+    let direction = axis.direction().normalize();
+    let surface_origin = surface.point_from_surface_coords([0., 0.]);
+    if direction.dot(&normal).abs() > tolerance
+        || (axis.origin() - surface_origin).dot(&normal).abs() > tolerance
+    {
+        return Err(SweepAlongPathError::AxisNotInFacePlane);
+    }
+
+    let perpendicular = normal.cross(&direction);
+    let distances = face
+        .region()
+        .all_cycles()
+        .flat_map(|cycle| cycle.edges())
+        .map(|edge| {
+            let position =
+                surface.point_from_surface_coords(edge.start_position());
+            (position - axis.origin()).dot(&perpendicular)
+        })
+        .collect::<Vec<_>>();
+
+    if distances.iter().all(|&distance| distance >= -tolerance)
+        || distances.iter().all(|&distance| distance <= tolerance)
+    {
+        Ok(())
+    } else {
+        Err(SweepAlongPathError::FaceCrossesAxis)
+    }
+}
+
+/// A path that objects can be swept along
+///
+/// See [`SweepAlongPath`].
+#[derive(Clone, Debug)]
+pub struct SweepPath {
+    segments: Vec<Segment>,
+    is_closed: bool,
+}
+
+impl SweepPath {
+    /// Create an open path from a sequence of edges
+    ///
+    /// The edges must be defined in the provided surface, and each edge must
+    /// start where the previous one ends. The sweep is closed off by end caps
+    /// at the start and end of the path.
+    ///
+    /// Returns an error, if no edges are provided, if the surface is not
+    /// planar, or if any of the edges is neither straight nor circular.
+    pub fn from_edges(
+        edges: impl IntoIterator<Item = Handle<Edge>>,
+        surface: &Surface,
+    ) -> Result<Self, SweepAlongPathError> {
+        let surface = surface.geometry();
+        let segments = edges
+            .into_iter()
+            .map(|edge| Segment::from_edge(&edge, &surface))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if segments.is_empty() {
+            return Err(SweepAlongPathError::EmptyPath);
+        }
+
+        Ok(Self {
+            segments,
+            is_closed: false,
+        })
+    }
+
+    /// Create a closed path from a cycle
+    ///
+    /// The cycle must be defined in the provided surface. The sweep ends where
+    /// it started, and is joined to itself there.
+    ///
+    /// Returns an error for the same reasons as [`SweepPath::from_edges`].
+    pub fn from_cycle(
+        cycle: &Cycle,
+        surface: &Surface,
+    ) -> Result<Self, SweepAlongPathError> {
+        Ok(Self {
+            is_closed: true,
+            ..Self::from_edges(cycle.edges().iter().cloned(), surface)?
+        })
+    }
+
+    /// Check that the path is tangent-continuous, for sweeping the given face
+    ///
+    /// Where the direction of the path changes by some angle, the cross-section
+    /// of the sweep would be displaced by up to the extent of the face times
+    /// that angle.
+    fn check_joints(
+        &self,
+        face: &Face,
+        tolerance: Tolerance,
+    ) -> Result<(), SweepAlongPathError> {
+        let surface = face.surface().geometry();
+        let extent =
+            face.region()
+                .exterior()
+                .aabb()
+                .map_or(Scalar::ZERO, |aabb| {
+                    surface
+                        .vector_from_surface_coords(aabb.max - aabb.min)
+                        .magnitude()
+                });
+
+        let num_joints = if self.is_closed {
+            self.segments.len()
+        } else {
+            self.segments.len() - 1
+        };
+
+        for i in 0..num_joints {
+            let j = (i + 1) % self.segments.len();
+
+            let [_, end] = self.segments[i].tangents();
+            let [start, _] = self.segments[j].tangents();
+
+            if (end - start).magnitude() * extent > tolerance.inner() {
+                return Err(SweepAlongPathError::Corner(j));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A segment of a [`SweepPath`]
+#[derive(Clone, Copy, Debug)]
+enum Segment {
+    /// A straight segment, defined by the translation from its start to its end
+    Line { path: Vector<3> },
+
+    /// A circular segment
+    Arc {
+        /// The point where the segment starts
+        start: Point<3>,
+
+        /// The axis of the arc, in the direction of the right-hand rule
+        axis: Line<3>,
+
+        /// The angle of the arc, which may be negative
+        angle: Scalar,
+    },
+}
+
+impl Segment {
+    fn from_edge(
+        edge: &Edge,
+        surface: &SurfaceGeometry,
+    ) -> Result<Self, SweepAlongPathError> {
+        if surface.plane_normal().is_none() {
+            return Err(SweepAlongPathError::RoundSurface);
+        }
+
+        let [start, end] = edge.boundary().inner.map(|point| {
+            let point = edge.path().point_from_path_coords(point);
+            surface.point_from_surface_coords(point)
+        });
+
+        let segment = match edge.path() {
+            SurfacePath::Line(_) => Self::Line { path: end - start },
+            SurfacePath::Circle(circle) => {
+                let center = surface.point_from_surface_coords(circle.center());
+                let [a, b] = [circle.a(), circle.b()]
+                    .map(|vector| surface.vector_from_surface_coords(vector));
+
+                let [t_start, t_end] =
+                    edge.boundary().inner.map(|point| point.t);
+
+                Self::Arc {
+                    start,
+                    axis: Line::from_origin_and_direction(
+                        center,
+                        a.cross(&b).normalize(),
+                    ),
+                    angle: t_end - t_start,
+                }
+            }
+            SurfacePath::Ellipse(_) | SurfacePath::Nurbs(_) => {
+                return Err(SweepAlongPathError::UnsupportedSegment);
+            }
+        };
+
+        Ok(segment)
+    }
+
+    /// Compute the direction of the segment at its start and end, normalized
+    fn tangents(&self) -> [Vector<3>; 2] {
+        match *self {
+            Self::Line { path } => [path.normalize(); 2],
+            Self::Arc { start, axis, angle } => {
+                let direction = axis.direction().normalize();
+                let radial = {
+                    let offset = start - axis.origin();
+                    offset - direction * direction.dot(&offset)
+                };
+
+                [Scalar::ZERO, angle].map(|t| {
+                    let (sin, cos) = t.sin_cos();
+                    let radial = radial * cos + direction.cross(&radial) * sin;
+                    let tangent = direction.cross(&radial).normalize();
+
+                    if angle < Scalar::ZERO {
+                        -tangent
+                    } else {
+                        tangent
+                    }
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Ellipse, Scalar, Vector};
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance,
+            sweep::{SweepAlongPath, SweepAlongPathError, SweepPath},
+            triangulate::Triangulate,
+        },
+        geometry::SurfacePath,
+        objects::{Cycle, Edge, Region, Sketch, Solid},
+        operations::{
+            fixtures::{num_faces, tolerance},
+            BuildCycle, BuildEdge, BuildRegion, BuildSketch, Insert,
+            UpdateCycle, UpdateSketch,
+        },
+        services::Services,
+        storage::Handle,
+    };
+
+    #[test]
+    fn sweep_along_open_path() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // A straight segment, followed by a bend to the left, followed by
+        // another straight segment.
+        let edges = [
+            Edge::line_segment([[0., 0.], [2., 0.]], None, &mut services),
+            Edge::arc([2., 0.], [4., 2.], Scalar::PI / 2., &mut services),
+            Edge::line_segment([[4., 2.], [4., 4.]], None, &mut services),
+        ]
+        .map(|edge| edge.insert(&mut services));
+        let path = SweepPath::from_edges(
+            edges,
+            &services.objects.surfaces.xy_plane(),
+        )?;
+
+        let solid = sweep_square(&path, &mut services)?;
+
+        // Each segment contributes four side faces. Plus the end caps.
+        assert_eq!(num_faces(&solid), 14);
+
+        let mesh = (&*solid, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_along_closed_path() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // A rectangle with rounded corners, starting at the origin.
+        let quarter = Scalar::PI / 2.;
+        let cycle = Cycle::empty()
+            .add_edges(
+                [
+                    Edge::line_segment(
+                        [[0., 0.], [2., 0.]],
+                        None,
+                        &mut services,
+                    ),
+                    Edge::arc([2., 0.], [4., 2.], quarter, &mut services),
+                    Edge::line_segment(
+                        [[4., 2.], [4., 4.]],
+                        None,
+                        &mut services,
+                    ),
+                    Edge::arc([4., 4.], [2., 6.], quarter, &mut services),
+                    Edge::line_segment(
+                        [[2., 6.], [-2., 6.]],
+                        None,
+                        &mut services,
+                    ),
+                    Edge::arc([-2., 6.], [-4., 4.], quarter, &mut services),
+                    Edge::line_segment(
+                        [[-4., 4.], [-4., 2.]],
+                        None,
+                        &mut services,
+                    ),
+                    Edge::arc([-4., 2.], [-2., 0.], quarter, &mut services),
+                    Edge::line_segment(
+                        [[-2., 0.], [0., 0.]],
+                        None,
+                        &mut services,
+                    ),
+                ]
+                .map(|edge| edge.insert(&mut services)),
+            )
+            .insert(&mut services);
+        let path = SweepPath::from_cycle(
+            &cycle,
+            &services.objects.surfaces.xy_plane(),
+        )?;
+
+        let solid = sweep_square(&path, &mut services)?;
+
+        // Each segment contributes four side faces. There are no end caps.
+        assert_eq!(num_faces(&solid), 36);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_along_path_of_consecutive_arcs() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // A bend to the left, directly followed by a bend to the right, with
+        // straight segments at either end.
+        let quarter = Scalar::PI / 2.;
+        let edges = [
+            Edge::line_segment([[0., 0.], [2., 0.]], None, &mut services),
+            Edge::arc([2., 0.], [4., 2.], quarter, &mut services),
+            Edge::arc([4., 2.], [6., 4.], -quarter, &mut services),
+            Edge::line_segment([[6., 4.], [8., 4.]], None, &mut services),
+        ]
+        .map(|edge| edge.insert(&mut services));
+        let path = SweepPath::from_edges(
+            edges,
+            &services.objects.surfaces.xy_plane(),
+        )?;
+
+        let solid = sweep_square(&path, &mut services)?;
+
+        // Each segment contributes four side faces. Plus the end caps.
+        assert_eq!(num_faces(&solid), 18);
+
+        // The end cap faces in the direction of the path, where it ends.
+        let end_cap_is_at_end = solid
+            .shells()
+            .first()
+            .faces()
+            .iter()
+            .filter_map(|face| face.normal())
+            .any(|normal| {
+                (normal - Vector::unit_x()).magnitude() < Scalar::from(1e-12)
+            });
+        assert!(end_cap_is_at_end);
+
+        let mesh = (&*solid, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_along_path_with_corner() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let edges = [
+            Edge::line_segment([[0., 0.], [2., 0.]], None, &mut services),
+            Edge::line_segment([[2., 0.], [2., 2.]], None, &mut services),
+        ]
+        .map(|edge| edge.insert(&mut services));
+        let path = SweepPath::from_edges(
+            edges,
+            &services.objects.surfaces.xy_plane(),
+        )?;
+
+        let result = sweep_square(&path, &mut services);
+        assert_eq!(result, Err(SweepAlongPathError::Corner(1)));
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_along_elliptical_path() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let edge = Edge::unjoined(
+            SurfacePath::Ellipse(Ellipse::new([0., 1.], [0., -1.], [2., 0.])),
+            [[0.], [1.]],
+            &mut services,
+        )
+        .insert(&mut services);
+
+        let result = SweepPath::from_edges(
+            [edge],
+            &services.objects.surfaces.xy_plane(),
+        );
+        assert!(matches!(
+            result,
+            Err(SweepAlongPathError::UnsupportedSegment)
+        ));
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    fn sweep_square(
+        path: &SweepPath,
+        services: &mut Services,
+    ) -> Result<Handle<Solid>, SweepAlongPathError> {
+        let sketch = Sketch::empty()
+            .add_region(
+                Region::polygon(
+                    [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]],
+                    services,
+                )
+                .insert(services),
+            )
+            .insert(services);
+        let surface = services.objects.surfaces.yz_plane();
+
+        (sketch, surface).sweep_along_path(path, tolerance(), services)
+    }
+}
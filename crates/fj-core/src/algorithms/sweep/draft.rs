@@ -0,0 +1,599 @@
+use fj_interop::mesh::Color;
+use fj_math::{Circle, Cone, Line, Plane, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{approx::Tolerance, transform::TransformObject},
+    geometry::{CurveBoundary, GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{
+        Cycle, Edge, Face, Region, Shell, Sketch, Solid, Surface, Vertex,
+    },
+    operations::{BuildCycle, Insert, JoinCycle, Reverse},
+    services::Services,
+    storage::Handle,
+};
+
+use super::{edge::side_face, Sweep, SweepCache};
+
+/// Sweep an object along a straight path, tapering it by a draft angle
+///
+/// This works like [`Sweep`], except that the side faces are inclined by the
+/// provided angle (in radians), relative to the path. A positive angle tapers
+/// them inward, making the end of the sweep smaller than its start. A negative
+/// angle tapers them outward.
+///
+/// Straight edges create planar side faces, circular edges create conical
+/// ones. The path must be perpendicular to the swept face, within the provided
+/// tolerance, and sharp corners are only supported between straight edges.
+pub trait SweepWithDraft: Sized {
+    /// The object that is created by sweeping the implementing object
+    type Swept;
+
+    /// Sweep the object along the given path, with the given draft angle
+    fn sweep_with_draft(
+        self,
+        path: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Self::Swept {
+        let mut cache = SweepCache::default();
+        self.sweep_with_draft_with_cache(
+            path, angle, tolerance, &mut cache, services,
+        )
+    }
+
+    /// Sweep the object with a draft angle, using the provided cache
+    fn sweep_with_draft_with_cache(
+        self,
+        path: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Swept;
+}
+
+impl SweepWithDraft for Handle<Face> {
+    type Swept = Handle<Shell>;
+
+    fn sweep_with_draft_with_cache(
+        self,
+        path: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Swept {
+        let path = path.into();
+        let angle = angle.into();
+        let tolerance = tolerance.into();
+
+        if angle == Scalar::ZERO {
+            return self.sweep_with_cache(path, cache, services);
+        }
+
+        let SurfaceGeometry::Swept {
+            u: GlobalPath::Line(line),
+            v,
+        } = self.surface().geometry()
+        else {
+            todo!("Sweeping faces with draft is only supported on planes")
+        };
+        let plane = Plane::from_parametric(line.origin(), line.direction(), v);
+        let normal = plane.normal().normalize();
+
+        let height = path.dot(&normal);
+        assert!(
+            (path - normal * height).magnitude() < tolerance.inner(),
+            "Path must be perpendicular to the face, to sweep it with draft"
+        );
+
+        let is_negative_sweep = height < Scalar::ZERO;
+
+        let bottom_face = if is_negative_sweep {
+            self.clone()
+        } else {
+            self.clone().reverse(services).insert(services)
+        };
+
+        // The cycles of a face that faces in the direction of the sweep have
+        // the face on their left, as seen from the front. Whether that's the
+        // left in surface coordinates too, depends on the direction of the
+        // sweep.
+        let (sin, cos) = angle.sin_cos();
+        let distance = height.abs() * sin / cos;
+        let draft = Draft {
+            plane,
+            distance: if is_negative_sweep {
+                -distance
+            } else {
+                distance
+            },
+            tolerance: tolerance.inner(),
+        };
+
+        let mut faces = vec![bottom_face.clone()];
+
+        let top_surface = self.surface().clone().translate(path, services);
+        let color = self.region().color();
+
+        let mut exterior = None;
+        let mut interiors = Vec::new();
+
+        for (i, cycle) in bottom_face.region().all_cycles().enumerate() {
+            let cycle = cycle.reverse(services);
+
+            let mut top_edges = Vec::new();
+            let offset = draft.offset(&cycle);
+            for ((edge, next), top) in cycle.edges().pairs().zip(offset) {
+                let (face, top_edge) = draft.sweep_edge(
+                    edge,
+                    next.start_vertex(),
+                    &top,
+                    self.surface(),
+                    color,
+                    path,
+                    cache,
+                    services,
+                );
+                faces.push(face);
+
+                top_edges.push((
+                    top_edge,
+                    top.path,
+                    CurveBoundary::from(top.boundary),
+                ));
+            }
+
+            let top_cycle = Cycle::empty()
+                .add_joined_edges(top_edges, services)
+                .insert(services);
+
+            if i == 0 {
+                exterior = Some(top_cycle);
+            } else {
+                interiors.push(top_cycle);
+            }
+        }
+
+        let region =
+            Region::new(exterior.unwrap(), interiors, color).insert(services);
+        faces.push(Face::new(top_surface, region).insert(services));
+
+        Shell::new(faces).insert(services)
+    }
+}
+
+impl SweepWithDraft for (Handle<Sketch>, Handle<Surface>) {
+    type Swept = Handle<Solid>;
+
+    fn sweep_with_draft_with_cache(
+        self,
+        path: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Swept {
+        let (sketch, surface) = self;
+        let path = path.into();
+        let angle = angle.into();
+        let tolerance = tolerance.into();
+
+        let mut shells = Vec::new();
+        for region in sketch.regions() {
+            let face =
+                Face::new(surface.clone(), region.clone()).insert(services);
+            let shell = face.sweep_with_draft_with_cache(
+                path, angle, tolerance, cache, services,
+            );
+            shells.push(shell);
+        }
+
+        Solid::new(shells).insert(services)
+    }
+}
+
+/// The offset of a face's edges, as caused by the draft
+struct Draft {
+    /// The plane that the face is defined in
+    plane: Plane,
+
+    /// The distance by which the edges are offset to their left
+    distance: Scalar,
+
+    /// Offset vectors that deviate by less than this are considered identical
+    tolerance: Scalar,
+}
+
+impl Draft {
+    /// Compute the offset of an edge's start, in surface coordinates
+    ///
+    /// The offset is perpendicular to the provided tangent, which points in
+    /// the direction that the edge is traversed in.
+    fn offset_vector(&self, tangent: Vector<2>) -> Vector<2> {
+        let tangent = self.plane.u() * tangent.u + self.plane.v() * tangent.v;
+        let left = self.plane.normal().cross(&tangent).normalize();
+
+        self.plane.project_vector(left * self.distance)
+    }
+
+    /// Compute the offset edges at the top of a cycle
+    fn offset(&self, cycle: &Cycle) -> Vec<TopEdge> {
+        let paths = cycle
+            .edges()
+            .iter()
+            .map(|edge| self.offset_path(edge))
+            .collect::<Vec<_>>();
+
+        // The corner at the start of each edge. Edges that meet tangentially
+        // are both offset by the same vector there. Otherwise, the corner is
+        // where the offset edges intersect.
+        let corners = cycle
+            .edges()
+            .pairs()
+            .zip(paths.iter().zip(paths.iter().cycle().skip(1)))
+            .map(|((edge, next), (path, next_path))| {
+                let [_, end] = tangents(edge);
+                let [start, _] = tangents(next);
+
+                // The offset vectors of both edges deviate by the sine of the
+                // angle between them, times the offset distance.
+                let is_tangent = end.cross2d(&start).abs()
+                    * self.distance.abs()
+                    < self.tolerance * end.magnitude() * start.magnitude()
+                    && end.dot(&start) > Scalar::ZERO;
+
+                if is_tangent {
+                    return next.start_position() + self.offset_vector(start);
+                }
+
+                match (path, next_path) {
+                    (SurfacePath::Line(a), SurfacePath::Line(b)) => {
+                        intersect_lines(a, b)
+                    }
+                    _ => todo!(
+                        "Sweeping with draft is not supported for sharp \
+                        corners that involve curved edges"
+                    ),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let num_edges = paths.len();
+        cycle
+            .edges()
+            .iter()
+            .zip(paths)
+            .enumerate()
+            .map(|(i, (edge, path))| {
+                let boundary = match &path {
+                    SurfacePath::Line(line) => {
+                        let start = corners[(i + num_edges - 1) % num_edges];
+                        let end = corners[i];
+
+                        [start, end]
+                            .map(|corner| line.point_to_line_coords(corner))
+                    }
+                    _ => edge.boundary().inner,
+                };
+
+                TopEdge { path, boundary }
+            })
+            .collect()
+    }
+
+    /// Compute the path of an edge's offset at the top of the sweep
+    fn offset_path(&self, edge: &Edge) -> SurfacePath {
+        match edge.path() {
+            SurfacePath::Line(line) => {
+                let [start, _] = tangents(edge);
+
+                SurfacePath::Line(Line::from_origin_and_direction(
+                    line.origin() + self.offset_vector(start),
+                    line.direction(),
+                ))
+            }
+            SurfacePath::Circle(circle) => {
+                SurfacePath::Circle(self.offset_circle(&circle, edge))
+            }
+            SurfacePath::Ellipse(_) | SurfacePath::Nurbs(_) => {
+                todo!(
+                    "Sweeping with draft is not supported for elliptical or \
+                    NURBS edges"
+                )
+            }
+        }
+    }
+
+    /// Compute the offset of a circle that an edge is defined on
+    ///
+    /// The circle keeps its center and its coordinate system. Only its radius
+    /// changes.
+    fn offset_circle(&self, circle: &Circle<2>, edge: &Edge) -> Circle<2> {
+        let [start, _] = tangents(edge);
+
+        let center_to_start = edge.start_position() - circle.center();
+        let to_start = self.plane.u() * center_to_start.u
+            + self.plane.v() * center_to_start.v;
+        let offset = self.offset_vector(start);
+        let offset = self.plane.u() * offset.u + self.plane.v() * offset.v;
+
+        // The offset is either toward the center, or away from it.
+        let radius = to_start.magnitude();
+        let radius_offset = radius + offset.dot(&to_start.normalize());
+        assert!(
+            radius_offset > Scalar::ZERO,
+            "Draft must not shrink a circular edge to nothing"
+        );
+
+        let factor = radius_offset / radius;
+        Circle::new(circle.center(), circle.a() * factor, circle.b() * factor)
+    }
+
+    /// Sweep an edge, creating a side face and the edge at its top
+    #[allow(clippy::too_many_arguments)]
+    fn sweep_edge(
+        &self,
+        edge: &Edge,
+        next_vertex: &Handle<Vertex>,
+        top: &TopEdge,
+        surface: &Surface,
+        color: Option<Color>,
+        path: Vector<3>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> (Handle<Face>, Handle<Edge>) {
+        let geometry = surface.geometry();
+
+        // The v-coordinate of the top edge in the side surface
+        let (surface, v_top) = match (edge.path(), &top.path) {
+            (SurfacePath::Line(_), SurfacePath::Line(_)) => {
+                let [start, _] = tangents(edge);
+                let offset = geometry
+                    .vector_from_surface_coords(self.offset_vector(start));
+
+                let surface = (edge.path(), surface).sweep_with_cache(
+                    path + offset,
+                    cache,
+                    services,
+                );
+
+                (surface, Scalar::ONE)
+            }
+            (SurfacePath::Circle(circle), SurfacePath::Circle(top_circle)) => {
+                let factor = top_circle.radius() / circle.radius();
+
+                let center =
+                    geometry.point_from_surface_coords(circle.center());
+                let [a, b] = [circle.a(), circle.b()]
+                    .map(|vector| geometry.vector_from_surface_coords(vector));
+                let circle = Circle::new(center, a, b);
+
+                // The side of the cone runs through the bottom and top
+                // circles, and reaches the axis at the apex.
+                let apex = center + path / (Scalar::ONE - factor);
+                let cone = Cone::new(circle, apex);
+
+                let surface =
+                    Surface::new(SurfaceGeometry::Cone(cone)).insert(services);
+
+                (surface, Scalar::ONE - factor)
+            }
+            _ => unreachable!("Offset edge must be of the same kind as edge"),
+        };
+
+        let surface_points = {
+            let [a, b] = edge.boundary().inner;
+            let [a_top, b_top] = top.boundary;
+
+            [
+                [a.t, Scalar::ZERO],
+                [b.t, Scalar::ZERO],
+                [b_top.t, v_top],
+                [a_top.t, v_top],
+            ]
+            .map(Point::from)
+        };
+
+        side_face(
+            edge,
+            next_vertex,
+            surface,
+            top.boundary,
+            surface_points,
+            color,
+            path,
+            cache,
+            services,
+        )
+    }
+}
+
+/// An edge at the top of a sweep with draft, in surface coordinates
+struct TopEdge {
+    path: SurfacePath,
+    boundary: [Point<1>; 2],
+}
+
+/// Compute the direction in which an edge is traversed, at its start and end
+fn tangents(edge: &Edge) -> [Vector<2>; 2] {
+    let [start, end] = edge.boundary().inner;
+    let direction = if start <= end {
+        Scalar::ONE
+    } else {
+        -Scalar::ONE
+    };
+
+    [start, end].map(|point| {
+        let tangent = match edge.path() {
+            SurfacePath::Line(line) => line.direction(),
+            SurfacePath::Circle(circle) => {
+                // The tangent of a circle is a quarter turn ahead of the
+                // vector from its center.
+                circle.vector_from_circle_coords([point.t + Scalar::PI / 2.])
+            }
+            SurfacePath::Ellipse(_) | SurfacePath::Nurbs(_) => {
+                todo!(
+                    "Sweeping with draft is not supported for elliptical or \
+                    NURBS edges"
+                )
+            }
+        };
+
+        tangent * direction
+    })
+}
+
+/// Compute the point where two lines intersect
+///
+/// The lines are expected not to be parallel.
+fn intersect_lines(a: &Line<2>, b: &Line<2>) -> Point<2> {
+    let t = (b.origin() - a.origin()).cross2d(&b.direction())
+        / a.direction().cross2d(&b.direction());
+    a.point_from_line_coords([t])
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance, sweep::SweepWithDraft, triangulate::Triangulate,
+        },
+        geometry::{SurfaceGeometry, SurfacePath},
+        objects::{Cycle, Face, Region, Sketch, Solid},
+        operations::{
+            fixtures::tolerance, BuildCycle, BuildRegion, BuildSketch, Insert,
+            Reverse, UpdateRegion, UpdateSketch,
+        },
+        services::Services,
+        storage::Handle,
+    };
+
+    #[test]
+    fn sweep_square_with_draft() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let region = Region::polygon(
+            [[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]],
+            &mut services,
+        )
+        .insert(&mut services);
+        let solid = sweep(region, 0.1, &mut services);
+
+        let faces = faces(&solid);
+        assert_eq!(faces.len(), 6);
+
+        // The side faces lean inward, making the top face smaller.
+        let top = faces.last().unwrap();
+        for edge in top.region().exterior().edges() {
+            let point = top
+                .surface()
+                .geometry()
+                .point_from_surface_coords(edge.start_position());
+
+            for coord in [point.x, point.y] {
+                assert!((coord.abs() - 0.9).abs() < Scalar::from(1e-12));
+            }
+            assert_eq!(point.z, Scalar::ONE);
+        }
+
+        let mesh = (&*solid, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_cylinder_with_draft() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let region =
+            Region::circle([0., 0.], 1., &mut services).insert(&mut services);
+        let solid = sweep(region, 0.1, &mut services);
+
+        let faces = faces(&solid);
+        assert_eq!(faces.len(), 3);
+        assert!(matches!(
+            faces[1].surface().geometry(),
+            SurfaceGeometry::Cone(_)
+        ));
+        assert_eq!(radius(faces.last().unwrap()), Scalar::from(0.9));
+
+        let mesh = (&*solid, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_hole_with_draft() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let region = Region::polygon(
+            [[-2., -2.], [2., -2.], [2., 2.], [-2., 2.]],
+            &mut services,
+        )
+        .add_interiors([Cycle::circle([0., 0.], 1., &mut services)
+            .reverse(&mut services)
+            .insert(&mut services)])
+        .insert(&mut services);
+        let solid = sweep(region, 0.1, &mut services);
+
+        let faces = faces(&solid);
+        assert_eq!(faces.len(), 7);
+
+        // Where the outside of the solid gets smaller, the hole gets bigger.
+        let top = faces.last().unwrap();
+        let hole = top.region().interiors().first();
+        let point = top
+            .surface()
+            .geometry()
+            .point_from_surface_coords(hole.edges().first().start_position());
+        assert!(
+            ((point - Point::from([0., 0., 1.])).magnitude() - 1.1).abs()
+                < Scalar::from(1e-12)
+        );
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    fn sweep(
+        region: Handle<Region>,
+        tan_angle: f64,
+        services: &mut Services,
+    ) -> Handle<Solid> {
+        let sketch = Sketch::empty().add_region(region).insert(services);
+        let surface = services.objects.surfaces.xy_plane();
+
+        (sketch, surface).sweep_with_draft(
+            [0., 0., 1.],
+            tan_angle.atan(),
+            tolerance(),
+            services,
+        )
+    }
+
+    fn faces(solid: &Solid) -> Vec<Face> {
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| {
+                shell.faces().iter().map(|face| Face::clone(face))
+            })
+            .collect()
+    }
+
+    fn radius(face: &Face) -> Scalar {
+        let edge = face.region().exterior().edges().first().clone();
+        match edge.path() {
+            SurfacePath::Circle(circle) => circle.radius(),
+            _ => unreachable!("Expected circle"),
+        }
+    }
+}
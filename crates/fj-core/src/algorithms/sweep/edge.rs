@@ -2,13 +2,13 @@ use fj_interop::{ext::ArrayExt, mesh::Color};
 use fj_math::{Point, Scalar, Vector};
 
 use crate::{
-    objects::{Cycle, Edge, Face, Region, Surface, Vertex},
+    objects::{Curve, Cycle, Edge, Face, Region, Surface, Vertex},
     operations::{BuildEdge, Insert, UpdateCycle, UpdateEdge},
     services::Services,
     storage::Handle,
 };
 
-use super::{Sweep, SweepCache};
+use super::{path::u_from_path_coords, Sweep, SweepCache};
 
 impl Sweep for (&Edge, &Handle<Vertex>, &Surface, Option<Color>) {
     type Swept = (Handle<Face>, Handle<Edge>);
@@ -22,95 +22,131 @@ impl Sweep for (&Edge, &Handle<Vertex>, &Surface, Option<Color>) {
         let (edge, next_vertex, surface, color) = self;
         let path = path.into();
 
-        let surface =
+        let swept_surface =
             (edge.path(), surface).sweep_with_cache(path, cache, services);
 
-        // Next, we need to define the boundaries of the face. Let's start with
-        // the global vertices and edges.
-        let (vertices, curves) = {
-            let [a, b] = [edge.start_vertex(), next_vertex].map(Clone::clone);
-            let (curve_up, [_, c]) =
-                b.clone().sweep_with_cache(path, cache, services);
-            let (curve_down, [_, d]) =
-                a.clone().sweep_with_cache(path, cache, services);
-
-            (
-                [a, b, c, d],
-                [
-                    Some(edge.curve().clone()),
-                    Some(curve_up),
-                    None,
-                    Some(curve_down),
-                ],
-            )
-        };
-
-        // Let's figure out the surface coordinates of the edge vertices.
+        // The u-coordinates of the swept surface don't necessarily match the
+        // curve coordinates of the edge.
         let surface_points = {
-            let [a, b] = edge.boundary().inner;
+            let [a, b] = edge.boundary().inner.map(|point| {
+                u_from_path_coords(&edge.path(), &surface.geometry(), point)
+            });
 
             [
-                [a.t, Scalar::ZERO],
-                [b.t, Scalar::ZERO],
-                [b.t, Scalar::ONE],
-                [a.t, Scalar::ONE],
+                [a, Scalar::ZERO],
+                [b, Scalar::ZERO],
+                [b, Scalar::ONE],
+                [a, Scalar::ONE],
             ]
             .map(Point::from)
         };
-        let surface_points_next = {
-            let mut points = surface_points;
-            points.rotate_left(1);
-            points
-        };
-
-        // Now, the boundaries of each edge.
-        let boundaries = {
-            let [a, b] = edge.boundary().inner;
-            let [c, d] = [0., 1.].map(|coord| Point::from([coord]));
-
-            [[a, b], [c, d], [b, a], [d, c]]
-        };
 
-        let mut exterior = Some(Cycle::new([]));
-
-        // Armed with all of that, we're ready to create the edges.
-        let [_edge_bottom, _edge_up, edge_top, _edge_down] = boundaries
-            .zip_ext(surface_points)
-            .zip_ext(surface_points_next)
-            .zip_ext(vertices)
-            .zip_ext(curves)
-            .map(|((((boundary, start), end), start_vertex), curve)| {
-                let edge = {
-                    let edge = Edge::line_segment(
-                        [start, end],
-                        Some(boundary),
-                        services,
-                    )
-                    .update_start_vertex(|_| start_vertex);
-
-                    let edge = if let Some(curve) = curve {
-                        edge.update_curve(|_| curve)
-                    } else {
-                        edge
-                    };
-
-                    edge.insert(services)
-                };
-
-                exterior =
-                    Some(exterior.take().unwrap().add_edges([edge.clone()]));
-
-                edge
-            });
-
-        let region = Region::new(exterior.unwrap().insert(services), [], color)
-            .insert(services);
-
-        let face = Face::new(surface, region);
-
-        // And we're done creating the face! All that's left to do is build our
-        // return values.
-        let face = face.insert(services);
-        (face, edge_top)
+        side_face(
+            edge,
+            next_vertex,
+            swept_surface,
+            edge.boundary().inner,
+            surface_points,
+            color,
+            path,
+            cache,
+            services,
+        )
     }
 }
+
+/// Build the face that sweeping an edge creates on the provided surface
+///
+/// `top` defines the curve coordinates of the edge at the top of the face,
+/// where the sweeps of the edge's vertices end. `surface_points` are the
+/// corners of the face in surface coordinates: The start and end of the edge,
+/// followed by the end and start of the edge at the top. The edge at the top of
+/// the face is returned along with the face.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn side_face(
+    edge: &Edge,
+    next_vertex: &Handle<Vertex>,
+    surface: Handle<Surface>,
+    top: [Point<1>; 2],
+    surface_points: [Point<2>; 4],
+    color: Option<Color>,
+    path: Vector<3>,
+    cache: &mut SweepCache,
+    services: &mut Services,
+) -> (Handle<Face>, Handle<Edge>) {
+    // First, we need to define the boundaries of the face. Let's start with
+    // the global vertices and edges.
+    let (vertices, curves) = {
+        let [a, b] = [edge.start_vertex(), next_vertex].map(Clone::clone);
+        let (curve_up, [_, c]) =
+            b.clone().sweep_with_cache(path, cache, services);
+        let (curve_down, [_, d]) =
+            a.clone().sweep_with_cache(path, cache, services);
+
+        // The curve at the top of the face is the one that the curve of
+        // the edge ends up as after the sweep. Looking it up in the cache
+        // makes sure that it can be shared, where the curve is. The cache
+        // also contains the curves that vertices are swept along, but
+        // those are keyed by the IDs of the vertices. Object IDs are
+        // unique, so they can't get mixed up.
+        let curve_top = cache
+            .curves
+            .entry(edge.curve().id())
+            .or_insert_with(|| Curve::new().insert(services))
+            .clone();
+
+        (
+            [a, b, c, d],
+            [edge.curve().clone(), curve_up, curve_top, curve_down],
+        )
+    };
+
+    // Each edge ends where the next one starts.
+    let surface_points_next = {
+        let mut points = surface_points;
+        points.rotate_left(1);
+        points
+    };
+
+    // Now, the boundaries of each edge.
+    let boundaries = {
+        let [a, b] = edge.boundary().inner;
+        let [a_top, b_top] = top;
+        let [c, d] = [0., 1.].map(|coord| Point::from([coord]));
+
+        [[a, b], [c, d], [b_top, a_top], [d, c]]
+    };
+
+    let mut exterior = Some(Cycle::new([]));
+
+    // Armed with all of that, we're ready to create the edges.
+    let [_edge_bottom, _edge_up, edge_top, _edge_down] = boundaries
+        .zip_ext(surface_points)
+        .zip_ext(surface_points_next)
+        .zip_ext(vertices)
+        .zip_ext(curves)
+        .map(|((((boundary, start), end), start_vertex), curve)| {
+            let edge = {
+                let edge =
+                    Edge::line_segment([start, end], Some(boundary), services)
+                        .update_start_vertex(|_| start_vertex)
+                        .update_curve(|_| curve);
+
+                edge.insert(services)
+            };
+
+            exterior = Some(exterior.take().unwrap().add_edges([edge.clone()]));
+
+            edge
+        });
+
+    let region = Region::new(exterior.unwrap().insert(services), [], color)
+        .insert(services);
+
+    let face = Face::new(surface, region);
+
+    // And we're done creating the face! All that's left to do is build our
+    // return values.
+    let face = face.insert(services);
+    (face, edge_top)
+}
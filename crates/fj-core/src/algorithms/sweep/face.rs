@@ -1,12 +1,13 @@
-use std::ops::Deref;
+use std::{collections::BTreeMap, f64::consts::FRAC_PI_2, ops::Deref};
 
-use fj_math::{Scalar, Vector};
+use fj_interop::mesh::Color;
+use fj_math::{Point, Scalar, Vector};
 
 use crate::{
-    algorithms::transform::TransformObject,
-    geometry::GlobalPath,
-    objects::{Cycle, Face, Region, Shell},
-    operations::{BuildCycle, Insert, JoinCycle, Reverse},
+    algorithms::{bounding_volume::BoundingVolume, transform::TransformObject},
+    geometry::{GlobalPath, SurfaceGeometry},
+    objects::{Curve, Cycle, Edge, Face, Region, Shell, Surface, Vertex},
+    operations::{BuildEdge, Insert, Reverse, UpdateEdge},
     services::Services,
     storage::Handle,
 };
@@ -27,14 +28,35 @@ impl Sweep for Handle<Face> {
         let mut faces = Vec::new();
 
         let is_negative_sweep = {
-            let u = match self.surface().geometry().u {
-                GlobalPath::Circle(_) => todo!(
-                    "Sweeping from faces defined in round surfaces is not \
-                    supported"
+            // On a curved surface, the normal differs from point to point. We
+            // use the one in the middle of the face, which is as good as any.
+            let point = self
+                .region()
+                .exterior()
+                .aabb()
+                .map(|aabb| aabb.min + (aabb.max - aabb.min) / 2.)
+                .unwrap_or_else(Point::origin);
+
+            let (u, v) = match self.surface().geometry() {
+                SurfaceGeometry::Swept {
+                    u: GlobalPath::Line(line),
+                    v,
+                } => (line.direction(), v),
+                SurfaceGeometry::Swept {
+                    u: path @ (GlobalPath::Circle(_) | GlobalPath::Ellipse(_)),
+                    v,
+                } => {
+                    // The tangent of a circle or ellipse is a quarter turn
+                    // ahead of the vector from its center.
+                    let tangent =
+                        path.vector_from_path_coords([point.u + FRAC_PI_2]);
+                    (tangent, v)
+                }
+                _ => todo!(
+                    "Sweeping from faces defined in this kind of surface is \
+                    not supported"
                 ),
-                GlobalPath::Line(line) => line.direction(),
             };
-            let v = self.surface().geometry().v;
 
             let normal = u.cross(&v);
 
@@ -50,51 +72,293 @@ impl Sweep for Handle<Face> {
         };
         faces.push(bottom_face.clone());
 
-        let top_surface =
-            bottom_face.surface().clone().translate(path, services);
+        let cycles = bottom_face
+            .region()
+            .all_cycles()
+            .map(|cycle| cycle.reverse(services))
+            .collect::<Vec<_>>();
 
-        let mut exterior = None;
-        let mut interiors = Vec::new();
+        let (side_faces, top_face) = sweep_cycles(
+            cycles,
+            self.surface(),
+            self.region().color(),
+            path,
+            cache,
+            services,
+        );
+        faces.extend(side_faces);
+        faces.push(top_face);
 
-        for (i, cycle) in bottom_face.region().all_cycles().cloned().enumerate()
-        {
-            let cycle = cycle.reverse(services);
+        Shell::new(faces).insert(services)
+    }
+}
 
-            let mut top_edges = Vec::new();
-            for (edge, next) in cycle.edges().pairs() {
-                let (face, top_edge) = (
-                    edge.deref(),
-                    next.start_vertex(),
-                    self.surface().deref(),
-                    self.region().color(),
-                )
-                    .sweep_with_cache(path, cache, services);
+/// Sweep the cycles of a face, creating the side faces and the top face
+///
+/// The cycles are expected to be oriented like those of a face that faces in
+/// the direction of the sweep. The returned top face faces in that direction
+/// too.
+pub(super) fn sweep_cycles(
+    cycles: impl IntoIterator<Item = Cycle>,
+    surface: &Handle<Surface>,
+    color: Option<Color>,
+    path: Vector<3>,
+    cache: &mut SweepCache,
+    services: &mut Services,
+) -> (Vec<Handle<Face>>, Handle<Face>) {
+    let mut faces = Vec::new();
 
-                faces.push(face);
+    let top_surface = surface.clone().translate(path, services);
 
-                top_edges.push((top_edge, edge.path(), edge.boundary()));
-            }
+    let mut exterior = None;
+    let mut interiors = Vec::new();
 
-            let top_cycle = Cycle::empty()
-                .add_joined_edges(top_edges, services)
-                .insert(services);
+    for (i, cycle) in cycles.into_iter().enumerate() {
+        // A face that wraps around a closed surface, like the side of a
+        // cylinder, has a seam. The seam consists of two edges, which share a
+        // curve. Sweeping them would result in two coincident faces that face
+        // in opposite directions. Those would cancel each other out, so we
+        // don't create them. The seam still needs its edges at the top though.
+        let mut num_edges_by_curve = BTreeMap::new();
+        for edge in cycle.edges() {
+            *num_edges_by_curve.entry(edge.curve().id()).or_insert(0) += 1;
+        }
+
+        let mut top_edges = Vec::new();
+        for (edge, next) in cycle.edges().pairs() {
+            let is_seam = num_edges_by_curve[&edge.curve().id()] > 1;
 
-            if i == 0 {
-                exterior = Some(top_cycle);
+            let top_curve = if is_seam {
+                cache
+                    .curves
+                    .entry(edge.curve().id())
+                    .or_insert_with(|| Curve::new().insert(services))
+                    .clone()
             } else {
-                interiors.push(top_cycle);
+                let (face, top_edge) =
+                    (edge.deref(), next.start_vertex(), surface.deref(), color)
+                        .sweep_with_cache(path, cache, services);
+                faces.push(face);
+
+                top_edge.curve().clone()
             };
+
+            let top_vertex = cache
+                .vertices
+                .entry(edge.start_vertex().id())
+                .or_insert_with(|| Vertex::new().insert(services))
+                .clone();
+
+            let top_edge =
+                Edge::unjoined(edge.path(), edge.boundary(), services)
+                    .update_curve(|_| top_curve)
+                    .update_start_vertex(|_| top_vertex)
+                    .insert(services);
+            top_edges.push(top_edge);
         }
 
-        let region =
-            Region::new(exterior.unwrap(), interiors, self.region().color())
-                .insert(services);
+        let top_cycle = Cycle::new(top_edges).insert(services);
 
-        let top_face = Face::new(top_surface, region);
+        if i == 0 {
+            exterior = Some(top_cycle);
+        } else {
+            interiors.push(top_cycle);
+        };
+    }
 
-        let top_face = top_face.insert(services);
-        faces.push(top_face);
+    let region =
+        Region::new(exterior.unwrap(), interiors, color).insert(services);
 
-        Shell::new(faces).insert(services)
+    let top_face = Face::new(top_surface, region).insert(services);
+
+    (faces, top_face)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::sweep::Sweep,
+        assert_contains_err,
+        geometry::{GlobalPath, SurfaceGeometry},
+        objects::{Cycle, Face, Region, Shell, Sketch, Surface},
+        operations::{
+            BuildCycle, BuildRegion, BuildSketch, Insert, Reverse,
+            UpdateRegion, UpdateSketch,
+        },
+        services::Services,
+        storage::Handle,
+        validate::{ShellValidationError, Validate, ValidationError},
+    };
+
+    #[test]
+    fn sweep_face_on_cylinder() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let face = face_on_cylinder(
+            [[-0.5, 0.], [0.5, 0.], [0.5, 1.], [-0.5, 1.]],
+            &mut services,
+        );
+        check_sweep(face, &mut services);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_narrow_face_on_cylinder() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // The edges around the cylinder are shorter than their curve
+        // coordinates, which go from 0 to 1.
+        let face = face_on_cylinder(
+            [[0., 0.], [0.8, 0.], [0.8, 1.], [0., 1.]],
+            &mut services,
+        );
+        check_sweep(face, &mut services);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_face_with_slanted_edge_on_cylinder() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // The slanted edge winds around the cylinder, like a helix.
+        let face = face_on_cylinder(
+            [[0., 0.], [1., 0.], [1.2, 1.], [0., 1.]],
+            &mut services,
+        );
+        let shell = check_sweep(face, &mut services);
+
+        let has_helix = shell.faces().iter().any(|face| {
+            matches!(
+                face.surface().geometry(),
+                SurfaceGeometry::Swept {
+                    u: GlobalPath::Helix(_),
+                    ..
+                }
+            )
+        });
+        assert!(has_helix);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_outer_face_of_spacer() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let [outer, inner] = [1., 0.5];
+        let sketch = Sketch::empty()
+            .add_region(
+                Region::circle(Point::origin(), outer, &mut services)
+                    .add_interiors([Cycle::circle(
+                        Point::origin(),
+                        inner,
+                        &mut services,
+                    )
+                    .reverse(&mut services)
+                    .insert(&mut services)])
+                    .insert(&mut services),
+            )
+            .insert(&mut services);
+        let surface = services.objects.surfaces.xy_plane();
+        let spacer = (sketch, surface).sweep([0., 0., 1.], &mut services);
+
+        let outer_face = spacer
+            .shells()
+            .first()
+            .faces()
+            .iter()
+            .find(|face| {
+                matches!(
+                    face.surface().geometry(),
+                    SurfaceGeometry::Swept {
+                        u: GlobalPath::Circle(circle),
+                        ..
+                    } if circle.radius() == Scalar::from(outer)
+                )
+            })
+            .expect("Spacer must have an outer face")
+            .clone();
+
+        // Sweeping a face that wraps around the cylinder can't result in a
+        // valid shell: Any sweep that isn't along the axis intersects itself,
+        // and one that is along the axis sweeps the edges at the bottom and top
+        // of the face onto the face itself. Validation must reject the latter.
+        //
+        // The seam of the outer face doesn't result in any side faces though.
+        let shell = outer_face.clone().sweep([0., 0., 1.], &mut services);
+        assert_eq!(shell.faces().len(), 4);
+
+        assert_contains_err!(
+            shell,
+            ValidationError::Shell(
+                ShellValidationError::CoincidentEdgesNotIdentical(..)
+            )
+        );
+
+        services.only_validate(&*outer_face);
+
+        Ok(())
+    }
+
+    fn face_on_cylinder(
+        points: [[f64; 2]; 4],
+        services: &mut Services,
+    ) -> Handle<Face> {
+        let surface = Surface::new(SurfaceGeometry::Swept {
+            u: GlobalPath::circle_from_radius(1.),
+            v: Vector::unit_z(),
+        })
+        .insert(services);
+
+        let exterior = Cycle::polygon(points, services).insert(services);
+        let region = Region::new(exterior, [], None).insert(services);
+
+        Face::new(surface, region).insert(services)
+    }
+
+    /// Sweep the face, and check that every vertex ends up where it should
+    fn check_sweep(
+        face: Handle<Face>,
+        services: &mut Services,
+    ) -> Handle<Shell> {
+        let path = Vector::from([1., 0., 0.]);
+        let shell = face.clone().sweep(path, services);
+
+        assert_eq!(shell.faces().len(), 6);
+
+        for swept in shell.faces() {
+            let surface = swept.surface().geometry();
+
+            for edge in swept.region().exterior().edges() {
+                let point =
+                    surface.point_from_surface_coords(edge.start_position());
+
+                let is_on_bottom = is_on_face(&face, point);
+                let is_on_top = is_on_face(&face, point - path);
+                assert!(
+                    is_on_bottom || is_on_top,
+                    "Unexpected point {point:?}"
+                );
+            }
+        }
+
+        shell
+    }
+
+    fn is_on_face(face: &Face, point: Point<3>) -> bool {
+        let surface = face.surface().geometry();
+        let point_surface = surface.project_global_point(point);
+
+        (surface.point_from_surface_coords(point_surface) - point).magnitude()
+            < Scalar::from(1e-12)
+            && point_surface.v >= Scalar::ZERO
+            && point_surface.v <= Scalar::ONE
     }
 }
@@ -0,0 +1,181 @@
+use fj_math::{Helix, Scalar};
+
+use crate::{
+    algorithms::approx::Tolerance,
+    objects::{Face, Shell, Sketch, Solid, Surface},
+    operations::Insert,
+    services::Services,
+    storage::Handle,
+};
+
+use super::{
+    screw::{sweep_with_screw, Screw},
+    SweepCache,
+};
+
+/// Sweep an object along a helix, to create another object
+///
+/// The object is rotated around the axis of the helix, while being moved along
+/// that axis, as if it were attached to a point that follows the helix. The
+/// number of turns can be fractional, and negative turns sweep backward along
+/// the helix. Only the axis and pitch of the helix are relevant for the sweep;
+/// its radius doesn't matter, as the object keeps its distance from the axis.
+///
+/// The object is expected to be defined in a plane that contains the axis of
+/// the helix, and to stay on one side of that axis. Its extent along the axis
+/// must be smaller than the pitch of the helix, or consecutive turns would
+/// intersect each other.
+///
+/// The side faces that this creates are curved. They are approximated by NURBS
+/// surfaces, which deviate from the exact shape by no more than the provided
+/// tolerance. Curved edges of the swept object are approximated by straight
+/// ones beforehand, within the same tolerance.
+pub trait SweepAlongHelix: Sized {
+    /// The object that is created by sweeping the implementing object
+    type Swept;
+
+    /// Sweep the object along the given helix, for the given number of turns
+    fn sweep_along_helix(
+        self,
+        helix: &Helix,
+        turns: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Self::Swept {
+        let mut cache = SweepCache::default();
+        self.sweep_along_helix_with_cache(
+            helix, turns, tolerance, &mut cache, services,
+        )
+    }
+
+    /// Sweep the object along the given helix, using the provided cache
+    fn sweep_along_helix_with_cache(
+        self,
+        helix: &Helix,
+        turns: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Swept;
+}
+
+impl SweepAlongHelix for Handle<Face> {
+    type Swept = Handle<Shell>;
+
+    fn sweep_along_helix_with_cache(
+        self,
+        helix: &Helix,
+        turns: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Swept {
+        let turns = turns.into();
+        assert_ne!(turns, Scalar::ZERO, "Can't sweep along zero turns");
+
+        let circle = helix.circle();
+        let screw = Screw {
+            origin: circle.center(),
+            axis: circle.a().cross(&circle.b()).normalize(),
+            angle: Scalar::TAU * turns,
+            translation: helix.pitch() * turns,
+        };
+
+        sweep_with_screw(self, &screw, tolerance.into(), cache, services)
+    }
+}
+
+impl SweepAlongHelix for (Handle<Sketch>, Handle<Surface>) {
+    type Swept = Handle<Solid>;
+
+    fn sweep_along_helix_with_cache(
+        self,
+        helix: &Helix,
+        turns: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Swept {
+        let (sketch, surface) = self;
+        let turns = turns.into();
+        let tolerance = tolerance.into();
+
+        let mut shells = Vec::new();
+        for region in sketch.regions() {
+            let face =
+                Face::new(surface.clone(), region.clone()).insert(services);
+            let shell = face.sweep_along_helix_with_cache(
+                helix, turns, tolerance, cache, services,
+            );
+            shells.push(shell);
+        }
+
+        Solid::new(shells).insert(services)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Helix, Scalar};
+
+    use crate::{
+        algorithms::{approx::Tolerance, sweep::SweepAlongHelix},
+        geometry::SurfaceGeometry,
+        objects::{Region, Sketch},
+        operations::{BuildRegion, BuildSketch, Insert, UpdateSketch},
+        services::Services,
+    };
+
+    #[test]
+    fn sweep_square_along_helix() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let region = Region::polygon(
+            [[2., 0.], [2.4, 0.], [2.4, 0.4], [2., 0.4]],
+            &mut services,
+        )
+        .insert(&mut services);
+        let sketch = Sketch::empty().add_region(region).insert(&mut services);
+        let surface = services.objects.surfaces.xz_plane();
+
+        let helix = Helix::from_radius_and_pitch(2., 1.);
+        let solid = (sketch, surface).sweep_along_helix(
+            &helix,
+            1.5,
+            tolerance,
+            &mut services,
+        );
+
+        let faces = solid
+            .shells()
+            .first()
+            .faces()
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(faces.len(), 6);
+
+        // The side faces must follow the helix through one and a half turns.
+        for face in &faces[1..5] {
+            let SurfaceGeometry::Nurbs(surface) = face.surface().geometry()
+            else {
+                panic!("Expected side face to be defined in a NURBS surface");
+            };
+
+            let [[min_u, max_u], _] = surface.domain();
+            for u in [min_u, max_u] {
+                let start = surface.point_from_nurbs_coords([u, Scalar::ZERO]);
+                let end = surface.point_from_nurbs_coords([u, Scalar::ONE]);
+
+                let expected = [-start.x, -start.y, start.z + 1.5];
+                for (a, b) in end.coords.components.into_iter().zip(expected) {
+                    assert!((a - b).abs() < tolerance.inner());
+                }
+            }
+        }
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+}
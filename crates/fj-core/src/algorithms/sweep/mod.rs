@@ -1,9 +1,14 @@
 //! Sweeping objects along a path to create new objects
 
+mod along_path;
+mod draft;
 mod edge;
 mod face;
+mod helix;
 mod path;
+mod screw;
 mod sketch;
+mod twist;
 mod vertex;
 
 use std::collections::BTreeMap;
@@ -16,6 +21,15 @@ use crate::{
     storage::{Handle, ObjectId},
 };
 
+pub use self::{
+    along_path::{SweepAlongPath, SweepAlongPathError, SweepPath},
+    draft::SweepWithDraft,
+    helix::SweepAlongHelix,
+    twist::SweepWithTwist,
+};
+
+pub(crate) use self::screw::Screw;
+
 /// Sweep an object along a path to create another object
 pub trait Sweep: Sized {
     /// The object that is created by sweeping the implementing object
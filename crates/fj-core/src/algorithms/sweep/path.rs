@@ -1,4 +1,4 @@
-use fj_math::{Circle, Line, Vector};
+use fj_math::{Circle, Ellipse, Helix, Line, Point, Scalar, Vector};
 
 use crate::{
     geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
@@ -21,57 +21,185 @@ impl Sweep for (SurfacePath, &Surface) {
     ) -> Self::Swept {
         let (curve, surface) = self;
 
-        match surface.geometry().u {
-            GlobalPath::Circle(_) => {
+        let u = match surface.geometry() {
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Line(_),
+                ..
+            } => global_path_on_flat_surface(&curve, &surface.geometry()),
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Circle(_) | GlobalPath::Ellipse(_),
+                ..
+            } => global_path_on_swept_curve(&curve, &surface.geometry()),
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Helix(_) | GlobalPath::Nurbs(_),
+                ..
+            }
+            | SurfaceGeometry::Sphere(_)
+            | SurfaceGeometry::Cone(_)
+            | SurfaceGeometry::Torus(_)
+            | SurfaceGeometry::Nurbs(_) => {
                 // Sweeping a `Curve` creates a `Surface`. The u-axis of that
-                // `Surface` is a `GlobalPath`, which we are computing below.
-                // That computation might or might not work with an arbitrary
-                // surface. Probably not, but I'm not sure.
-                //
-                // What definitely won't work, is computing the bottom edge of
-                // the sweep. The edge sweeping code currently assumes that the
-                // bottom edge is a line (which is true when sweeping from a
-                // flat surface). But is the surface we're sweeping from is
-                // curved, there's simply no way to represent the curve of the
-                // resulting bottom edge.
+                // `Surface` is a `GlobalPath`, which we would need to compute
+                // from the curve. On these surfaces, most curves end up as
+                // something that no `GlobalPath` can represent.
                 todo!(
-                    "Sweeping a curve that is defined on a curved surface is \
-                    not supported yet."
+                    "Sweeping a curve that is defined on this kind of surface \
+                    is not supported yet."
                 )
             }
-            GlobalPath::Line(_) => {
-                // We're sweeping from a curve on a flat surface, which is
-                // supported. Carry on.
-            }
+        };
+
+        Surface::new(SurfaceGeometry::Swept { u, v: path.into() })
+            .insert(services)
+    }
+}
+
+fn global_path_on_flat_surface(
+    curve: &SurfacePath,
+    surface: &SurfaceGeometry,
+) -> GlobalPath {
+    match curve {
+        SurfacePath::Circle(circle) => {
+            let center = surface.point_from_surface_coords(circle.center());
+            let a = surface.vector_from_surface_coords(circle.a());
+            let b = surface.vector_from_surface_coords(circle.b());
+
+            let circle = Circle::new(center, a, b);
+
+            GlobalPath::Circle(circle)
         }
+        SurfacePath::Ellipse(ellipse) => {
+            let center = surface.point_from_surface_coords(ellipse.center());
+            let a = surface.vector_from_surface_coords(ellipse.a());
+            let b = surface.vector_from_surface_coords(ellipse.b());
+
+            let ellipse = Ellipse::new(center, a, b);
+
+            GlobalPath::Ellipse(ellipse)
+        }
+        SurfacePath::Nurbs(curve) => {
+            // NURBS curves are invariant under affine transformations, and
+            // the surface we're sweeping from is flat.
+            let curve = curve.map_control_points(|point| {
+                surface.point_from_surface_coords(point)
+            });
+
+            GlobalPath::Nurbs(curve.into())
+        }
+        SurfacePath::Line(line) => {
+            let origin = surface.point_from_surface_coords(line.origin());
+            let direction =
+                surface.vector_from_surface_coords(line.direction());
+
+            let line = Line::from_origin_and_direction(origin, direction);
+
+            GlobalPath::Line(line)
+        }
+    }
+}
 
-        let u = match curve {
-            SurfacePath::Circle(circle) => {
-                let center = surface
-                    .geometry()
-                    .point_from_surface_coords(circle.center());
-                let a =
-                    surface.geometry().vector_from_surface_coords(circle.a());
-                let b =
-                    surface.geometry().vector_from_surface_coords(circle.b());
+/// Compute the global form of a path on a surface that is a swept curve
+///
+/// Only lines on the surface are supported. Lines that go straight along the
+/// direction the surface is swept in end up as lines, with the same
+/// coordinates. Any other line goes around the surface, and ends up as a circle
+/// or ellipse, or a helix, if it also goes along the surface. Those are
+/// parameterized like the u-axis of the surface, which means their coordinates
+/// are the u-coordinates of the points on the line. Use [`u_from_path_coords`]
+/// to convert from the coordinates of the line.
+fn global_path_on_swept_curve(
+    curve: &SurfacePath,
+    surface: &SurfaceGeometry,
+) -> GlobalPath {
+    let SurfacePath::Line(line) = curve else {
+        todo!(
+            "Sweeping a curve on a curved surface is only supported for lines"
+        )
+    };
+    let SurfaceGeometry::Swept { u, v } = surface else {
+        unreachable!("Expected surface to be a swept curve")
+    };
 
-                let circle = Circle::new(center, a, b);
+    let direction = line.direction();
 
-                GlobalPath::Circle(circle)
+    if direction.u == Scalar::ZERO {
+        // The line goes straight along the direction of the sweep.
+        let origin = surface.point_from_surface_coords(line.origin());
+        let line = Line::from_origin_and_direction(origin, *v * direction.v);
+        return GlobalPath::Line(line);
+    }
+
+    // Expressed in terms of the u-coordinate, the line moves along the surface
+    // by `direction.v / direction.u` per unit of `u`. At `u = 0`, it is at this
+    // offset from the u-axis of the surface.
+    let rise = direction.v / direction.u;
+    let offset = *v * (line.origin().v - line.origin().u * rise);
+
+    match u {
+        GlobalPath::Circle(circle) => {
+            let circle =
+                Circle::new(circle.center() + offset, circle.a(), circle.b());
+
+            if direction.v == Scalar::ZERO {
+                return GlobalPath::Circle(circle);
             }
-            SurfacePath::Line(line) => {
-                let origin =
-                    surface.geometry().point_from_surface_coords(line.origin());
-                let direction = surface
-                    .geometry()
-                    .vector_from_surface_coords(line.direction());
 
-                let line = Line::from_origin_and_direction(origin, direction);
+            // Same tolerance as for circles. See `Circle::new`.
+            let epsilon = Scalar::from(f64::EPSILON * 16.);
 
-                GlobalPath::Line(line)
+            let normal = circle.a().cross(&circle.b()).normalize();
+            if (*v - normal * v.dot(&normal)).magnitude()
+                > v.magnitude() * epsilon
+            {
+                todo!(
+                    "Sweeping a slanted line on a cylinder is only supported, \
+                    if the cylinder is swept along its axis"
+                )
+            }
+
+            GlobalPath::Helix(Helix::new(circle, *v * rise * Scalar::TAU))
+        }
+        GlobalPath::Ellipse(ellipse) => {
+            if direction.v != Scalar::ZERO {
+                todo!(
+                    "Sweeping a slanted line on an elliptic cylinder is not \
+                    supported"
+                )
             }
-        };
 
-        Surface::new(SurfaceGeometry { u, v: path.into() }).insert(services)
+            GlobalPath::Ellipse(Ellipse::new(
+                ellipse.center() + offset,
+                ellipse.a(),
+                ellipse.b(),
+            ))
+        }
+        GlobalPath::Helix(_) | GlobalPath::Line(_) | GlobalPath::Nurbs(_) => {
+            unreachable!("Expected surface to be a swept circle or ellipse")
+        }
+    }
+}
+
+/// Convert curve coordinates into the u-coordinates of the swept surface
+///
+/// Sweeping a curve creates a surface, whose u-axis is the global form of the
+/// curve. This computes the u-coordinate that a point on the curve ends up at.
+/// That is the same as the curve coordinate, except for lines that go around a
+/// swept curve. See [`global_path_on_swept_curve`].
+pub(super) fn u_from_path_coords(
+    curve: &SurfacePath,
+    surface: &SurfaceGeometry,
+    point: Point<1>,
+) -> Scalar {
+    match (curve, surface) {
+        (
+            SurfacePath::Line(line),
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Circle(_) | GlobalPath::Ellipse(_),
+                ..
+            },
+        ) if line.direction().u != Scalar::ZERO => {
+            line.point_from_line_coords(point).u
+        }
+        _ => point.t,
     }
 }
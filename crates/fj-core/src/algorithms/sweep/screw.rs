@@ -0,0 +1,350 @@
+use std::iter;
+
+use fj_math::{Line, NurbsSurface, Point, Scalar, Transform, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{Approx, Tolerance},
+        bounding_volume::BoundingVolume,
+        transform::TransformObject,
+    },
+    geometry::{SurfaceGeometry, SurfacePath},
+    objects::{Cycle, Face, Region, Shell, Surface},
+    operations::{BuildCycle, Insert, JoinCycle, Reverse},
+    services::Services,
+    storage::Handle,
+};
+
+use super::{edge::side_face, SweepCache};
+
+/// A screw motion, which rotates around an axis while moving along it
+///
+/// Points that are moved like this follow a helix. Sweep coordinates go from
+/// `0`, where the motion starts, to `1`, where it ends. Larger coordinates
+/// continue the motion beyond that.
+pub(crate) struct Screw {
+    /// A point on the axis
+    pub origin: Point<3>,
+
+    /// The direction of the axis, normalized
+    pub axis: Vector<3>,
+
+    /// The angle of the rotation, following the right-hand rule around the axis
+    pub angle: Scalar,
+
+    /// The translation along the axis
+    pub translation: Vector<3>,
+}
+
+impl Screw {
+    /// Compute the transform from the start of the motion to its end
+    pub fn transform(&self) -> Transform {
+        self.transform_at(Scalar::ONE)
+    }
+
+    /// Compute the transform from the start of the motion to a sweep coordinate
+    pub fn transform_at(&self, s: Scalar) -> Transform {
+        Transform::translation(self.origin.coords + self.translation * s)
+            * Transform::rotation(self.axis * self.angle * s)
+            * Transform::translation(-self.origin.coords)
+    }
+
+    /// Compute where a point ends up, and its direction there
+    fn point_and_tangent(
+        &self,
+        point: Point<3>,
+        s: Scalar,
+    ) -> (Point<3>, Vector<3>) {
+        let point = self.transform_at(s).transform_point(&point);
+        let tangent = self.axis.cross(&(point - self.origin)) * self.angle
+            + self.translation;
+
+        (point, tangent)
+    }
+
+    /// Compute the number of segments that approximate the helices
+    ///
+    /// The points are those whose helices need to be approximated. The helix
+    /// that is farthest from the axis is the most curved one.
+    pub fn num_segments(
+        &self,
+        points: impl IntoIterator<Item = Point<3>>,
+        tolerance: Tolerance,
+    ) -> u64 {
+        let radius = points
+            .into_iter()
+            .map(|point| {
+                let to_point = point - self.origin;
+                (to_point - self.axis * to_point.dot(&self.axis)).magnitude()
+            })
+            .max()
+            .unwrap_or(Scalar::ZERO);
+
+        // Each segment of a helix is approximated by a cubic curve that
+        // matches the position and direction of the helix at its ends. The
+        // distance between both is no larger than `h^4 / 384 * max(|H''''|)`,
+        // where `h` is the length of the segment in sweep coordinates. The
+        // fourth derivative of the helix is `angle^4 * radius`.
+        let num_segments = (self.angle.abs()
+            * (radius / (tolerance.inner() * 384.)).sqrt().sqrt())
+        .ceil()
+        .max(Scalar::ONE);
+
+        num_segments.into_u64()
+    }
+
+    /// Compute the surface that a line segment sweeps out
+    ///
+    /// The u-coordinates of the surface are the coordinates of the line, the
+    /// v-coordinates are the sweep coordinates. A line stays straight while
+    /// being swept, so the surface only needs to approximate the helices along
+    /// its v-axis.
+    fn surface(
+        &self,
+        line: &Line<3>,
+        boundary: [Point<1>; 2],
+        num_segments: u64,
+    ) -> NurbsSurface {
+        let breakpoints = (0..=num_segments)
+            .map(|i| Scalar::from_u64(i) / Scalar::from_u64(num_segments))
+            .collect::<Vec<_>>();
+
+        self.surface_with_breakpoints(line, boundary, &breakpoints)
+    }
+
+    /// Compute the surface that a line segment sweeps out between breakpoints
+    ///
+    /// Like [`Screw::surface`], but approximates the helices by one segment
+    /// between each pair of consecutive breakpoints. Those are sweep
+    /// coordinates in ascending order, and the v-coordinates of the surface
+    /// range from the first to the last of them.
+    ///
+    /// Surfaces that share a helix must use the same breakpoints along it, or
+    /// their approximations of that helix don't match.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if there are less than two breakpoints.
+    pub fn surface_with_breakpoints(
+        &self,
+        line: &Line<3>,
+        boundary: [Point<1>; 2],
+        breakpoints: &[Scalar],
+    ) -> NurbsSurface {
+        let [first, interior @ .., last] = breakpoints else {
+            panic!("Need at least two breakpoints to define a surface")
+        };
+
+        let [min, max] = {
+            let [a, b] = boundary.map(|point| point.t);
+            if a < b {
+                [a, b]
+            } else {
+                [b, a]
+            }
+        };
+
+        let control_points = [min, max].map(|t| {
+            let point = line.point_from_line_coords([t]);
+
+            let mut control_points = Vec::new();
+            for segment in breakpoints.windows(2) {
+                let [a, b] = [segment[0], segment[1]];
+                let [start, end] =
+                    [a, b].map(|s| self.point_and_tangent(point, s));
+
+                control_points.push(start.0);
+                control_points.push(start.0 + start.1 * (b - a) / 3.);
+                control_points.push(end.0 - end.1 * (b - a) / 3.);
+            }
+            control_points
+                .push(self.transform_at(*last).transform_point(&point));
+
+            control_points
+        });
+        let weights = control_points
+            .iter()
+            .map(|row| row.iter().map(|_| Scalar::ONE).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let knots_v = iter::repeat(*first)
+            .take(4)
+            .chain(interior.iter().flat_map(|&s| iter::repeat(s).take(3)))
+            .chain(iter::repeat(*last).take(4))
+            .collect();
+
+        NurbsSurface::new(
+            [1, 3],
+            control_points,
+            weights,
+            [vec![min, min, max, max], knots_v],
+        )
+    }
+}
+
+/// Sweep a face with a screw motion, creating a shell
+///
+/// Curved edges of the face are approximated by straight ones, and the side
+/// faces by NURBS surfaces, all within the tolerance. The face must not move
+/// through itself during the motion.
+pub(super) fn sweep_with_screw(
+    face: Handle<Face>,
+    screw: &Screw,
+    tolerance: Tolerance,
+    cache: &mut SweepCache,
+    services: &mut Services,
+) -> Handle<Shell> {
+    let Some(normal) = face.surface().geometry().plane_normal() else {
+        todo!("Sweeping curved faces along helical paths is not supported")
+    };
+
+    let face = polygonize(face, tolerance, services);
+    let surface = face.surface().clone();
+    let color = face.region().color();
+
+    // All points of the face move to the same side of it. Which side that is,
+    // can be determined from any point. Let's use one in the middle.
+    let is_negative_sweep = {
+        let point = face
+            .region()
+            .exterior()
+            .aabb()
+            .map(|aabb| aabb.min + (aabb.max - aabb.min) / 2.)
+            .unwrap_or_else(Point::origin);
+        let point = surface.geometry().point_from_surface_coords(point);
+
+        let (_, tangent) = screw.point_and_tangent(point, Scalar::ZERO);
+        normal.dot(&tangent) < Scalar::ZERO
+    };
+    let bottom_face = if is_negative_sweep {
+        face.clone()
+    } else {
+        face.clone().reverse(services).insert(services)
+    };
+
+    let num_segments = screw.num_segments(
+        face.region()
+            .all_cycles()
+            .flat_map(|cycle| cycle.edges())
+            .map(|edge| {
+                surface
+                    .geometry()
+                    .point_from_surface_coords(edge.start_position())
+            }),
+        tolerance,
+    );
+
+    let mut faces = vec![bottom_face.clone()];
+
+    let mut exterior = None;
+    let mut interiors = Vec::new();
+
+    for (i, cycle) in bottom_face.region().all_cycles().enumerate() {
+        let cycle = cycle.reverse(services);
+
+        let mut top_edges = Vec::new();
+        for (edge, next) in cycle.edges().pairs() {
+            let SurfacePath::Line(line) = edge.path() else {
+                unreachable!("Face has been approximated by a polygon")
+            };
+            let line = Line::from_origin_and_direction(
+                surface.geometry().point_from_surface_coords(line.origin()),
+                surface
+                    .geometry()
+                    .vector_from_surface_coords(line.direction()),
+            );
+
+            let surface_points = {
+                let [a, b] = edge.boundary().inner.map(|point| point.t);
+                [
+                    [a, Scalar::ZERO],
+                    [b, Scalar::ZERO],
+                    [b, Scalar::ONE],
+                    [a, Scalar::ONE],
+                ]
+                .map(Point::from)
+            };
+
+            let side_surface = Surface::new(SurfaceGeometry::Nurbs(
+                screw
+                    .surface(&line, edge.boundary().inner, num_segments)
+                    .into(),
+            ))
+            .insert(services);
+
+            let (side_face, top_edge) = side_face(
+                edge,
+                next.start_vertex(),
+                side_surface,
+                edge.boundary().inner,
+                surface_points,
+                color,
+                screw.translation,
+                cache,
+                services,
+            );
+            faces.push(side_face);
+
+            top_edges.push((top_edge, edge.path(), edge.boundary()));
+        }
+
+        let top_cycle = Cycle::empty()
+            .add_joined_edges(top_edges, services)
+            .insert(services);
+
+        if i == 0 {
+            exterior = Some(top_cycle);
+        } else {
+            interiors.push(top_cycle);
+        }
+    }
+
+    let top_surface = surface.transform(&screw.transform(), services);
+    let region =
+        Region::new(exterior.unwrap(), interiors, color).insert(services);
+    faces.push(Face::new(top_surface, region).insert(services));
+
+    Shell::new(faces).insert(services)
+}
+
+/// Replace the curved edges of a face with straight ones
+///
+/// Returns the face unchanged, if all of its edges are straight already.
+fn polygonize(
+    face: Handle<Face>,
+    tolerance: Tolerance,
+    services: &mut Services,
+) -> Handle<Face> {
+    let is_polygon = face
+        .region()
+        .all_cycles()
+        .flat_map(|cycle| cycle.edges())
+        .all(|edge| matches!(edge.path(), SurfacePath::Line(_)));
+    if is_polygon {
+        return face;
+    }
+
+    let mut cycles = face.region().all_cycles().map(|cycle| {
+        let points = cycle
+            .edges()
+            .iter()
+            .flat_map(|edge| {
+                let approx = (&edge.path(), edge.boundary())
+                    .approx(tolerance)
+                    .into_iter()
+                    .map(|(_, point)| point);
+
+                iter::once(edge.start_position()).chain(approx)
+            })
+            .collect::<Vec<_>>();
+
+        Cycle::polygon(points, services).insert(services)
+    });
+
+    let exterior = cycles.next().expect("Region has an exterior");
+    let interiors = cycles.collect::<Vec<_>>();
+
+    let region = Region::new(exterior, interiors, face.region().color())
+        .insert(services);
+    Face::new(face.surface().clone(), region).insert(services)
+}
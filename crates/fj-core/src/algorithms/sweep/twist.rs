@@ -0,0 +1,243 @@
+use fj_math::{Scalar, Vector};
+
+use crate::{
+    algorithms::approx::Tolerance,
+    objects::{Face, Shell, Sketch, Solid, Surface},
+    operations::Insert,
+    services::Services,
+    storage::Handle,
+};
+
+use super::{
+    screw::{sweep_with_screw, Screw},
+    Sweep, SweepCache,
+};
+
+/// Sweep an object along a straight path, twisting it around that path
+///
+/// This works like [`Sweep`], except that the object is rotated by the
+/// provided angle (in radians) over the length of the path. The rotation
+/// follows the right-hand rule around the path. Its axis is parallel to the
+/// path and passes through the origin of the surface that the object is
+/// defined in.
+///
+/// The side faces that this creates are curved. They are approximated by NURBS
+/// surfaces, which deviate from the exact shape by no more than the provided
+/// tolerance. Curved edges of the swept object are approximated by straight
+/// ones beforehand, within the same tolerance. The path must be perpendicular
+/// to the swept face.
+pub trait SweepWithTwist: Sized {
+    /// The object that is created by sweeping the implementing object
+    type Swept;
+
+    /// Sweep the object along the given path, twisting it by the given angle
+    fn sweep_with_twist(
+        self,
+        path: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Self::Swept {
+        let mut cache = SweepCache::default();
+        self.sweep_with_twist_with_cache(
+            path, angle, tolerance, &mut cache, services,
+        )
+    }
+
+    /// Sweep the object with a twist, using the provided cache
+    fn sweep_with_twist_with_cache(
+        self,
+        path: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Swept;
+}
+
+impl SweepWithTwist for Handle<Face> {
+    type Swept = Handle<Shell>;
+
+    fn sweep_with_twist_with_cache(
+        self,
+        path: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Swept {
+        let path = path.into();
+        let angle = angle.into();
+        let tolerance = tolerance.into();
+
+        if angle == Scalar::ZERO {
+            return self.sweep_with_cache(path, cache, services);
+        }
+
+        let Some(normal) = self.surface().geometry().plane_normal() else {
+            todo!("Sweeping faces with twist is only supported on planes")
+        };
+
+        let height = path.dot(&normal);
+        assert!(
+            (path - normal * height).magnitude() < tolerance.inner(),
+            "Path must be perpendicular to the face, to sweep it with twist"
+        );
+
+        let screw = Screw {
+            origin: self
+                .surface()
+                .geometry()
+                .point_from_surface_coords([0., 0.]),
+            axis: path.normalize(),
+            angle,
+            translation: path,
+        };
+
+        sweep_with_screw(self, &screw, tolerance, cache, services)
+    }
+}
+
+impl SweepWithTwist for (Handle<Sketch>, Handle<Surface>) {
+    type Swept = Handle<Solid>;
+
+    fn sweep_with_twist_with_cache(
+        self,
+        path: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut SweepCache,
+        services: &mut Services,
+    ) -> Self::Swept {
+        let (sketch, surface) = self;
+        let path = path.into();
+        let angle = angle.into();
+        let tolerance = tolerance.into();
+
+        let mut shells = Vec::new();
+        for region in sketch.regions() {
+            let face =
+                Face::new(surface.clone(), region.clone()).insert(services);
+            let shell = face.sweep_with_twist_with_cache(
+                path, angle, tolerance, cache, services,
+            );
+            shells.push(shell);
+        }
+
+        Solid::new(shells).insert(services)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Scalar, Transform, Vector};
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance, sweep::SweepWithTwist, triangulate::Triangulate,
+        },
+        geometry::SurfaceGeometry,
+        objects::{Face, Region, Sketch, Solid},
+        operations::{BuildRegion, BuildSketch, Insert, UpdateSketch},
+        services::Services,
+        storage::Handle,
+    };
+
+    #[test]
+    fn sweep_square_with_twist() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let region = Region::polygon(
+            [[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]],
+            &mut services,
+        )
+        .insert(&mut services);
+        let solid = sweep(region, tolerance, &mut services);
+
+        let faces = faces(&solid);
+        assert_eq!(faces.len(), 6);
+
+        // The side faces must stay within the tolerance of the exact twist.
+        for face in &faces[1..5] {
+            let SurfaceGeometry::Nurbs(surface) = face.surface().geometry()
+            else {
+                panic!("Expected side face to be defined in a NURBS surface");
+            };
+
+            let [[min_u, max_u], _] = surface.domain();
+            let [start, end] = [min_u, max_u]
+                .map(|u| surface.point_from_nurbs_coords([u, Scalar::ZERO]));
+
+            for i in 0..=10 {
+                for j in 0..=10 {
+                    let [a, b] = [i, j].map(|k| Scalar::from(k as f64 / 10.));
+
+                    let point = surface.point_from_nurbs_coords([
+                        min_u + (max_u - min_u) * a,
+                        b,
+                    ]);
+                    let expected =
+                        twist(b).transform_point(&(start + (end - start) * a));
+
+                    assert!(
+                        (point - expected).magnitude() <= tolerance.inner()
+                    );
+                }
+            }
+        }
+
+        let mesh = (&*solid, tolerance).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_circle_with_twist() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let region =
+            Region::circle([1., 0.], 1., &mut services).insert(&mut services);
+        let solid = sweep(region, Tolerance::from_scalar(0.01)?, &mut services);
+
+        // The circle is approximated by a polygon, each edge of which results
+        // in a side face.
+        assert!(faces(&solid).len() > 5);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    fn sweep(
+        region: Handle<Region>,
+        tolerance: Tolerance,
+        services: &mut Services,
+    ) -> Handle<Solid> {
+        let sketch = Sketch::empty().add_region(region).insert(services);
+        let surface = services.objects.surfaces.xy_plane();
+
+        (sketch, surface).sweep_with_twist(
+            [0., 0., 2.],
+            Scalar::PI / 2.,
+            tolerance,
+            services,
+        )
+    }
+
+    fn twist(s: Scalar) -> Transform {
+        Transform::translation(Vector::from([0., 0., 2.]) * s)
+            * Transform::rotation(Vector::unit_z() * Scalar::PI / 2. * s)
+    }
+
+    fn faces(solid: &Solid) -> Vec<Face> {
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| {
+                shell.faces().iter().map(|face| Face::clone(face))
+            })
+            .collect()
+    }
+}
@@ -4,7 +4,7 @@ mod delaunay;
 mod polygon;
 
 use fj_interop::mesh::Mesh;
-use fj_math::Point;
+use fj_math::{Point, Triangle};
 
 use self::polygon::Polygon;
 
@@ -56,8 +56,11 @@ impl Triangulate for FaceApprox {
             }));
 
         let cycles = [self.exterior].into_iter().chain(self.interiors);
-        let mut triangles =
-            delaunay::triangulate(cycles, self.coord_handedness);
+        let mut triangles = delaunay::triangulate(
+            cycles,
+            self.inner_points,
+            self.coord_handedness,
+        );
         triangles.retain(|triangle| {
             face_as_polygon
                 .contains_triangle(triangle.map(|point| point.point_surface))
@@ -67,6 +70,14 @@ impl Triangulate for FaceApprox {
 
         for triangle in triangles {
             let points = triangle.map(|point| point.point_global);
+
+            // Where the surface collapses into a single point, like at the apex
+            // of a cone, triangles can collapse too. Those don't contribute
+            // anything to the mesh.
+            if Triangle::from_points(points).is_err() {
+                continue;
+            }
+
             mesh.push_triangle(points, color);
         }
     }
@@ -74,13 +85,18 @@ impl Triangulate for FaceApprox {
 
 #[cfg(test)]
 mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
     use fj_interop::mesh::Mesh;
-    use fj_math::{Point, Scalar};
+    use fj_math::{Circle, Point, Scalar, Vector};
 
     use crate::{
         algorithms::approx::{Approx, Tolerance},
-        objects::{Cycle, Face},
-        operations::{BuildCycle, BuildFace, Insert, UpdateFace, UpdateRegion},
+        objects::{Cycle, Face, Surface},
+        operations::{
+            BuildCycle, BuildFace, BuildSurface, Insert, UpdateFace,
+            UpdateRegion,
+        },
         services::Services,
     };
 
@@ -235,6 +251,165 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn elliptical_face() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let [radius_u, radius_v] = [3., 1.];
+
+        let face =
+            Face::unbound(services.objects.surfaces.xy_plane(), &mut services)
+                .update_region(|region| {
+                    region
+                        .update_exterior(|_| {
+                            Cycle::ellipse(
+                                [0., 0.],
+                                [radius_u, radius_v],
+                                &mut services,
+                            )
+                            .insert(&mut services)
+                        })
+                        .insert(&mut services)
+                });
+        services.only_validate(&face);
+
+        let triangles = triangulate(face)?;
+        assert!(triangles.triangles().next().is_some());
+
+        // All vertices of the approximation are located on the ellipse.
+        for vertex in triangles.vertices() {
+            let distance = (vertex.x / radius_u) * (vertex.x / radius_u)
+                + (vertex.y / radius_v) * (vertex.y / radius_v);
+            assert!((distance - 1.).abs() < Scalar::from(1e-12));
+            assert_eq!(vertex.z, Scalar::ZERO);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn spherical_face() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let surface = Surface::sphere_from_center_and_radius([0., 0., 0.], 1.)
+            .insert(&mut services);
+
+        // A patch that covers a quarter of the longitude, from the equator
+        // to well below the north pole.
+        let face = Face::polygon(
+            surface,
+            [[0., 0.], [FRAC_PI_2, 0.], [FRAC_PI_2, 1.], [0., 1.]],
+            &mut services,
+        );
+        services.only_validate(&face);
+
+        triangulate_curved(&face)
+    }
+
+    #[test]
+    fn conical_face() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let surface = Surface::cone_from_circle_and_apex(
+            Circle::from_center_and_radius([0., 0., 0.], 2.),
+            [0., 0., 2.],
+        )
+        .insert(&mut services);
+
+        // A patch that covers a quarter of the angle, from the circle halfway
+        // up to the apex.
+        let face = Face::polygon(
+            surface,
+            [[0., 0.], [FRAC_PI_2, 0.], [FRAC_PI_2, 0.5], [0., 0.5]],
+            &mut services,
+        );
+        services.only_validate(&face);
+
+        triangulate_curved(&face)
+    }
+
+    #[test]
+    fn toroidal_face() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let surface =
+            Surface::torus_from_center_and_radii([0., 0., 0.], 3., 1.)
+                .insert(&mut services);
+
+        // A patch that covers a quarter of the angle around the axis, and the
+        // upper half of the tube.
+        let face = Face::polygon(
+            surface,
+            [[0., 0.], [FRAC_PI_2, 0.], [FRAC_PI_2, PI], [0., PI]],
+            &mut services,
+        );
+        services.only_validate(&face);
+
+        triangulate_curved(&face)
+    }
+
+    #[test]
+    fn nurbs_face() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // A saddle-like patch, bent along both axes.
+        let surface = Surface::nurbs_from_control_points(
+            [2, 2],
+            [
+                [[0., 0., 0.], [0., 1., 1.], [0., 2., 0.]],
+                [[1., 0., -1.], [1., 1., 0.], [1., 2., -1.]],
+                [[2., 0., 0.], [2., 1., 1.], [2., 2., 0.]],
+            ],
+        )
+        .insert(&mut services);
+
+        // Most of the surface, keeping clear of its edges.
+        let face = Face::polygon(
+            surface,
+            [[0.1, 0.1], [0.9, 0.1], [0.9, 0.9], [0.1, 0.9]],
+            &mut services,
+        );
+        services.only_validate(&face);
+
+        triangulate_curved(&face)
+    }
+
+    /// Triangulate a face on a curved surface and check the result
+    ///
+    /// All triangles must stay within the tolerance of the surface, and their
+    /// front sides must match that of the surface.
+    fn triangulate_curved(face: &Face) -> anyhow::Result<()> {
+        let tolerance = 0.05;
+        let surface = face.surface().geometry();
+
+        let approx = face.approx(Tolerance::from_scalar(tolerance)?);
+        assert!(!approx.inner_points.is_empty());
+
+        let triangles = approx.triangulate();
+        assert!(triangles.triangles().count() > 0);
+
+        for triangle in triangles.triangles() {
+            let [a, b, c] = triangle.inner.points();
+            let center = a + ((b - a) + (c - a)) / 3.;
+
+            let center_surface = surface.project_global_point(center);
+            let on_surface = surface.point_from_surface_coords(center_surface);
+            assert!((on_surface - center).magnitude() <= tolerance.into());
+
+            let normal = {
+                let [u, v] = [[0.001, 0.], [0., 0.001]].map(|offset| {
+                    surface.point_from_surface_coords(
+                        center_surface + Vector::from(offset),
+                    ) - on_surface
+                });
+                u.cross(&v)
+            };
+            assert!((b - a).cross(&(c - a)).dot(&normal) > Scalar::ZERO);
+        }
+
+        Ok(())
+    }
+
     fn triangulate(face: Face) -> anyhow::Result<Mesh<Point<3>>> {
         let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
         Ok(face.approx(tolerance).triangulate())
@@ -2,10 +2,12 @@
 
 mod boundary;
 mod path;
+mod shared;
 mod surface;
 
 pub use self::{
     boundary::{CurveBoundary, CurveBoundaryElement},
     path::{GlobalPath, SurfacePath},
-    surface::SurfaceGeometry,
+    shared::Shared,
+    surface::{ProjectionError, SurfaceGeometry},
 };
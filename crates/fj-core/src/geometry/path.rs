@@ -2,7 +2,11 @@
 //!
 //! See [`SurfacePath`] and [`GlobalPath`].
 
-use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
+use fj_math::{
+    Circle, Ellipse, Helix, Line, NurbsCurve, Point, Scalar, Transform, Vector,
+};
+
+use super::Shared;
 
 /// A path through surface (2D) space
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -10,8 +14,14 @@ pub enum SurfacePath {
     /// A circle
     Circle(Circle<2>),
 
+    /// An ellipse
+    Ellipse(Ellipse<2>),
+
     /// A line
     Line(Line<2>),
+
+    /// A NURBS curve
+    Nurbs(Shared<NurbsCurve<2>>),
 }
 
 impl SurfacePath {
@@ -23,6 +33,17 @@ impl SurfacePath {
         Self::Circle(Circle::from_center_and_radius(center, radius))
     }
 
+    /// Build an ellipse from the given radii
+    ///
+    /// The first radius is measured along the u-axis of the surface, the
+    /// second along the v-axis.
+    pub fn ellipse_from_center_and_radii(
+        center: impl Into<Point<2>>,
+        radii: [impl Into<Scalar>; 2],
+    ) -> Self {
+        Self::Ellipse(Ellipse::from_center_and_radii(center, radii))
+    }
+
     /// Build a line that represents the u-axis of the surface its on
     pub fn u_axis() -> Self {
         let a = Point::origin();
@@ -58,6 +79,18 @@ impl SurfacePath {
         Self::Line(Line::from_points_with_line_coords(points))
     }
 
+    /// Build a B-spline curve from a degree and control points
+    ///
+    /// See [`NurbsCurve::from_control_points`].
+    pub fn nurbs_from_control_points(
+        degree: usize,
+        control_points: impl IntoIterator<Item = impl Into<Point<2>>>,
+    ) -> Self {
+        Self::Nurbs(
+            NurbsCurve::from_control_points(degree, control_points).into(),
+        )
+    }
+
     /// Convert a point on the path into surface coordinates
     pub fn point_from_path_coords(
         &self,
@@ -65,7 +98,9 @@ impl SurfacePath {
     ) -> Point<2> {
         match self {
             Self::Circle(circle) => circle.point_from_circle_coords(point),
+            Self::Ellipse(ellipse) => ellipse.point_from_ellipse_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
+            Self::Nurbs(curve) => curve.point_from_nurbs_coords(point),
         }
     }
 
@@ -74,7 +109,11 @@ impl SurfacePath {
     pub fn reverse(self) -> Self {
         match self {
             Self::Circle(circle) => Self::Circle(circle.reverse()),
+            Self::Ellipse(ellipse) => Self::Ellipse(ellipse.reverse()),
             Self::Line(line) => Self::Line(line.reverse()),
+            Self::Nurbs(curve) => {
+                Self::Nurbs(NurbsCurve::clone(&curve).reverse().into())
+            }
         }
     }
 }
@@ -85,8 +124,17 @@ pub enum GlobalPath {
     /// A circle
     Circle(Circle<3>),
 
+    /// An ellipse
+    Ellipse(Ellipse<3>),
+
+    /// A helix
+    Helix(Helix),
+
     /// A line
     Line(Line<3>),
+
+    /// A NURBS curve
+    Nurbs(Shared<NurbsCurve<3>>),
 }
 
 impl GlobalPath {
@@ -135,7 +183,13 @@ impl GlobalPath {
     pub fn origin(&self) -> Point<3> {
         match self {
             Self::Circle(circle) => circle.center() + circle.a(),
+            Self::Ellipse(ellipse) => ellipse.center() + ellipse.a(),
+            Self::Helix(helix) => helix.circle().center() + helix.circle().a(),
             Self::Line(line) => line.origin(),
+            Self::Nurbs(curve) => {
+                let [start, _] = curve.domain();
+                curve.point_from_nurbs_coords([start])
+            }
         }
     }
 
@@ -146,18 +200,38 @@ impl GlobalPath {
     ) -> Point<3> {
         match self {
             Self::Circle(circle) => circle.point_from_circle_coords(point),
+            Self::Ellipse(ellipse) => ellipse.point_from_ellipse_coords(point),
+            Self::Helix(helix) => helix.point_from_helix_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
+            Self::Nurbs(curve) => curve.point_from_nurbs_coords(point),
         }
     }
 
     /// Convert a vector on the path into global coordinates
+    ///
+    /// The curve coordinates of a NURBS curve don't relate to global
+    /// coordinates in the same way everywhere. Vectors on such a curve are
+    /// converted using its derivative at the [origin](Self::origin) of the
+    /// path.
     pub fn vector_from_path_coords(
         &self,
         vector: impl Into<Vector<1>>,
     ) -> Vector<3> {
         match self {
             Self::Circle(circle) => circle.vector_from_circle_coords(vector),
+            Self::Ellipse(ellipse) => {
+                ellipse.vector_from_ellipse_coords(vector)
+            }
+            Self::Helix(helix) => helix.vector_from_helix_coords(vector),
             Self::Line(line) => line.vector_from_line_coords(vector),
+            Self::Nurbs(curve) => {
+                let [start, _] = curve.domain();
+                let [derivative] = curve.derivatives([start], 1)[..] else {
+                    unreachable!("Requested exactly one derivative")
+                };
+
+                derivative * vector.into().t
+            }
         }
     }
 
@@ -168,7 +242,16 @@ impl GlobalPath {
             Self::Circle(curve) => {
                 Self::Circle(transform.transform_circle(&curve))
             }
+            Self::Ellipse(curve) => {
+                Self::Ellipse(transform.transform_ellipse(&curve))
+            }
+            Self::Helix(curve) => {
+                Self::Helix(transform.transform_helix(&curve))
+            }
             Self::Line(curve) => Self::Line(transform.transform_line(&curve)),
+            Self::Nurbs(curve) => {
+                Self::Nurbs(transform.transform_nurbs_curve(&curve).into())
+            }
         }
     }
 }
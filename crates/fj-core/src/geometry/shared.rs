@@ -0,0 +1,77 @@
+use std::{fmt, ops::Deref};
+
+/// Geometry data that is shared, instead of being copied
+///
+/// Paths and surface geometry are small values that are copied freely. Data
+/// that doesn't fit that, like the control points of a NURBS curve or surface,
+/// is stored once and shared between all copies.
+///
+/// Like objects in a [`Store`], that data is never deallocated. That keeps
+/// `Shared` as cheap to copy as a reference.
+///
+/// Equality, ordering, and hashing are defined via the shared data, not its
+/// location in memory.
+///
+/// [`Store`]: crate::storage::Store
+pub struct Shared<T: 'static>(&'static T);
+
+impl<T> Shared<T> {
+    /// Store the provided data, so it can be shared
+    pub fn new(data: T) -> Self {
+        Self(Box::leak(Box::new(data)))
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Shared<T> {}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<T> From<T> for Shared<T> {
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Shared<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq(other.0)
+    }
+}
+
+impl<T: Eq> Eq for Shared<T> {}
+
+impl<T: PartialOrd> PartialOrd for Shared<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(other.0)
+    }
+}
+
+impl<T: Ord> Ord for Shared<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(other.0)
+    }
+}
+
+impl<T: std::hash::Hash> std::hash::Hash for Shared<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
@@ -1,74 +1,288 @@
 //! The geometry that defines a surface
 
-use fj_math::{Line, Plane, Point, Transform, Vector};
+use fj_math::{
+    Cone, Ellipse, Line, NurbsSurface, Plane, Point, Scalar, Sphere, Torus,
+    Transform, Vector,
+};
 
-use super::GlobalPath;
+use super::{GlobalPath, Shared};
 
 /// The geometry that defines a surface
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct SurfaceGeometry {
-    /// The u-axis of the surface
-    pub u: GlobalPath,
+pub enum SurfaceGeometry {
+    /// A surface that is defined by sweeping a path along a straight line
+    ///
+    /// Sweeping a line results in a plane, sweeping a circle in a cylinder.
+    Swept {
+        /// The u-axis of the surface
+        u: GlobalPath,
 
-    /// The v-axis of the surface
-    pub v: Vector<3>,
+        /// The v-axis of the surface
+        v: Vector<3>,
+    },
+
+    /// A sphere
+    ///
+    /// The surface coordinates are the longitude (u) and latitude (v) of the
+    /// sphere, as defined by [`Sphere`]. If the sphere's `a`, `b`, and `c`
+    /// vectors form a right-handed coordinate system, the front side of the
+    /// surface faces away from the center of the sphere.
+    ///
+    /// At the poles, all longitudes refer to the same point. Edges that bound
+    /// a face on a sphere should stay clear of the poles.
+    Sphere(Sphere),
+
+    /// A cone
+    ///
+    /// The surface coordinates are the angle around the axis (u) and the
+    /// position along the axis (v), as defined by [`Cone`]. If the apex of the
+    /// cone is located on the side of its circle that the circle's normal
+    /// points to, the front side of the surface faces away from the axis.
+    ///
+    /// At the apex, all angles refer to the same point. A face on a cone that
+    /// reaches the apex is bounded there by an edge that collapses into that
+    /// point.
+    Cone(Cone),
+
+    /// A torus
+    ///
+    /// The surface coordinates are the angle around the axis (u) and the angle
+    /// around the tube (v), as defined by [`Torus`]. If the torus' `a`, `b`,
+    /// and `c` vectors form a right-handed coordinate system, the front side of
+    /// the surface faces away from the middle of the tube.
+    Torus(Torus),
+
+    /// A NURBS surface
+    ///
+    /// The surface coordinates are the parameters of the surface, as defined
+    /// by [`NurbsSurface`]. The front side of the surface is the one that the
+    /// cross product of the partial derivatives along the u-axis and the
+    /// v-axis points to.
+    ///
+    /// Where those derivatives are parallel, for example where one side of the
+    /// grid of control points collapses into a single point, different surface
+    /// coordinates refer to the same point. Edges that bound a face on a NURBS
+    /// surface should stay clear of such places.
+    Nurbs(Shared<NurbsSurface>),
 }
 
 impl SurfaceGeometry {
+    /// Compute the normal of the surface, if it is a plane
+    ///
+    /// The normal is normalized, and points to the side from which the
+    /// coordinate system of the surface appears right-handed. Returns `None`,
+    /// if the surface is not a plane.
+    pub fn plane_normal(&self) -> Option<Vector<3>> {
+        let Self::Swept {
+            u: GlobalPath::Line(line),
+            v,
+        } = self
+        else {
+            return None;
+        };
+
+        Some(line.direction().cross(v).normalize())
+    }
+
     /// Convert a point in surface coordinates to model coordinates
     pub fn point_from_surface_coords(
         &self,
         point: impl Into<Point<2>>,
     ) -> Point<3> {
         let point = point.into();
-        self.u.point_from_path_coords([point.u])
-            + self.path_to_line().vector_from_line_coords([point.v])
+
+        match self {
+            Self::Swept { u, v } => {
+                u.point_from_path_coords([point.u])
+                    + path_to_line(u, v).vector_from_line_coords([point.v])
+            }
+            Self::Sphere(sphere) => sphere.point_from_sphere_coords(point),
+            Self::Cone(cone) => cone.point_from_cone_coords(point),
+            Self::Torus(torus) => torus.point_from_torus_coords(point),
+            Self::Nurbs(surface) => surface.point_from_nurbs_coords(point),
+        }
     }
 
     /// Convert a vector in surface coordinates to model coordinates
+    ///
+    /// The surface coordinates of a NURBS surface don't relate to model
+    /// coordinates in the same way everywhere. Vectors on such a surface are
+    /// converted using its partial derivatives at the start of its domain. The
+    /// same goes for surfaces that have been swept from a NURBS curve, as
+    /// described in [`GlobalPath::vector_from_path_coords`].
     pub fn vector_from_surface_coords(
         &self,
         vector: impl Into<Vector<2>>,
     ) -> Vector<3> {
         let vector = vector.into();
-        self.u.vector_from_path_coords([vector.u])
-            + self.path_to_line().vector_from_line_coords([vector.v])
-    }
 
-    fn path_to_line(&self) -> Line<3> {
-        Line::from_origin_and_direction(self.u.origin(), self.v)
+        match self {
+            Self::Swept { u, v } => {
+                u.vector_from_path_coords([vector.u])
+                    + path_to_line(u, v).vector_from_line_coords([vector.v])
+            }
+            Self::Sphere(sphere) => sphere.vector_from_sphere_coords(vector),
+            Self::Cone(cone) => cone.vector_from_cone_coords(vector),
+            Self::Torus(torus) => torus.vector_from_torus_coords(vector),
+            Self::Nurbs(surface) => {
+                let [[min_u, _], [min_v, _]] = surface.domain();
+                let derivatives = surface.derivatives([min_u, min_v], 1);
+
+                derivatives[1][0] * vector.u + derivatives[0][1] * vector.v
+            }
+        }
     }
 
     /// Project the global point into the surface
+    ///
+    /// # Panics
+    ///
+    /// Panics, if projecting points into the surface is not supported. See
+    /// [`SurfaceGeometry::try_project_global_point`] for a fallible variant.
     pub fn project_global_point(&self, point: impl Into<Point<3>>) -> Point<2> {
-        let GlobalPath::Line(line) = self.u else {
-            todo!("Projecting point into non-plane surface is not supported")
+        self.try_project_global_point(point)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Project the global point into the surface, if that is supported
+    ///
+    /// Points that are not located on the surface are projected along the
+    /// surface's normal, as far as that is possible. On a cylinder, for
+    /// example, a point is projected towards or away from the axis.
+    ///
+    /// Where the surface coordinates of a point are ambiguous, like for a point
+    /// on the axis of a cylinder, the angle that refers to that point is zero.
+    pub fn try_project_global_point(
+        &self,
+        point: impl Into<Point<3>>,
+    ) -> Result<Point<2>, ProjectionError> {
+        let point = point.into();
+
+        let projected = match self {
+            Self::Swept {
+                u: GlobalPath::Line(line),
+                v,
+            } => {
+                let plane =
+                    Plane::from_parametric(line.origin(), line.direction(), *v);
+                plane.project_point(point)
+            }
+            Self::Swept {
+                u: GlobalPath::Circle(circle),
+                v,
+            } => project_into_swept_ellipse((*circle).into(), *v, point)?,
+            Self::Swept {
+                u: GlobalPath::Ellipse(ellipse),
+                v,
+            } => project_into_swept_ellipse(*ellipse, *v, point)?,
+            Self::Swept {
+                u: GlobalPath::Helix(_),
+                ..
+            } => {
+                return Err(ProjectionError::UnsupportedSurface {
+                    surface: "surface swept from helix",
+                });
+            }
+            Self::Swept {
+                u: GlobalPath::Nurbs(_),
+                ..
+            } => {
+                return Err(ProjectionError::UnsupportedSurface {
+                    surface: "surface swept from NURBS curve",
+                });
+            }
+            Self::Sphere(sphere) => sphere.point_to_sphere_coords(point),
+            Self::Cone(cone) => cone.point_to_cone_coords(point),
+            Self::Torus(torus) => torus.point_to_torus_coords(point),
+            Self::Nurbs(surface) => surface.point_to_nurbs_coords(point),
         };
 
-        let plane =
-            Plane::from_parametric(line.origin(), line.direction(), self.v);
-        plane.project_point(point)
+        Ok(projected)
     }
 
     /// Transform the surface geometry
     #[must_use]
     pub fn transform(self, transform: &Transform) -> Self {
-        let u = self.u.transform(transform);
-        let v = transform.transform_vector(&self.v);
-        Self { u, v }
+        match self {
+            Self::Swept { u, v } => {
+                let u = u.transform(transform);
+                let v = transform.transform_vector(&v);
+                Self::Swept { u, v }
+            }
+            Self::Sphere(sphere) => {
+                Self::Sphere(transform.transform_sphere(&sphere))
+            }
+            Self::Cone(cone) => Self::Cone(transform.transform_cone(&cone)),
+            Self::Torus(torus) => {
+                Self::Torus(transform.transform_torus(&torus))
+            }
+            Self::Nurbs(surface) => {
+                Self::Nurbs(transform.transform_nurbs_surface(&surface).into())
+            }
+        }
+    }
+}
+
+/// Error projecting a point into a surface
+///
+/// Returned by [`SurfaceGeometry::try_project_global_point`].
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ProjectionError {
+    /// The surface is degenerate
+    ///
+    /// This is the case for a swept surface whose v-axis lies within the plane
+    /// of its circle or ellipse. All points of such a surface lie in a single
+    /// plane, and most of them can be reached by many different surface
+    /// coordinates.
+    #[error("Can't project point into degenerate surface")]
+    DegenerateSurface,
+
+    /// Projecting points into this kind of surface is not supported yet
+    #[error("Projecting point into {surface} is not supported yet")]
+    UnsupportedSurface {
+        /// A description of the surface
+        surface: &'static str,
+    },
+}
+
+fn project_into_swept_ellipse(
+    ellipse: Ellipse<3>,
+    v: Vector<3>,
+    point: Point<3>,
+) -> Result<Point<2>, ProjectionError> {
+    // The v-axis doesn't need to be perpendicular to the ellipse. Move the
+    // point along it, into the plane of the ellipse, to find its v-coordinate.
+    let normal = ellipse.a().cross(&ellipse.b());
+    let v_along_normal = v.dot(&normal);
+    if v_along_normal == Scalar::ZERO {
+        return Err(ProjectionError::DegenerateSurface);
     }
+
+    let point_v = (point - ellipse.center()).dot(&normal) / v_along_normal;
+    let [point_u] = ellipse
+        .point_to_ellipse_coords(point - v * point_v)
+        .coords
+        .components;
+
+    Ok(Point::from([point_u, point_v]))
+}
+
+fn path_to_line(u: &GlobalPath, v: &Vector<3>) -> Line<3> {
+    Line::from_origin_and_direction(u.origin(), *v)
 }
 
 #[cfg(test)]
 mod tests {
-    use fj_math::{Line, Point, Vector};
+    use fj_math::{
+        Circle, Ellipse, Line, NurbsSurface, Point, Scalar, Sphere, Vector,
+    };
     use pretty_assertions::assert_eq;
 
-    use crate::geometry::{GlobalPath, SurfaceGeometry};
+    use crate::geometry::{GlobalPath, ProjectionError, SurfaceGeometry};
 
     #[test]
     fn point_from_surface_coords() {
-        let surface = SurfaceGeometry {
+        let surface = SurfaceGeometry::Swept {
             u: GlobalPath::Line(Line::from_origin_and_direction(
                 Point::from([1., 1., 1.]),
                 Vector::from([0., 2., 0.]),
@@ -84,7 +298,7 @@ mod tests {
 
     #[test]
     fn vector_from_surface_coords() {
-        let surface = SurfaceGeometry {
+        let surface = SurfaceGeometry::Swept {
             u: GlobalPath::Line(Line::from_origin_and_direction(
                 Point::from([1., 0., 0.]),
                 Vector::from([0., 2., 0.]),
@@ -97,4 +311,143 @@ mod tests {
             Vector::from([0., 4., 8.]),
         );
     }
+
+    #[test]
+    fn vector_from_nurbs_surface_coords() {
+        let surface = SurfaceGeometry::Nurbs(
+            NurbsSurface::from_control_points(
+                [1, 2],
+                [
+                    [[0., 0., 0.], [0., 1., 0.], [0., 2., 1.]],
+                    [[2., 0., 0.], [2., 1., 0.], [2., 2., 1.]],
+                ],
+            )
+            .into(),
+        );
+
+        // At the start of the domain, the surface runs along the x-axis with
+        // twice the speed of its u-coordinate, and along the y-axis with twice
+        // the speed of its v-coordinate.
+        assert_eq!(
+            surface.vector_from_surface_coords([0.5, 0.25]),
+            Vector::from([1., 0.5, 0.]),
+        );
+    }
+
+    #[test]
+    fn project_global_point_into_sphere() {
+        let surface = SurfaceGeometry::Sphere(Sphere::from_center_and_radius(
+            [1., 1., 1.],
+            2.,
+        ));
+
+        let point = Point::from([1., 0.5]);
+        let point_global = surface.point_from_surface_coords(point);
+
+        let projected = surface.project_global_point(point_global);
+        assert!((projected - point).magnitude() < Scalar::from(1e-12));
+    }
+
+    #[test]
+    fn project_global_point_into_nurbs_surface() {
+        let surface = SurfaceGeometry::Nurbs(
+            NurbsSurface::from_control_points(
+                [2, 3],
+                [
+                    [[0., 0., 0.], [0., 1., 1.], [0., 2., -1.], [0., 3., 0.]],
+                    [[1., 0., 1.], [1., 1., 0.], [1., 2., 1.], [1., 3., -1.]],
+                    [[2., 0., 0.], [2., 1., -1.], [2., 2., 0.], [2., 3., 1.]],
+                ],
+            )
+            .into(),
+        );
+
+        for point in [[0., 0.], [0.2, 0.7], [0.6, 0.3], [1., 1.]] {
+            let point = Point::from(point);
+            let point_global = surface.point_from_surface_coords(point);
+
+            let projected = surface.project_global_point(point_global);
+            assert!((projected - point).magnitude() < Scalar::from(1e-9));
+        }
+    }
+
+    #[test]
+    fn project_global_point_into_cylinder() {
+        let circle = Circle::from_center_and_radius([1., 2., 3.], 2.);
+
+        // The v-axis of a cylinder doesn't need to be perpendicular to its
+        // circle.
+        for v in [[0., 0., 2.], [1., 0., 1.]] {
+            let surface = SurfaceGeometry::Swept {
+                u: GlobalPath::Circle(circle),
+                v: Vector::from(v),
+            };
+
+            for point in [[0., 0.], [1., 0.5], [4., -2.]] {
+                let point = Point::from(point);
+                let point_global = surface.point_from_surface_coords(point);
+
+                let projected = surface.project_global_point(point_global);
+                assert!((projected - point).magnitude() < Scalar::from(1e-12));
+            }
+        }
+    }
+
+    #[test]
+    fn project_global_point_into_elliptic_cylinder() {
+        let surface = SurfaceGeometry::Swept {
+            u: GlobalPath::Ellipse(Ellipse::new(
+                [1., 2., 3.],
+                [3., 0., 0.],
+                [1., 1., 0.],
+            )),
+            v: Vector::from([0., 1., 2.]),
+        };
+
+        for point in [[0., 0.], [1., 0.5], [4., -2.]] {
+            let point = Point::from(point);
+            let point_global = surface.point_from_surface_coords(point);
+
+            let projected = surface.project_global_point(point_global);
+            assert!((projected - point).magnitude() < Scalar::from(1e-12));
+        }
+    }
+
+    #[test]
+    fn project_global_point_near_cylinder() {
+        let surface = SurfaceGeometry::Swept {
+            u: GlobalPath::Circle(Circle::from_center_and_radius(
+                [0., 0., 0.],
+                1.,
+            )),
+            v: Vector::from([0., 0., 1.]),
+        };
+
+        // Points inside and outside of the cylinder are projected towards or
+        // away from the axis.
+        assert_eq!(
+            surface.project_global_point([0.5, 0., 2.]),
+            Point::from([0., 2.]),
+        );
+        assert_eq!(
+            surface.project_global_point([0., 3., -1.]),
+            Point::from([Scalar::PI / 2., Scalar::from(-1.)]),
+        );
+    }
+
+    #[test]
+    fn try_project_global_point_into_degenerate_surface() {
+        let surface = SurfaceGeometry::Swept {
+            u: GlobalPath::Circle(Circle::from_center_and_radius(
+                [0., 0., 0.],
+                1.,
+            )),
+            v: Vector::from([1., 0., 0.]),
+        };
+
+        assert_eq!(
+            surface.try_project_global_point([1., 0., 0.]),
+            Err(ProjectionError::DegenerateSurface),
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use fj_math::{Scalar, Winding};
+use fj_math::{Point, Scalar, Winding};
 
 use crate::{
     geometry::SurfacePath,
@@ -43,13 +43,36 @@ impl Cycle {
             let [a, b] = first.boundary().inner;
             let edge_direction_positive = a < b;
 
-            let circle = match first.path() {
-                SurfacePath::Circle(circle) => circle,
+            let [a, b] = match first.path() {
+                SurfacePath::Circle(circle) => [circle.a(), circle.b()],
+                SurfacePath::Ellipse(ellipse) => [ellipse.a(), ellipse.b()],
+                SurfacePath::Nurbs(_) => {
+                    // There's no axis we could look at here. Sample points
+                    // along the edges instead, and treat those as a polygon.
+                    let points = self
+                        .edges()
+                        .iter()
+                        .flat_map(|edge| {
+                            let [a, b] = edge.boundary().inner;
+                            (0..NUM_SAMPLES_PER_EDGE).map(move |i| {
+                                let t = a.t
+                                    + (b.t - a.t) * i as f64
+                                        / NUM_SAMPLES_PER_EDGE as f64;
+                                edge.path().point_from_path_coords([t])
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    return polygon_winding(&points).unwrap_or_else(|| {
+                        unreachable!("Encountered invalid cycle: {self:#?}")
+                    });
+                }
                 SurfacePath::Line(_) => unreachable!(
-                    "Invalid cycle: less than 3 edges, but not all are circles"
+                    "Invalid cycle: less than 3 edges, but not all are \
+                    curves"
                 ),
             };
-            let cross_positive = circle.a().cross2d(&circle.b()) > Scalar::ZERO;
+            let cross_positive = a.cross2d(&b) > Scalar::ZERO;
 
             if edge_direction_positive == cross_positive {
                 return Winding::Ccw;
@@ -62,21 +85,38 @@ impl Cycle {
         // cycle as a polygon:
         // https://stackoverflow.com/a/1165943
 
-        let mut sum = Scalar::ZERO;
+        let points = self
+            .edges()
+            .iter()
+            .map(|edge| edge.start_position())
+            .collect::<Vec<_>>();
 
-        for (a, b) in self.edges().pairs() {
-            let [a, b] = [a, b].map(|edge| edge.start_position());
+        polygon_winding(&points).unwrap_or_else(|| {
+            unreachable!("Encountered invalid cycle: {self:#?}")
+        })
+    }
+}
 
-            sum += (b.u - a.u) * (b.v + a.v);
-        }
+/// The number of points sampled per edge, when computing the winding of a
+/// cycle made up of NURBS curves
+const NUM_SAMPLES_PER_EDGE: usize = 16;
 
-        if sum > Scalar::ZERO {
-            return Winding::Cw;
-        }
-        if sum < Scalar::ZERO {
-            return Winding::Ccw;
-        }
+/// Compute the winding of a polygon
+///
+/// Returns `None`, if the polygon is degenerate.
+fn polygon_winding(points: &[Point<2>]) -> Option<Winding> {
+    let mut sum = Scalar::ZERO;
+
+    for (a, b) in points.iter().zip(points.iter().cycle().skip(1)) {
+        sum += (b.u - a.u) * (b.v + a.v);
+    }
 
-        unreachable!("Encountered invalid cycle: {self:#?}");
+    if sum > Scalar::ZERO {
+        return Some(Winding::Cw);
     }
+    if sum < Scalar::ZERO {
+        return Some(Winding::Ccw);
+    }
+
+    None
 }
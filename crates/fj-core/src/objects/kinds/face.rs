@@ -1,4 +1,4 @@
-use fj_math::Winding;
+use fj_math::{Vector, Winding};
 
 use crate::{
     objects::{Region, Surface},
@@ -67,6 +67,21 @@ impl Face {
             Winding::Cw => Handedness::LeftHanded,
         }
     }
+
+    /// Compute the normal of the face, if its surface is a plane
+    ///
+    /// The normal points to the front side of the face. Returns `None`, if the
+    /// surface is not a plane. See [`SurfaceGeometry::plane_normal`].
+    ///
+    /// [`SurfaceGeometry::plane_normal`]: crate::geometry::SurfaceGeometry::plane_normal
+    pub fn normal(&self) -> Option<Vector<3>> {
+        let normal = self.surface.geometry().plane_normal()?;
+
+        match self.coord_handedness() {
+            Handedness::RightHanded => Some(normal),
+            Handedness::LeftHanded => Some(-normal),
+        }
+    }
 }
 
 /// The handedness of a face's coordinate system
@@ -1,7 +1,7 @@
 use crate::geometry::SurfaceGeometry;
 
 /// A two-dimensional shape
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Surface {
     geometry: SurfaceGeometry,
 }
@@ -0,0 +1,258 @@
+//! Beveling of the edges of solids
+
+use fj_math::Scalar;
+
+use crate::{
+    algorithms::approx::Tolerance,
+    objects::{Edge, Solid},
+    services::Services,
+    storage::Handle,
+};
+
+use super::{apply, Blend, BlendError};
+
+/// Bevel the edges of a [`Solid`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait Chamfer: Sized {
+    /// Bevel the provided edges of the solid
+    ///
+    /// Each edge is replaced with a planar face, which cuts across the corner
+    /// that the two adjacent faces formed at the edge.
+    ///
+    /// Two beveled edges may share a vertex, like the edges around the top
+    /// face of a cuboid. Where they do, the bevel faces meet in a mitre.
+    ///
+    /// Returns an error, if the distance is not positive, if the angle is out
+    /// of range, if any of the edges is not part of the solid, or if the edges
+    /// are in a configuration that is not supported. See the
+    /// [module documentation].
+    ///
+    /// [module documentation]: super
+    fn chamfer(
+        &self,
+        edges: impl IntoIterator<Item = Handle<Edge>>,
+        size: ChamferSize,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BlendError>;
+}
+
+impl Chamfer for Solid {
+    fn chamfer(
+        &self,
+        edges: impl IntoIterator<Item = Handle<Edge>>,
+        size: ChamferSize,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BlendError> {
+        let (distance, angle) = match size {
+            ChamferSize::Distance(distance) => (distance, None),
+            ChamferSize::DistanceAngle { distance, angle } => {
+                (distance, Some(angle))
+            }
+        };
+        if distance <= Scalar::ZERO {
+            return Err(BlendError::NonPositiveSize);
+        }
+
+        apply(
+            self,
+            edges,
+            Blend::Straight { distance, angle },
+            tolerance,
+            services,
+        )
+    }
+}
+
+/// The size of a chamfer
+///
+/// Distances are measured from the beveled edge, along the faces next to it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum ChamferSize {
+    /// Bevel both faces next to the edge by the same distance
+    Distance(Scalar),
+
+    /// Bevel the face of the edge by a distance, at an angle
+    ///
+    /// The distance applies to the face that the provided edge is part of. The
+    /// angle, in radians, is the angle between that face and the bevel face.
+    /// How far the other face is beveled follows from that.
+    DistanceAngle {
+        /// The distance along the face that the edge is part of
+        distance: Scalar,
+
+        /// The angle between that face and the bevel face
+        angle: Scalar,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        operations::{
+            fixtures::{find_edge, num_faces, prism, tolerance},
+            BlendError, Insert,
+        },
+        services::Services,
+    };
+
+    use super::{Chamfer, ChamferSize};
+
+    #[test]
+    fn chamfer_vertical_edges_of_cuboid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edges = [[0., 0.], [2., 0.], [2., 2.], [0., 2.]]
+            .map(|[x, y]| find_edge(&cuboid, [[x, y, 0.], [x, y, 2.]]));
+
+        let beveled = cuboid
+            .chamfer(
+                edges,
+                ChamferSize::Distance(0.5.into()),
+                tolerance(),
+                &mut services,
+            )?
+            .insert(&mut services);
+
+        // The original six faces, plus one for each beveled edge.
+        assert_eq!(num_faces(&beveled), 10);
+
+        // The bevel faces start half a unit from the original edges.
+        find_edge(&beveled, [[0.5, 0., 0.], [0.5, 0., 2.]]);
+        find_edge(&beveled, [[0., 0.5, 0.], [0., 0.5, 2.]]);
+
+        let mesh = (&*beveled, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_edge_by_distance_and_angle() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edge = find_edge(&cuboid, [[0., 0., 2.], [2., 0., 2.]]);
+
+        let beveled = cuboid
+            .chamfer(
+                [edge],
+                ChamferSize::DistanceAngle {
+                    distance: Scalar::ONE,
+                    angle: Scalar::PI / 4.,
+                },
+                tolerance(),
+                &mut services,
+            )?
+            .insert(&mut services);
+
+        // At an angle of 45 degrees, both faces are beveled by the same
+        // distance.
+        assert_eq!(num_faces(&beveled), 7);
+        find_edge(&beveled, [[0., 1., 2.], [2., 1., 2.]]);
+        find_edge(&beveled, [[0., 0., 1.], [2., 0., 1.]]);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_edge_of_triangular_prism() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let prism = prism([[0., 0.], [2., 0.], [1., 2.]], 1., &mut services);
+        let edge = find_edge(&prism, [[1., 2., 0.], [1., 2., 1.]]);
+
+        let beveled = prism
+            .chamfer(
+                [edge],
+                ChamferSize::DistanceAngle {
+                    distance: 0.5.into(),
+                    angle: Scalar::PI / 3.,
+                },
+                tolerance(),
+                &mut services,
+            )?
+            .insert(&mut services);
+
+        assert_eq!(num_faces(&beveled), 6);
+
+        let mesh = (&*beveled, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_edges_around_top_of_cuboid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edges = [[0., 0.], [2., 0.], [2., 2.], [0., 2.], [0., 0.]]
+            .windows(2)
+            .map(|points| {
+                let [[x1, y1], [x2, y2]] = [points[0], points[1]];
+                find_edge(&cuboid, [[x1, y1, 2.], [x2, y2, 2.]])
+            })
+            .collect::<Vec<_>>();
+
+        let beveled = cuboid
+            .chamfer(
+                edges,
+                ChamferSize::Distance(0.5.into()),
+                tolerance(),
+                &mut services,
+            )?
+            .insert(&mut services);
+
+        // The original six faces, plus one for each beveled edge. The bevel
+        // faces meet in mitres.
+        assert_eq!(num_faces(&beveled), 10);
+        find_edge(&beveled, [[0.5, 0.5, 2.], [1.5, 0.5, 2.]]);
+        find_edge(&beveled, [[0., 0., 1.5], [0.5, 0.5, 2.]]);
+
+        let mesh = (&*beveled, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_edges_with_mismatched_distances() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edges = [
+            find_edge(&cuboid, [[0., 0., 0.], [0., 0., 2.]]),
+            find_edge(&cuboid, [[0., 0., 2.], [0., 2., 2.]]),
+        ];
+
+        // The vertical edge is beveled by the same distance along both faces
+        // next to it, the horizontal one isn't. Their bevels would not meet on
+        // the edge between them.
+        let size = ChamferSize::DistanceAngle {
+            distance: 0.5.into(),
+            angle: Scalar::PI / 3.,
+        };
+        let result = cuboid.chamfer(edges, size, tolerance(), &mut services);
+        assert_eq!(result, Err(BlendError::MismatchedSetbacks));
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+//! Rounding of the edges of solids
+
+use fj_math::Scalar;
+
+use crate::{
+    algorithms::approx::Tolerance,
+    objects::{Edge, Solid},
+    services::Services,
+    storage::Handle,
+};
+
+use super::{apply, Blend, BlendError};
+
+/// Round the edges of a [`Solid`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait Fillet: Sized {
+    /// Round the provided edges of the solid
+    ///
+    /// Each edge is replaced with a face on a cylindrical surface of the given
+    /// radius, which is tangent to both faces that met at the edge.
+    ///
+    /// Two rounded edges may share a vertex, like the edges around the top
+    /// face of a cuboid. Where they do, the rounded faces meet in a mitre.
+    ///
+    /// Returns an error, if the radius is not positive, if any of the edges is
+    /// not part of the solid, or if the edges are in a configuration that is
+    /// not supported. See [module documentation].
+    ///
+    /// [module documentation]: super
+    fn fillet(
+        &self,
+        edges: impl IntoIterator<Item = Handle<Edge>>,
+        radius: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BlendError>;
+}
+
+impl Fillet for Solid {
+    fn fillet(
+        &self,
+        edges: impl IntoIterator<Item = Handle<Edge>>,
+        radius: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BlendError> {
+        let radius = radius.into();
+        if radius <= Scalar::ZERO {
+            return Err(BlendError::NonPositiveSize);
+        }
+
+        apply(self, edges, Blend::Round { radius }, tolerance, services)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        operations::{
+            fixtures::{find_edge, num_faces, prism, tolerance},
+            BlendError, Insert,
+        },
+        services::Services,
+    };
+
+    use super::Fillet;
+
+    #[test]
+    fn fillet_vertical_edges_of_cuboid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edges = [[0., 0.], [2., 0.], [2., 2.], [0., 2.]]
+            .map(|[x, y]| find_edge(&cuboid, [[x, y, 0.], [x, y, 2.]]));
+
+        let rounded = cuboid
+            .fillet(edges, 0.5, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // The original six faces, plus one for each rounded edge.
+        assert_eq!(num_faces(&rounded), 10);
+
+        let mesh = (&*rounded, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_horizontal_edge_of_cuboid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edge = find_edge(&cuboid, [[0., 0., 2.], [2., 0., 2.]]);
+
+        let rounded = cuboid
+            .fillet([edge], 0.5, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(num_faces(&rounded), 7);
+
+        let mesh = (&*rounded, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_edge_of_triangular_prism() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let prism = prism([[0., 0.], [2., 0.], [1., 2.]], 1., &mut services);
+        let edge = find_edge(&prism, [[2., 0., 0.], [2., 0., 1.]]);
+
+        let rounded = prism
+            .fillet([edge], 0.25, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(num_faces(&rounded), 6);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_edges_that_share_a_vertex() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edges = [
+            find_edge(&cuboid, [[0., 0., 0.], [0., 0., 2.]]),
+            find_edge(&cuboid, [[0., 0., 2.], [2., 0., 2.]]),
+        ];
+
+        let rounded = cuboid
+            .fillet(edges, 0.5, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // The original six faces, plus one for each rounded edge. The rounded
+        // faces meet in a mitre, so there's no additional face where they do.
+        assert_eq!(num_faces(&rounded), 8);
+
+        let mesh = (&*rounded, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_edges_around_top_of_cuboid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edges = [[0., 0.], [2., 0.], [2., 2.], [0., 2.], [0., 0.]]
+            .windows(2)
+            .map(|points| {
+                let [[x1, y1], [x2, y2]] = [points[0], points[1]];
+                find_edge(&cuboid, [[x1, y1, 2.], [x2, y2, 2.]])
+            })
+            .collect::<Vec<_>>();
+
+        let rounded = cuboid
+            .fillet(edges, 0.5, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(num_faces(&rounded), 10);
+
+        let mesh = (&*rounded, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_edges_that_meet_at_a_corner() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edges = [
+            find_edge(&cuboid, [[0., 0., 0.], [0., 0., 2.]]),
+            find_edge(&cuboid, [[0., 0., 2.], [2., 0., 2.]]),
+            find_edge(&cuboid, [[0., 0., 2.], [0., 2., 2.]]),
+        ];
+
+        let result = cuboid.fillet(edges, 0.5, tolerance(), &mut services);
+        assert_eq!(result, Err(BlendError::Corner));
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_with_non_positive_radius() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let edge = find_edge(&cuboid, [[0., 0., 0.], [0., 0., 2.]]);
+
+        let result = cuboid.fillet([edge], 0., tolerance(), &mut services);
+        assert_eq!(result, Err(BlendError::NonPositiveSize));
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+}
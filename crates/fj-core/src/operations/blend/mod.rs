@@ -0,0 +1,1327 @@
+//! Blending of the edges of solids
+//!
+//! Blending replaces an edge of a solid with a new face, which connects the two
+//! faces that met at the edge. Those two faces, as well as the faces at the
+//! ends of the edge, are trimmed to make room for the new face.
+//!
+//! Only a limited set of configurations is supported so far:
+//!
+//! - The blended edges must be straight, and so must be the edges next to
+//!   them. The faces that meet at the blended edges must be planar.
+//! - The blended edges must be convex.
+//! - Exactly three faces must meet at each end of a blended edge, and the faces
+//!   at the ends must be perpendicular to the edge.
+//! - At most two blended edges may share a vertex. Where they do, the faces
+//!   that meet there must be perpendicular to each other, and the blends must
+//!   trim the faces that only one of the edges is next to by the same
+//!   distance. The blends then meet in a mitre.
+//!
+//! All blend operations take a tolerance. Faces whose normals deviate by less
+//! than that are considered parallel, and points that are closer to each other
+//! than that are considered identical.
+
+pub mod chamfer;
+pub mod fillet;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_interop::mesh::Color;
+use fj_math::{
+    Circle, Ellipse, Line, NurbsCurve, NurbsSurface, Point, Scalar, Vector,
+};
+
+use crate::{
+    algorithms::approx::Tolerance,
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{
+        Curve, Cycle, Edge, Face, Region, Shell, Solid, Surface, Vertex,
+    },
+    operations::{
+        Insert, UpdateEdge, UpdateFace, UpdateRegion, UpdateShell, UpdateSolid,
+    },
+    services::Services,
+    storage::{Handle, ObjectId},
+};
+
+/// The shape of the face that replaces a blended edge
+#[derive(Clone, Copy, Debug)]
+enum Blend {
+    /// A round face of the given radius, tangent to both adjacent faces
+    Round { radius: Scalar },
+
+    /// A planar face
+    ///
+    /// The distance is measured from the edge, along the first of the adjacent
+    /// faces. If an angle is provided, it's the angle between that face and
+    /// the blend face. Otherwise, the same distance is used for the second of
+    /// the adjacent faces.
+    Straight {
+        distance: Scalar,
+        angle: Option<Scalar>,
+    },
+}
+
+impl Blend {
+    /// Compute how far the adjacent faces are trimmed back from the edge
+    ///
+    /// Expects the normals of the two faces that meet at the edge.
+    fn setbacks(
+        &self,
+        [n1, n2]: [Vector<3>; 2],
+    ) -> Result<[Scalar; 2], BlendError> {
+        match *self {
+            Self::Round { radius } => {
+                // The angle between the normals is the angle that the blend
+                // face turns through.
+                let angle = n1.cross(&n2).magnitude().atan2(n1.dot(&n2));
+                let (sin, cos) = (angle / 2.).sin_cos();
+
+                let setback = radius * sin / cos;
+                Ok([setback, setback])
+            }
+            Self::Straight { distance, angle } => {
+                let Some(angle) = angle else {
+                    return Ok([distance, distance]);
+                };
+
+                // The blend face, together with the adjacent faces, forms a
+                // triangle in cross-section. Its angles are the angle between
+                // the adjacent faces, the provided angle, and the remainder.
+                let between =
+                    Scalar::PI - n1.cross(&n2).magnitude().atan2(n1.dot(&n2));
+                if angle <= Scalar::ZERO || between + angle >= Scalar::PI {
+                    return Err(BlendError::InvalidAngle);
+                }
+
+                let (sin, _) = angle.sin_cos();
+                let (sin_remainder, _) = (between + angle).sin_cos();
+                Ok([distance, distance * sin / sin_remainder])
+            }
+        }
+    }
+
+    /// Compute the cross-section of the blend face at the start of the edge
+    ///
+    /// Expects the points where the cross-section meets the two adjacent faces,
+    /// as well as the normals of those faces. Returns the path of the
+    /// cross-section, which starts at coordinate `0` on the first face, and
+    /// the coordinate where it ends on the second face.
+    fn cross_section(
+        &self,
+        [start, end]: [Point<3>; 2],
+        [n1, n2]: [Vector<3>; 2],
+    ) -> (GlobalPath, Scalar) {
+        match *self {
+            Self::Round { radius } => {
+                let center = start - n1 * radius;
+                let a = n1 * radius;
+                let b = (n2 - n1 * n1.dot(&n2)).normalize() * radius;
+
+                let angle = n1.cross(&n2).magnitude().atan2(n1.dot(&n2));
+
+                (GlobalPath::Circle(Circle::new(center, a, b)), angle)
+            }
+            Self::Straight { .. } => {
+                let line = Line::from_points_with_line_coords([
+                    ([0.], start),
+                    ([1.], end),
+                ]);
+
+                (GlobalPath::Line(line), Scalar::ONE)
+            }
+        }
+    }
+}
+
+/// An error that can occur while blending edges
+///
+/// Returned by [`Fillet::fillet`] and [`Chamfer::chamfer`].
+///
+/// [`Fillet::fillet`]: crate::operations::Fillet::fillet
+/// [`Chamfer::chamfer`]: crate::operations::Chamfer::chamfer
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum BlendError {
+    /// The radius or distance of the blend is not positive
+    #[error("Size of blend must be positive")]
+    NonPositiveSize,
+
+    /// The angle of the blend is out of range
+    #[error(
+        "Angle must be positive, and not exceed the angle that the faces \
+        leave open"
+    )]
+    InvalidAngle,
+
+    /// A blended edge is not part of the solid
+    #[error("Blended edge must be part of the solid")]
+    EdgeNotInSolid,
+
+    /// A face next to a blended edge is not planar
+    #[error("Blending edges of faces in round surfaces is not supported")]
+    CurvedFace,
+
+    /// A blended edge, or an edge next to it, is curved
+    #[error("Blending curved edges, or edges next to them, is not supported")]
+    CurvedEdge,
+
+    /// A blended edge is concave
+    #[error("Blending concave edges is not supported")]
+    ConcaveEdge,
+
+    /// The blend would consume an edge next to the blended edge, or the
+    /// blended edge itself
+    #[error("Blend must not consume the edges next to the blended edge")]
+    EdgeConsumed,
+
+    /// A face at the end of a blended edge is not perpendicular to it
+    #[error(
+        "Blending edges whose end faces are not perpendicular to them is not \
+        supported"
+    )]
+    EndFaceNotPerpendicular,
+
+    /// Not exactly three faces meet at the end of a blended edge
+    #[error("Blending edges is only supported where exactly three faces meet")]
+    NotThreeFaces,
+
+    /// More than two blended edges share a vertex
+    #[error(
+        "Blending more than two edges that share a vertex is not supported"
+    )]
+    Corner,
+
+    /// Blended edges share a vertex, where faces meet at other angles
+    #[error(
+        "Blending edges that share a vertex is only supported, where the faces \
+        that meet there are perpendicular to each other"
+    )]
+    UnsupportedCorner,
+
+    /// Blended edges that share a vertex don't meet on the edge between them
+    #[error(
+        "Blending edges that share a vertex is only supported, if they trim \
+        the faces that only one of them is next to by the same distance"
+    )]
+    MismatchedSetbacks,
+}
+
+/// Blend the provided edges of a solid
+fn apply(
+    solid: &Solid,
+    edges: impl IntoIterator<Item = Handle<Edge>>,
+    blend: Blend,
+    tolerance: impl Into<Tolerance>,
+    services: &mut Services,
+) -> Result<Solid, BlendError> {
+    let tolerance = tolerance.into().inner();
+    let mut keys = Vec::new();
+    let mut curves = BTreeSet::new();
+    let mut keys_by_vertex = BTreeMap::new();
+
+    for edge in edges {
+        // Both half-edges of an edge refer to the same curve. Blending either
+        // of them blends the edge.
+        if !curves.insert(edge.curve().id()) {
+            continue;
+        }
+
+        let half_edge = solid
+            .shells()
+            .iter()
+            .find_map(|shell| {
+                HalfEdge::find(shell, |other| key(other) == key(&edge))
+            })
+            .ok_or(BlendError::EdgeNotInSolid)?;
+
+        for vertex in [edge.start_vertex(), half_edge.next.start_vertex()] {
+            let shared: &mut Vec<_> =
+                keys_by_vertex.entry(vertex.id()).or_default();
+            shared.push(key(&edge));
+
+            if shared.len() > 2 {
+                return Err(BlendError::Corner);
+            }
+        }
+
+        keys.push(key(&edge));
+    }
+
+    // Where two blended edges share a vertex, the blend of the first one ends
+    // in a mitre face there. The blend of the second one replaces that face.
+    let mut mitres = BTreeSet::new();
+    let mut solid = solid.clone();
+
+    for key in keys {
+        let shell = solid
+            .shells()
+            .iter()
+            .find(|shell| {
+                HalfEdge::find(shell, |edge| self::key(edge) == key).is_some()
+            })
+            .expect("Blended edge must be part of the solid")
+            .clone();
+
+        let blended = blend_edge(
+            &shell,
+            key,
+            blend,
+            &keys_by_vertex,
+            &mut mitres,
+            tolerance,
+            services,
+        )?;
+        solid = solid.update_shell(&shell, |_| blended.insert(services));
+    }
+
+    Ok(solid)
+}
+
+/// Identify an edge by its curve and its start vertex
+///
+/// Blending an edge rebuilds the faces around it, which replaces the handles of
+/// edges that are not blended themselves. Curves and vertices of those edges
+/// stay the same though.
+fn key(edge: &Edge) -> (ObjectId, ObjectId) {
+    (edge.curve().id(), edge.start_vertex().id())
+}
+
+/// Blend a single edge of a shell
+///
+/// `keys_by_vertex` contains the keys of all blended edges, by the IDs of their
+/// vertices. `mitres` contains the IDs of the vertices, where the blend of
+/// another edge ended in a mitre face.
+fn blend_edge(
+    shell: &Shell,
+    key: (ObjectId, ObjectId),
+    blend: Blend,
+    keys_by_vertex: &BTreeMap<ObjectId, Vec<(ObjectId, ObjectId)>>,
+    mitres: &mut BTreeSet<ObjectId>,
+    tolerance: Scalar,
+    services: &mut Services,
+) -> Result<Shell, BlendError> {
+    // The edge runs from `a` to `b` in the first face, and the other way
+    // around in the second one.
+    let h1 = HalfEdge::find(shell, |edge| self::key(edge) == key)
+        .expect("Blended edge must be part of the shell");
+    let h2 = h1.twin(shell);
+
+    let [a, b] = [&h1, &h2].map(|half_edge| half_edge.start());
+    let [n1, n2] = [h1.normal()?, h2.normal()?];
+    let direction = (b - a).normalize();
+
+    if !matches!(h1.edge.path(), SurfacePath::Line(_)) {
+        return Err(BlendError::CurvedEdge);
+    }
+    if n1.cross(&direction).dot(&n2) >= Scalar::ZERO {
+        return Err(BlendError::ConcaveEdge);
+    }
+
+    let [s1, s2] = blend.setbacks([n1, n2])?;
+
+    // A round blend face that ends in a mitre can't be defined in a swept
+    // surface, as the mitre is not a straight line in its coordinates. It is
+    // defined in a ruled surface between the curves at its ends instead.
+    let meets_end_face = |half_edge: &HalfEdge| {
+        let vertex = half_edge.edge.start_vertex().id();
+        !mitres.contains(&vertex) && keys_by_vertex[&vertex].len() == 1
+    };
+    let angle = n1.cross(&n2).magnitude().atan2(n1.dot(&n2));
+    let (is_ruled, end) = match blend {
+        Blend::Round { .. } if meets_end_face(&h1) && meets_end_face(&h2) => {
+            (false, angle)
+        }
+        Blend::Round { .. } => (true, Scalar::ONE),
+        Blend::Straight { .. } => (false, Scalar::ONE),
+    };
+
+    // Each end is described from the perspective of the half-edge that starts
+    // there, and the one that ends there.
+    let context = Context {
+        shell,
+        key,
+        blend,
+        keys_by_vertex,
+        mitres,
+        tolerance,
+    };
+    let [start, finish] = [[0.], [end.into_f64()]].map(Point::from);
+    let at_a =
+        End::new(&h1, &h2, [s1, s2], [start, finish], &context, services)?;
+    let at_b =
+        End::new(&h2, &h1, [s2, s1], [finish, start], &context, services)?;
+
+    let [p1a, p2a] = [0, 1].map(|i| at_a.corners[i].point);
+    let [p2b, p1b] = [0, 1].map(|i| at_b.corners[i].point);
+
+    for [start, end] in [[p1a, p1b], [p2a, p2b]] {
+        if (end - start).dot(&direction) <= tolerance {
+            return Err(BlendError::EdgeConsumed);
+        }
+    }
+
+    // The cross-sections of the blend face at the ends of the edge. Their
+    // coordinates start at `0` on the first face.
+    let weight = (angle / 2.).cos();
+    let section = |at: &End, [start, end]: [Point<3>; 2]| {
+        if is_ruled {
+            // A rational quadratic arc, whose control points are the points
+            // where it meets the faces, and the point where their tangents
+            // meet. A mitre shears the arc along the edge, which moves its
+            // ends but not that point.
+            let arc = NurbsCurve::new(
+                2,
+                [start, at.point, end],
+                [Scalar::ONE, weight, Scalar::ONE],
+                [0., 0., 0., 1., 1., 1.],
+            );
+            GlobalPath::Nurbs(arc.into())
+        } else {
+            blend.cross_section([start, end], [n1, n2]).0
+        }
+    };
+    let path_a = section(&at_a, [p1a, p2a]);
+    let path_b = section(&at_b, [p1b, p2b]);
+
+    let [line1, line2] = [(); 2].map(|()| Curve::new().insert(services));
+
+    // If the blend face is defined in a swept surface, that surface sweeps the
+    // cross-section at `a` along the edge. Its u-coordinates are those of the
+    // cross-section, its v-coordinates go from `0` at `a` to `1` at `b`. Where
+    // the blend face ends in a mitre, its corners are moved along the edge.
+    //
+    // If it is defined in a ruled surface, that surface connects the
+    // cross-sections at both ends. Its coordinates go from `0` to `1` along
+    // both.
+    let blend_face = {
+        let (surface, [q1a, q2a, q2b, q1b]) = if is_ruled {
+            let surface = NurbsSurface::new(
+                [2, 1],
+                [[p1a, p1b], [a, b], [p2a, p2b]],
+                [
+                    [Scalar::ONE, Scalar::ONE],
+                    [weight, weight],
+                    [Scalar::ONE, Scalar::ONE],
+                ],
+                [vec![0., 0., 0., 1., 1., 1.], vec![0., 0., 1., 1.]]
+                    .map(|knots| knots.into_iter().map(Scalar::from).collect()),
+            );
+
+            (
+                SurfaceGeometry::Nurbs(surface.into()),
+                [[0., 0.], [1., 0.], [1., 1.], [0., 1.]].map(Point::from),
+            )
+        } else {
+            let v = |offset: Vector<3>| {
+                offset.dot(&(b - a)) / (b - a).dot(&(b - a))
+            };
+
+            (
+                SurfaceGeometry::Swept {
+                    u: path_a,
+                    v: b - a,
+                },
+                [
+                    [Scalar::ZERO, Scalar::ZERO],
+                    [end, Scalar::ZERO],
+                    [end, v(p2b - p2a)],
+                    [Scalar::ZERO, v(p1b - p1a)],
+                ]
+                .map(Point::from),
+            )
+        };
+
+        let edges = [
+            at_a.blend_edge([q1a, q2a]),
+            Edge::new(
+                SurfacePath::line_from_points_with_coords([
+                    ([0.], q2a),
+                    ([1.], q2b),
+                ]),
+                [[0.], [1.]],
+                line2.clone(),
+                at_a.corners[1].vertex.clone(),
+            ),
+            at_b.blend_edge([q2b, q1b]),
+            Edge::new(
+                SurfacePath::line_from_points_with_coords([
+                    ([0.], q1a),
+                    ([1.], q1b),
+                ]),
+                [[1.], [0.]],
+                line1.clone(),
+                at_b.corners[1].vertex.clone(),
+            ),
+        ]
+        .map(|edge| edge.insert(services));
+
+        let region = Region::new(
+            Cycle::new(edges).insert(services),
+            [],
+            h1.face.region().color(),
+        )
+        .insert(services);
+
+        Face::new(Surface::new(surface).insert(services), region)
+            .insert(services)
+    };
+
+    let face1 = {
+        let edge = Edge::new(
+            h1.line_through([p1a, p1b]),
+            [[0.], [1.]],
+            line1,
+            at_a.corners[0].vertex.clone(),
+        )
+        .insert(services);
+
+        h1.replace(
+            [
+                at_a.update_prev(&h1, services)?,
+                vec![edge],
+                at_b.update_next(&h1, services)?,
+            ],
+            services,
+        )
+    };
+    let face2 = {
+        let edge = Edge::new(
+            h2.line_through([p2a, p2b]),
+            [[1.], [0.]],
+            line2,
+            at_b.corners[0].vertex.clone(),
+        )
+        .insert(services);
+
+        h2.replace(
+            [
+                at_b.update_prev(&h2, services)?,
+                vec![edge],
+                at_a.update_next(&h2, services)?,
+            ],
+            services,
+        )
+    };
+
+    let mut shell = shell
+        .replace_face(&h1.face, |_| [face1.insert(services), blend_face])
+        .update_face(&h2.face, |_| face2.insert(services));
+
+    // The faces at the ends of the edge are located through the edges that
+    // lead away from the blended edge, which connect them to the first face.
+    // They might turn out to be the same face, so each of them needs to be
+    // located after the other one has been updated.
+    let color = h1.face.region().color();
+    for (at, [first, second], path) in
+        [(&at_a, [&h1, &h2], path_a), (&at_b, [&h2, &h1], path_b)]
+    {
+        shell =
+            at.update_end_face(shell, [first, second], path, color, services)?;
+
+        match at.kind {
+            EndKind::Face => {}
+            EndKind::Mitre { .. } => {
+                mitres.insert(at.vertex.id());
+            }
+            EndKind::Close => {
+                mitres.remove(&at.vertex.id());
+            }
+        }
+    }
+
+    Ok(shell)
+}
+
+/// The state that the ends of a blended edge are computed from
+struct Context<'r> {
+    shell: &'r Shell,
+    key: (ObjectId, ObjectId),
+    blend: Blend,
+    keys_by_vertex: &'r BTreeMap<ObjectId, Vec<(ObjectId, ObjectId)>>,
+    mitres: &'r BTreeSet<ObjectId>,
+    tolerance: Scalar,
+}
+
+/// One of the ends of a blended edge
+///
+/// An end is described from the perspective of the two half-edges of the
+/// blended edge: The first one starts at the end, the second one ends there.
+struct End {
+    /// What the blend face meets at this end
+    kind: EndKind,
+
+    /// The vertex at this end of the blended edge
+    vertex: Handle<Vertex>,
+
+    /// The position of that vertex
+    point: Point<3>,
+
+    /// The corners of the blend face at this end
+    ///
+    /// The first corner is located in the face of the first half-edge, the
+    /// second one in the face of the second half-edge.
+    corners: [Corner; 2],
+
+    /// The curve of the edge of the blend face at this end
+    curve: Handle<Curve>,
+}
+
+/// A corner of the blend face
+struct Corner {
+    point: Point<3>,
+    vertex: Handle<Vertex>,
+
+    /// The coordinate of the corner on the curve at the end of the blend face
+    coord: Point<1>,
+}
+
+/// What the blend face meets at one of the ends of the blended edge
+enum EndKind {
+    /// The face at the end of the edge, which is trimmed to make room
+    Face,
+
+    /// A mitre face, where the blend of another edge is going to end too
+    ///
+    /// The mitre face is located in the plane, in which both blend faces
+    /// intersect. It fills the gap between the blend face and the unblended
+    /// other edge, until the other edge is blended.
+    ///
+    /// `common` is the index of the corner that is located in the face, which
+    /// the other edge is next to too. `line` is the curve of the edge between
+    /// that corner and the vertex.
+    Mitre { common: usize, line: Handle<Curve> },
+
+    /// The mitre face that the blend of another edge ended in
+    ///
+    /// The blend face replaces it, sharing the curve at its end with the blend
+    /// face of the other edge.
+    Close,
+}
+
+impl End {
+    /// Compute the end, where the first half-edge starts
+    ///
+    /// Expects the distances by which the faces of both half-edges are trimmed,
+    /// and the coordinates of the corners on the curve at the end of the blend
+    /// face, if it's a new one.
+    fn new(
+        first: &HalfEdge,
+        second: &HalfEdge,
+        setbacks: [Scalar; 2],
+        coords: [Point<1>; 2],
+        context: &Context,
+        services: &mut Services,
+    ) -> Result<Self, BlendError> {
+        let vertex = first.edge.start_vertex().clone();
+        let point = first.start();
+        let far = [first.start_of(&first.prev), second.end_of(&second.next)];
+
+        if context.mitres.contains(&vertex.id()) {
+            // The edges next to the blended edge are shared with the mitre
+            // face. Its edge that connects them is the one that the blend face
+            // is going to share.
+            let mitre =
+                HalfEdge::find_end(context.shell, &first.prev, &first.edge);
+            let boundary = mitre.next.boundary().inner;
+
+            return Ok(Self {
+                kind: EndKind::Close,
+                vertex,
+                point,
+                corners: [
+                    Corner {
+                        point: far[0],
+                        vertex: first.prev.start_vertex().clone(),
+                        coord: boundary[0],
+                    },
+                    Corner {
+                        point: far[1],
+                        vertex: mitre.prev.start_vertex().clone(),
+                        coord: boundary[1],
+                    },
+                ],
+                curve: mitre.next.curve().clone(),
+            });
+        }
+
+        for edge in [&first.prev, &second.next] {
+            if !matches!(edge.path(), SurfacePath::Line(_)) {
+                return Err(BlendError::CurvedEdge);
+            }
+        }
+        for (far, setback) in far.into_iter().zip(setbacks) {
+            if (far - point).magnitude() - setback <= context.tolerance {
+                return Err(BlendError::EdgeConsumed);
+            }
+        }
+
+        let into = (first.end_of(&first.edge) - point).normalize();
+
+        let end_face =
+            HalfEdge::find_end(context.shell, &first.prev, &first.edge);
+        if end_face.normal()?.cross(&into).magnitude() > context.tolerance {
+            return Err(BlendError::EndFaceNotPerpendicular);
+        }
+        if end_face.prev.curve().id() != second.next.curve().id() {
+            return Err(BlendError::NotThreeFaces);
+        }
+
+        let mut points =
+            [0, 1].map(|i| point + (far[i] - point).normalize() * setbacks[i]);
+
+        let other = context.keys_by_vertex[&vertex.id()]
+            .iter()
+            .find(|other| **other != context.key);
+        let kind = if let Some(other) = other {
+            let common = if first.prev.curve().id() == other.0 {
+                0
+            } else if second.next.curve().id() == other.0 {
+                1
+            } else {
+                return Err(BlendError::NotThreeFaces);
+            };
+
+            if first.normal()?.dot(&second.normal()?).abs() > context.tolerance
+            {
+                return Err(BlendError::UnsupportedCorner);
+            }
+
+            // Both blends meet the edge that is not blended at the same point,
+            // if they trim the faces next to it by the same distance.
+            let common_face = [first, second][common].face.id();
+            let [on_common, on_other] = {
+                let h1 =
+                    HalfEdge::find(context.shell, |edge| key(edge) == *other)
+                        .expect("Blended edge must be part of the shell");
+                let h2 = h1.twin(context.shell);
+
+                let [s1, s2] =
+                    context.blend.setbacks([h1.normal()?, h2.normal()?])?;
+                if h1.face.id() == common_face {
+                    [s1, s2]
+                } else {
+                    [s2, s1]
+                }
+            };
+            if (on_other - setbacks[1 - common]).abs() > context.tolerance {
+                return Err(BlendError::MismatchedSetbacks);
+            }
+
+            // In the face that both edges are next to, the blends meet where
+            // the lines, along which they meet that face, intersect.
+            points[common] += into * on_common;
+
+            EndKind::Mitre {
+                common,
+                line: Curve::new().insert(services),
+            }
+        } else {
+            EndKind::Face
+        };
+
+        let corners = [0, 1].map(|i| Corner {
+            point: points[i],
+            vertex: Vertex::new().insert(services),
+            coord: coords[i],
+        });
+
+        Ok(Self {
+            kind,
+            vertex,
+            point,
+            corners,
+            curve: Curve::new().insert(services),
+        })
+    }
+
+    /// Build the edge of the blend face at this end
+    ///
+    /// Expects the surface coordinates of the corners.
+    fn blend_edge(&self, points: [Point<2>; 2]) -> Edge {
+        let [a, b] = &self.corners;
+        let [p, q] = points;
+
+        Edge::new(
+            SurfacePath::line_from_points_with_coords([
+                (a.coord, p),
+                (b.coord, q),
+            ]),
+            [a.coord, b.coord],
+            self.curve.clone(),
+            a.vertex.clone(),
+        )
+    }
+
+    /// Replace the edge that leads to this end, in the face of the first
+    /// half-edge
+    fn update_prev(
+        &self,
+        first: &HalfEdge,
+        services: &mut Services,
+    ) -> Result<Vec<Handle<Edge>>, BlendError> {
+        let prev = &first.prev;
+
+        let edges = match &self.kind {
+            EndKind::Face | EndKind::Mitre { common: 1, .. } => {
+                let coord = first.coord_of(prev, self.corners[0].point)?;
+                vec![trim_end(prev, coord).insert(services)]
+            }
+            EndKind::Mitre { line, .. } => {
+                let edge = Edge::new(
+                    first.line_through([self.point, self.corners[0].point]),
+                    [[0.], [1.]],
+                    line.clone(),
+                    self.vertex.clone(),
+                )
+                .insert(services);
+
+                vec![prev.clone(), edge]
+            }
+            EndKind::Close => Vec::new(),
+        };
+
+        Ok(edges)
+    }
+
+    /// Replace the edge that leads away from this end, in the face of the
+    /// second half-edge
+    fn update_next(
+        &self,
+        second: &HalfEdge,
+        services: &mut Services,
+    ) -> Result<Vec<Handle<Edge>>, BlendError> {
+        let next = &second.next;
+
+        let edges = match &self.kind {
+            EndKind::Face | EndKind::Mitre { common: 0, .. } => {
+                let corner = &self.corners[1];
+                let coord = second.coord_of(next, corner.point)?;
+                vec![trim_start(next, coord, &corner.vertex).insert(services)]
+            }
+            EndKind::Mitre { line, .. } => {
+                let edge = Edge::new(
+                    second.line_through([self.point, self.corners[1].point]),
+                    [[1.], [0.]],
+                    line.clone(),
+                    self.corners[1].vertex.clone(),
+                )
+                .insert(services);
+
+                vec![edge, next.clone()]
+            }
+            EndKind::Close => Vec::new(),
+        };
+
+        Ok(edges)
+    }
+
+    /// Update the face at this end of the blended edge
+    ///
+    /// Expects the half-edges of the blended edge, as they were before the
+    /// blend, and the path of the cross-section of the blend face at this end.
+    fn update_end_face(
+        &self,
+        mut shell: Shell,
+        [first, second]: [&HalfEdge; 2],
+        path: GlobalPath,
+        color: Option<Color>,
+        services: &mut Services,
+    ) -> Result<Shell, BlendError> {
+        let mut end_face = HalfEdge::find_end(&shell, &first.prev, &first.edge);
+
+        let [c_first, c_second] = match self.kind {
+            EndKind::Close => return Ok(shell.remove_face(&end_face.face)),
+            _ => [
+                first.coord_of(&first.prev, self.corners[0].point),
+                second.coord_of(&second.next, self.corners[1].point),
+            ],
+        };
+
+        let (face, mitre_face) = match &self.kind {
+            EndKind::Face => {
+                // Circles can only be represented in orthonormal surfaces.
+                if matches!(path, GlobalPath::Circle(_)) {
+                    let face = orthonormalize(&end_face.face, services)?;
+                    shell = shell
+                        .update_face(&end_face.face, |_| face.insert(services));
+                    end_face =
+                        HalfEdge::find_end(&shell, &first.prev, &first.edge);
+                }
+
+                let [c_first, c_second] = [c_first?, c_second?];
+                let arc = Edge::new(
+                    end_face.path_of(path),
+                    [self.corners[1].coord, self.corners[0].coord],
+                    self.curve.clone(),
+                    self.corners[1].vertex.clone(),
+                )
+                .insert(services);
+
+                let face = end_face.replace(
+                    [
+                        vec![
+                            trim_end(&end_face.prev, c_second).insert(services)
+                        ],
+                        vec![
+                            arc,
+                            trim_start(
+                                &end_face.edge,
+                                c_first,
+                                &self.corners[0].vertex,
+                            )
+                            .insert(services),
+                        ],
+                        vec![end_face.next.clone()],
+                    ],
+                    services,
+                );
+
+                (face, None)
+            }
+            EndKind::Mitre { common: 0, line } => {
+                // The edge that is not blended leads to the end, in the end
+                // face. It's split where the mitre face starts.
+                let prev = &end_face.prev;
+                let c_second = c_second?;
+                let end = prev.boundary().inner[1];
+
+                let face = end_face.replace(
+                    [
+                        vec![
+                            trim_end(prev, c_second).insert(services),
+                            trim_start(prev, c_second, &self.corners[1].vertex)
+                                .insert(services),
+                        ],
+                        vec![end_face.edge.clone()],
+                        vec![end_face.next.clone()],
+                    ],
+                    services,
+                );
+                let mitre_face = self.mitre_face(
+                    [
+                        (line.clone(), [[1.], [0.]].map(Point::from)),
+                        (prev.curve().clone(), [end, c_second]),
+                    ],
+                    path,
+                    color,
+                    services,
+                );
+
+                (face, Some(mitre_face))
+            }
+            EndKind::Mitre { line, .. } => {
+                // The edge that is not blended leads away from the end, in the
+                // end face. It's split where the mitre face ends.
+                let edge = &end_face.edge;
+                let c_first = c_first?;
+                let start = edge.boundary().inner[0];
+
+                let face = end_face.replace(
+                    [
+                        vec![end_face.prev.clone()],
+                        vec![
+                            trim_end(edge, c_first).insert(services),
+                            trim_start(edge, c_first, &self.corners[0].vertex)
+                                .insert(services),
+                        ],
+                        vec![end_face.next.clone()],
+                    ],
+                    services,
+                );
+                let mitre_face = self.mitre_face(
+                    [
+                        (edge.curve().clone(), [c_first, start]),
+                        (line.clone(), [[0.], [1.]].map(Point::from)),
+                    ],
+                    path,
+                    color,
+                    services,
+                );
+
+                (face, Some(mitre_face))
+            }
+            EndKind::Close => unreachable!("Handled above"),
+        };
+
+        let shell =
+            shell.update_face(&end_face.face, |_| face.insert(services));
+        Ok(shell.add_faces(mitre_face))
+    }
+
+    /// Build the mitre face at this end
+    ///
+    /// The mitre face is a triangle between the corners of the blend face and
+    /// the vertex, with the cross-section of the blend face as one of its
+    /// sides. Expects the curves and boundaries of its other sides: The one
+    /// from the first corner to the vertex, and the one from the vertex to the
+    /// second corner.
+    fn mitre_face(
+        &self,
+        sides: [(Handle<Curve>, [Point<1>; 2]); 2],
+        path: GlobalPath,
+        color: Option<Color>,
+        services: &mut Services,
+    ) -> Handle<Face> {
+        let [first, second] = &self.corners;
+
+        let surface = SurfaceGeometry::Swept {
+            u: GlobalPath::Line(Line::from_origin_and_direction(
+                self.point,
+                second.point - self.point,
+            )),
+            v: first.point - self.point,
+        };
+        let [vertex, p_first, p_second] =
+            [[0., 0.], [0., 1.], [1., 0.]].map(Point::from);
+
+        let [(curve_first, boundary_first), (curve_second, boundary_second)] =
+            sides;
+        let edges = [
+            Edge::new(
+                SurfacePath::line_from_points_with_coords([
+                    (boundary_first[0], p_first),
+                    (boundary_first[1], vertex),
+                ]),
+                boundary_first,
+                curve_first,
+                first.vertex.clone(),
+            ),
+            Edge::new(
+                SurfacePath::line_from_points_with_coords([
+                    (boundary_second[0], vertex),
+                    (boundary_second[1], p_second),
+                ]),
+                boundary_second,
+                curve_second,
+                self.vertex.clone(),
+            ),
+            Edge::new(
+                path_in(&surface, path),
+                [second.coord, first.coord],
+                self.curve.clone(),
+                second.vertex.clone(),
+            ),
+        ]
+        .map(|edge| edge.insert(services));
+
+        let region = Region::new(Cycle::new(edges).insert(services), [], color)
+            .insert(services);
+
+        Face::new(Surface::new(surface).insert(services), region)
+            .insert(services)
+    }
+}
+
+/// An edge, together with the face it is part of and its neighbors
+struct HalfEdge {
+    face: Handle<Face>,
+    cycle: Handle<Cycle>,
+    prev: Handle<Edge>,
+    edge: Handle<Edge>,
+    next: Handle<Edge>,
+}
+
+impl HalfEdge {
+    /// Find the first edge in the shell that matches the predicate
+    fn find(shell: &Shell, predicate: impl Fn(&Edge) -> bool) -> Option<Self> {
+        for face in shell.faces() {
+            for cycle in face.region().all_cycles() {
+                let edges = cycle.edges();
+
+                for (i, edge) in edges.iter().enumerate() {
+                    if !predicate(edge) {
+                        continue;
+                    }
+
+                    return Some(Self {
+                        face: face.clone(),
+                        cycle: cycle.clone(),
+                        prev: edges.nth_circular(i + edges.len() - 1).clone(),
+                        edge: edge.clone(),
+                        next: edges.nth_circular(i + 1).clone(),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Find the other half of the edge
+    fn twin(&self, shell: &Shell) -> Self {
+        Self::find(shell, |other| {
+            other.curve().id() == self.edge.curve().id()
+                && other.start_vertex().id() == self.next.start_vertex().id()
+        })
+        .expect("Edges of a shell must be shared by two faces")
+    }
+
+    /// Find the edge that leads away from the end of a blended edge
+    ///
+    /// Expects an edge that leads to the blended edge, and the blended edge
+    /// itself. The edge that is returned is the other half of the former.
+    fn find_end(shell: &Shell, to_edge: &Edge, edge: &Edge) -> Self {
+        Self::find(shell, |other| {
+            other.curve().id() == to_edge.curve().id()
+                && other.start_vertex().id() == edge.start_vertex().id()
+        })
+        .expect("Edges next to blended edge must be shared by two faces")
+    }
+
+    /// Compute the normal of the face
+    fn normal(&self) -> Result<Vector<3>, BlendError> {
+        self.face.normal().ok_or(BlendError::CurvedFace)
+    }
+
+    /// Compute the position of the start of the edge
+    fn start(&self) -> Point<3> {
+        self.start_of(&self.edge)
+    }
+
+    /// Compute the position of the start of an edge in the same face
+    fn start_of(&self, edge: &Edge) -> Point<3> {
+        self.position(edge, edge.boundary().inner[0])
+    }
+
+    /// Compute the position of the end of an edge in the same face
+    fn end_of(&self, edge: &Edge) -> Point<3> {
+        self.position(edge, edge.boundary().inner[1])
+    }
+
+    fn position(&self, edge: &Edge, point: Point<1>) -> Point<3> {
+        let point = edge.path().point_from_path_coords(point);
+        self.face
+            .surface()
+            .geometry()
+            .point_from_surface_coords(point)
+    }
+
+    /// Compute the curve coordinate of a point on an edge in the same face
+    fn coord_of(
+        &self,
+        edge: &Edge,
+        point: Point<3>,
+    ) -> Result<Point<1>, BlendError> {
+        let SurfacePath::Line(line) = edge.path() else {
+            return Err(BlendError::CurvedEdge);
+        };
+
+        let point = self.face.surface().geometry().project_global_point(point);
+        Ok(line.point_to_line_coords(point))
+    }
+
+    /// Create a line through two points on the face
+    ///
+    /// The line has coordinate `0` at the first point, and `1` at the second.
+    fn line_through(&self, points: [Point<3>; 2]) -> SurfacePath {
+        let surface = self.face.surface().geometry();
+        let [a, b] = points.map(|point| surface.project_global_point(point));
+
+        SurfacePath::line_from_points_with_coords([([0.], a), ([1.], b)])
+    }
+
+    /// Represent a path in the surface of the face
+    ///
+    /// See [`path_in`].
+    fn path_of(&self, path: GlobalPath) -> SurfacePath {
+        path_in(&self.face.surface().geometry(), path)
+    }
+
+    /// Replace the edge and its neighbors, returning the updated face
+    ///
+    /// Expects the edges that replace the previous edge, the edge itself, and
+    /// the next edge, in that order.
+    fn replace(
+        &self,
+        [prev, edge, next]: [Vec<Handle<Edge>>; 3],
+        services: &mut Services,
+    ) -> Face {
+        let mut replacements = [
+            (self.prev.id(), prev),
+            (self.edge.id(), edge),
+            (self.next.id(), next),
+        ];
+
+        self.update_cycle(
+            |cycle, services| {
+                let edges = cycle.edges().iter().flat_map(|edge| {
+                    replacements
+                        .iter_mut()
+                        .find(|(id, _)| *id == edge.id())
+                        .map(|(_, replacement)| std::mem::take(replacement))
+                        .unwrap_or_else(|| vec![edge.clone()])
+                });
+
+                Cycle::new(edges.collect::<Vec<_>>()).insert(services)
+            },
+            services,
+        )
+    }
+
+    /// Update the cycle that the edge is part of, returning the updated face
+    fn update_cycle(
+        &self,
+        update: impl FnOnce(&Handle<Cycle>, &mut Services) -> Handle<Cycle>,
+        services: &mut Services,
+    ) -> Face {
+        self.face.update_region(|region| {
+            let update_cycle = |cycle: &Handle<Cycle>| update(cycle, services);
+
+            let region = if region.exterior().id() == self.cycle.id() {
+                region.update_exterior(update_cycle)
+            } else {
+                region.update_interior(&self.cycle, update_cycle)
+            };
+
+            region.insert(services)
+        })
+    }
+}
+
+/// Represent a path in a surface
+///
+/// The path must lie within the surface. Circles can only be represented in
+/// orthonormal surfaces.
+fn path_in(surface: &SurfaceGeometry, path: GlobalPath) -> SurfacePath {
+    match path {
+        GlobalPath::Circle(circle) => {
+            let center = surface.project_global_point(circle.center());
+            let [a, b] = [circle.a(), circle.b()].map(|vector| {
+                surface.project_global_point(circle.center() + vector) - center
+            });
+
+            SurfacePath::Circle(Circle::new(center, a, b))
+        }
+        GlobalPath::Ellipse(ellipse) => {
+            let center = surface.project_global_point(ellipse.center());
+            let [a, b] = [ellipse.a(), ellipse.b()].map(|vector| {
+                surface.project_global_point(ellipse.center() + vector) - center
+            });
+
+            SurfacePath::Ellipse(Ellipse::new(center, a, b))
+        }
+        GlobalPath::Helix(_) => {
+            unreachable!("Cross-sections of blends are never helices")
+        }
+        GlobalPath::Nurbs(curve) => SurfacePath::Nurbs(
+            curve
+                .map_control_points(|point| surface.project_global_point(point))
+                .into(),
+        ),
+        GlobalPath::Line(line) => {
+            let [a, b] = [0., 1.].map(|t| {
+                let point = line.point_from_line_coords([t]);
+                ([t], surface.project_global_point(point))
+            });
+
+            SurfacePath::line_from_points_with_coords([a, b])
+        }
+    }
+}
+
+/// Move the end of an edge to a new curve coordinate
+fn trim_end(edge: &Edge, end: Point<1>) -> Edge {
+    edge.update_boundary(|boundary| [boundary.inner[0], end].into())
+}
+
+/// Move the start of an edge to a new curve coordinate and vertex
+fn trim_start(edge: &Edge, start: Point<1>, vertex: &Handle<Vertex>) -> Edge {
+    edge.update_boundary(|boundary| [start, boundary.inner[1]].into())
+        .update_start_vertex(|_| vertex.clone())
+}
+
+/// Convert a planar face to an orthonormal coordinate system
+///
+/// Returns a face with the same orientation, curves, and vertices, whose edges
+/// are defined in the new coordinate system.
+///
+/// Returns an error, if the face is not planar.
+fn orthonormalize(
+    face: &Face,
+    services: &mut Services,
+) -> Result<Face, BlendError> {
+    // Same tolerance as for circles. See `Circle::new`.
+    let epsilon = Scalar::from(f64::EPSILON * 16.);
+
+    let surface = face.surface().geometry();
+    let SurfaceGeometry::Swept {
+        u: GlobalPath::Line(line),
+        v,
+    } = surface
+    else {
+        return Err(BlendError::CurvedFace);
+    };
+
+    let [u, v] = [line.direction(), v];
+    if (u.magnitude() - 1.).abs() <= epsilon
+        && (v.magnitude() - 1.).abs() <= epsilon
+        && u.dot(&v).abs() <= epsilon
+    {
+        return Ok(face.clone());
+    }
+
+    // Orthogonalizing `v` against `u` preserves the handedness of the
+    // coordinate system. The cycles of the face keep their winding.
+    let u = u.normalize();
+    let v = (v - u * u.dot(&v)).normalize();
+    let orthonormal = SurfaceGeometry::Swept {
+        u: GlobalPath::Line(Line::from_origin_and_direction(line.origin(), u)),
+        v,
+    };
+
+    let convert = |point: Point<2>| {
+        orthonormal
+            .project_global_point(surface.point_from_surface_coords(point))
+    };
+
+    let cycles = face.region().all_cycles().map(|cycle| {
+        let edges = cycle.edges().iter().map(|edge| {
+            edge.update_path(|path| match path {
+                SurfacePath::Circle(circle) => {
+                    let center = convert(circle.center());
+                    let [a, b] = [circle.a(), circle.b()].map(|vector| {
+                        convert(circle.center() + vector) - center
+                    });
+
+                    SurfacePath::Circle(Circle::new(center, a, b))
+                }
+                SurfacePath::Ellipse(ellipse) => {
+                    let center = convert(ellipse.center());
+                    let [a, b] = [ellipse.a(), ellipse.b()].map(|vector| {
+                        convert(ellipse.center() + vector) - center
+                    });
+
+                    SurfacePath::Ellipse(Ellipse::new(center, a, b))
+                }
+                SurfacePath::Nurbs(curve) => {
+                    SurfacePath::Nurbs(curve.map_control_points(convert).into())
+                }
+                SurfacePath::Line(line) => {
+                    let [a, b] = [0., 1.].map(|t| {
+                        ([t], convert(line.point_from_line_coords([t])))
+                    });
+
+                    SurfacePath::line_from_points_with_coords([a, b])
+                }
+            })
+            .insert(services)
+        });
+
+        Cycle::new(edges.collect::<Vec<_>>()).insert(services)
+    });
+    let cycles = cycles.collect::<Vec<_>>();
+
+    let [exterior, interiors @ ..] = cycles.as_slice() else {
+        unreachable!("A region always has an exterior cycle")
+    };
+    let region = Region::new(
+        exterior.clone(),
+        interiors.iter().cloned(),
+        face.region().color(),
+    )
+    .insert(services);
+
+    Ok(Face::new(
+        Surface::new(orthonormal).insert(services),
+        region,
+    ))
+}
@@ -0,0 +1,166 @@
+use fj_math::{Aabb, Point, Scalar, Transform, Vector};
+
+use crate::{
+    algorithms::{
+        bounding_volume::BoundingVolume,
+        intersect::{
+            ray_face::{self, RayFaceIntersection},
+            HorizontalRayToTheRight, Intersect,
+        },
+    },
+    objects::Face,
+    storage::Handle,
+};
+
+use super::{fragment::Fragment, BooleanError, Classification};
+
+/// Classify a fragment of a face, relative to another solid
+///
+/// `faces` are the faces of the other solid, `aabb` its bounding box.
+pub fn classify(
+    fragment: &Fragment,
+    faces: &[Handle<Face>],
+    aabb: Option<Aabb<3>>,
+    tolerance: Scalar,
+) -> Result<Classification, BooleanError> {
+    match (fragment.face.aabb(), aabb) {
+        (Some(a), Some(b)) if a.intersects(&b) => {}
+        _ => return Ok(Classification::Outside),
+    }
+
+    let point = fragment
+        .point_inside()
+        .expect("Fragments of faces should not be degenerate");
+    let point = fragment
+        .face
+        .surface()
+        .geometry()
+        .point_from_surface_coords(point);
+
+    if let Some(normal) = fragment.face.normal() {
+        for face in faces {
+            let Some(other_normal) =
+                normal_if_coplanar(face, point, normal, tolerance)
+            else {
+                continue;
+            };
+
+            let point_surface =
+                face.surface().geometry().project_global_point(point);
+
+            if (&**face, &point_surface).intersect().is_some() {
+                return Ok(if normal.dot(&other_normal) > Scalar::ZERO {
+                    Classification::OnSame
+                } else {
+                    Classification::OnOpposite
+                });
+            }
+        }
+    }
+
+    if is_inside(point, faces, tolerance)? {
+        Ok(Classification::Inside)
+    } else {
+        Ok(Classification::Outside)
+    }
+}
+
+/// Return the normal of a face, if it's in the plane defined by the arguments
+fn normal_if_coplanar(
+    face: &Face,
+    point: Point<3>,
+    normal: Vector<3>,
+    tolerance: Scalar,
+) -> Option<Vector<3>> {
+    let other_normal = face.normal()?;
+
+    if normal.cross(&other_normal).magnitude() > tolerance {
+        return None;
+    }
+
+    let origin = face
+        .surface()
+        .geometry()
+        .point_from_surface_coords([0., 0.]);
+    if (point - origin).dot(&other_normal).abs() > tolerance {
+        return None;
+    }
+
+    Some(other_normal)
+}
+
+/// Determine whether a point is inside the solid bounded by the given faces
+///
+/// Casts a ray from the point and counts how many times it crosses the faces.
+/// If the ray hits an edge or a vertex, or runs within a face, the result would
+/// be ambiguous, so we try again in another direction.
+///
+/// Rays can only be cast along the x-axis. To cast them in other directions, we
+/// rotate the faces instead.
+fn is_inside(
+    point: Point<3>,
+    faces: &[Handle<Face>],
+    tolerance: Scalar,
+) -> Result<bool, BooleanError> {
+    // Arbitrary rotations that are unlikely to align the ray with anything in
+    // a typical model.
+    let rotations = [
+        [0.41, 0.67, 0.61],
+        [-0.73, 0.31, 0.65],
+        [0.26, -0.59, -0.77],
+        [-0.52, -0.44, 0.71],
+        [0.83, -0.29, 0.37],
+    ];
+
+    'rotations: for rotation in rotations {
+        let transform = Transform::rotation(Vector::from(rotation));
+        let ray = HorizontalRayToTheRight {
+            origin: transform.transform_point(&point),
+        };
+
+        let mut num_hits = 0;
+
+        for face in faces {
+            if let Some(aabb) = face.aabb() {
+                let aabb = Aabb::<3>::from_points(
+                    aabb.vertices()
+                        .iter()
+                        .map(|vertex| transform.transform_point(vertex)),
+                );
+                if !ray_hits_aabb(&ray, &aabb, tolerance) {
+                    continue;
+                }
+            }
+
+            for hit in ray_face::all_hits(&ray, face, &transform) {
+                match hit {
+                    RayFaceIntersection::RayHitsFace => num_hits += 1,
+                    RayFaceIntersection::RayHitsFaceAndAreParallel
+                    | RayFaceIntersection::RayHitsEdge(_)
+                    | RayFaceIntersection::RayHitsVertex(_) => {
+                        continue 'rotations;
+                    }
+                }
+            }
+        }
+
+        return Ok(num_hits % 2 == 1);
+    }
+
+    Err(BooleanError::AmbiguousClassification)
+}
+
+fn ray_hits_aabb(
+    ray: &HorizontalRayToTheRight<3>,
+    aabb: &Aabb<3>,
+    tolerance: Scalar,
+) -> bool {
+    let [x, y, z] = ray.origin.coords.components;
+    let [min, max] = [aabb.min, aabb.max].map(|point| point.coords.components);
+
+    x <= max[0] + tolerance
+        && y >= min[1] - tolerance
+        && y <= max[1] + tolerance
+        && z >= min[2] - tolerance
+        && z <= max[2] + tolerance
+}
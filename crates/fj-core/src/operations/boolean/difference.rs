@@ -0,0 +1,226 @@
+//! Difference of two solids
+
+use crate::{
+    algorithms::approx::Tolerance, objects::Solid, services::Services,
+};
+
+use super::{apply, BooleanError, Classification, Operand, Selection};
+
+/// Compute the difference of two [`Solid`]s
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait Difference: Sized {
+    /// Subtract another solid from this one
+    ///
+    /// The resulting solid encloses the volume that is enclosed by this solid,
+    /// but not by the other one. The faces of this solid that are located
+    /// within the other one are removed. The faces of the other solid that are
+    /// located within this one become part of the result, with their
+    /// orientation reversed, as they now bound the volume from the outside.
+    ///
+    /// Returns an error, if faces need to be split that are not supported. See
+    /// [module documentation].
+    ///
+    /// [module documentation]: super
+    fn difference(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BooleanError>;
+}
+
+impl Difference for Solid {
+    fn difference(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BooleanError> {
+        apply(
+            [self, other],
+            |operand, classification| match (operand, classification) {
+                (Operand::First, Classification::Outside) => Selection::Keep,
+                (Operand::First, Classification::OnOpposite) => Selection::Keep,
+                (Operand::Second, Classification::Inside) => {
+                    Selection::KeepReversed
+                }
+                _ => Selection::Drop,
+            },
+            tolerance,
+            services,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Vector;
+
+    use crate::{
+        algorithms::transform::TransformObject,
+        operations::{
+            boolean::BooleanError,
+            fixtures::{cuboid, num_faces, spacer, tolerance},
+            Insert,
+        },
+        services::Services,
+    };
+
+    use super::Difference;
+
+    #[test]
+    fn difference_of_disjoint_solids() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([3., 0., 0.], [2., 2., 2.], &mut services);
+
+        let difference = a
+            .difference(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(num_faces(&difference), 6);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn difference_with_pocket() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [4., 4., 2.], &mut services);
+        let b = cuboid([1., 1., 1.], [2., 2., 2.], &mut services);
+
+        let difference = a
+            .difference(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // The top face of `a` gets a hole. The four sides and the bottom of
+        // the pocket come from `b`.
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(num_faces(&difference), 11);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn difference_with_through_hole() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [4., 4., 2.], &mut services);
+        let b = cuboid([1., 1., -1.], [2., 2., 4.], &mut services);
+
+        let difference = a
+            .difference(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(num_faces(&difference), 10);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn difference_with_flush_pocket() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [4., 4., 2.], &mut services);
+        let b = cuboid([1., 1., 1.], [2., 2., 1.], &mut services);
+
+        let difference = a
+            .difference(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // The top faces of `a` and `b` coincide. Neither of them ends up in
+        // the result, where they overlap.
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(num_faces(&difference), 11);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn difference_with_touching_solid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([0.5, 0.5, 2.], [1., 1., 1.], &mut services);
+
+        let difference = a
+            .difference(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // `b` only touches `a` from the outside. The top face of `a` is split
+        // where `b` touches it, but nothing is removed.
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(num_faces(&difference), 7);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn difference_with_rotated_solid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([0., 0., 0.], [2., 2., 2.], &mut services)
+            .rotate(Vector::from([1., 2., 3.]).normalize() * 0.5, &mut services)
+            .translate([1., 0.5, 0.7], &mut services);
+
+        let difference = a
+            .difference(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(difference.shells().len(), 1);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn difference_with_solid_in_hole() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = spacer(2., 1., 1., &mut services);
+        let b = cuboid([-0.5, -0.5, 0.25], [1., 1., 0.5], &mut services);
+
+        let difference = a
+            .difference(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // `b` is located in the hole of `a`, without touching it. Rays cast
+        // from the faces of `b` cross the round faces of `a`.
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(num_faces(&difference), 4);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn difference_with_cylinder_through_solid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [4., 4., 2.], &mut services);
+        let b = spacer(1., 0.5, 4., &mut services)
+            .translate([2., 2., -1.], &mut services);
+
+        let result = a.difference(&b, tolerance(), &mut services);
+
+        // Splitting the faces of `a` along the round faces of `b` is not
+        // supported.
+        assert_eq!(result, Err(BooleanError::CurvedFace));
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+}
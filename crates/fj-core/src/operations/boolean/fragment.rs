@@ -0,0 +1,567 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use fj_math::{Point, Scalar, Vector, Winding};
+
+use crate::{
+    algorithms::{
+        approx::{Approx, Tolerance},
+        bounding_volume::BoundingVolume,
+        intersect::{face_point::FacePointIntersection, Intersect},
+    },
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{Cycle, Edge, Face, Handedness, Region, Vertex},
+    operations::{BuildEdge, Insert, UpdateEdge},
+    services::Services,
+    storage::{Handle, ObjectId},
+};
+
+use super::{
+    registry::{Curves, Vertices},
+    BooleanError,
+};
+
+/// A part of a face, as created by splitting it along intersections
+pub struct Fragment {
+    /// The face that this fragment is a part of
+    pub face: Handle<Face>,
+
+    /// The boundary of the fragment
+    ///
+    /// `None`, if the face didn't need to be split, and the fragment is the
+    /// whole, unchanged face.
+    boundary: Option<Boundary>,
+}
+
+impl Fragment {
+    /// Split a face into fragments
+    ///
+    /// `segments` are the line segments along which the face is to be split,
+    /// in addition to its own edges. They must be located within the face.
+    ///
+    /// Only planar faces with straight edges can be split. For any other face,
+    /// an error is returned, if it would need to be split.
+    pub fn split(
+        face: &Handle<Face>,
+        segments: &[[Handle<Vertex>; 2]],
+        vertices: &Vertices,
+    ) -> Result<Vec<Self>, BooleanError> {
+        let surface = face.surface().geometry();
+        let is_planar = matches!(
+            surface,
+            SurfaceGeometry::Swept {
+                u: GlobalPath::Line(_),
+                ..
+            }
+        );
+
+        let mut boundary_segments = Vec::new();
+        let mut is_modified = !segments.is_empty();
+        let mut has_curved_edges = false;
+
+        for cycle in face.region().all_cycles() {
+            for (edge, next) in cycle.edges().pairs() {
+                let a = vertices.original(edge.start_vertex());
+                let b = vertices.original(next.start_vertex());
+
+                if a.id() != edge.start_vertex().id() {
+                    is_modified = true;
+                }
+
+                let is_straight =
+                    is_planar && matches!(edge.path(), SurfacePath::Line(_));
+                if !is_straight {
+                    has_curved_edges = true;
+                    continue;
+                }
+
+                let segment = [a, b].map(|vertex| vertices.position(vertex));
+                if !vertices.within_segment(segment).is_empty() {
+                    is_modified = true;
+                }
+
+                boundary_segments.push([a.clone(), b.clone()]);
+            }
+        }
+
+        if !is_modified {
+            return Ok(vec![Self {
+                face: face.clone(),
+                boundary: None,
+            }]);
+        }
+
+        if !is_planar {
+            return Err(BooleanError::CurvedFace);
+        }
+        if has_curved_edges {
+            return Err(BooleanError::CurvedEdge);
+        }
+
+        // Determine the positions of all vertices in surface coordinates. Where
+        // possible, we take those from the face's own edges, to make sure that
+        // the new edges match up exactly with the old ones.
+        let mut positions = BTreeMap::new();
+        for edge in face.region().all_cycles().flat_map(|cycle| cycle.edges()) {
+            if vertices.original(edge.start_vertex()).id()
+                == edge.start_vertex().id()
+            {
+                positions.insert(
+                    edge.start_vertex().id(),
+                    (edge.start_vertex().clone(), edge.start_position()),
+                );
+            }
+        }
+
+        let mut edges = BTreeSet::new();
+        for segment in boundary_segments.iter().chain(segments) {
+            let [a, b] = segment;
+            let positions_3d = [a, b].map(|vertex| vertices.position(vertex));
+
+            let mut points = vec![a.clone()];
+            points.extend(vertices.within_segment(positions_3d));
+            points.push(b.clone());
+
+            for vertex in &points {
+                positions.entry(vertex.id()).or_insert_with(|| {
+                    let position =
+                        surface.project_global_point(vertices.position(vertex));
+                    (vertex.clone(), position)
+                });
+            }
+
+            for window in points.windows(2) {
+                let [a, b] = [&window[0], &window[1]].map(|vertex| vertex.id());
+                if a != b {
+                    edges.insert([a.min(b), a.max(b)]);
+                }
+            }
+        }
+
+        let cycles = Arrangement::new(edges, &positions).faces();
+
+        let flip = face.coord_handedness() == Handedness::LeftHanded;
+
+        let fragments = cycles
+            .into_iter()
+            .map(|(exterior, interiors)| {
+                let corners = |ids: Vec<ObjectId>| {
+                    let mut corners = ids
+                        .into_iter()
+                        .map(|id| {
+                            let (vertex, position) = &positions[&id];
+                            Corner {
+                                vertex: vertex.clone(),
+                                position: *position,
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    if flip {
+                        corners.reverse();
+                    }
+
+                    corners
+                };
+
+                let boundary = Boundary {
+                    exterior: corners(exterior),
+                    interiors: interiors.into_iter().map(corners).collect(),
+                };
+
+                Self {
+                    face: face.clone(),
+                    boundary: Some(boundary),
+                }
+            })
+            .filter(|fragment| {
+                // The arrangement also contains the areas enclosed by the
+                // face's interior cycles. Those are not part of the face.
+                let Some(point) = fragment.point_inside() else {
+                    return false;
+                };
+
+                matches!(
+                    (&**face, &point).intersect(),
+                    Some(FacePointIntersection::PointIsInsideFace)
+                )
+            })
+            .collect();
+
+        Ok(fragments)
+    }
+
+    /// Find a point that is located within the fragment, in surface coordinates
+    pub fn point_inside(&self) -> Option<Point<2>> {
+        let rings = match &self.boundary {
+            Some(boundary) => boundary
+                .all_rings()
+                .map(|ring| ring.iter().map(|corner| corner.position).collect())
+                .collect::<Vec<Vec<_>>>(),
+            None => {
+                let aabb = self.face.aabb()?;
+                let tolerance =
+                    Tolerance::from_scalar(aabb.size().magnitude() / 1000.)
+                        .ok()?;
+
+                let approx = (&*self.face).approx(tolerance);
+
+                let mut rings = vec![approx.exterior.points()];
+                rings.extend(
+                    approx.interiors.iter().map(|interior| interior.points()),
+                );
+
+                rings
+                    .into_iter()
+                    .map(|ring| {
+                        ring.into_iter().map(|point| point.local_form).collect()
+                    })
+                    .collect()
+            }
+        };
+
+        point_inside_polygon(&rings)
+    }
+
+    /// Build the face that corresponds to this fragment
+    pub fn build(
+        &self,
+        curves: &mut Curves,
+        services: &mut Services,
+    ) -> Handle<Face> {
+        let Some(boundary) = &self.boundary else {
+            return self.face.clone();
+        };
+
+        let exterior = build_cycle(&boundary.exterior, curves, services);
+        let interiors = boundary
+            .interiors
+            .iter()
+            .map(|interior| build_cycle(interior, curves, services))
+            .collect::<Vec<_>>();
+
+        let region =
+            Region::new(exterior, interiors, self.face.region().color())
+                .insert(services);
+
+        Face::new(self.face.surface().clone(), region).insert(services)
+    }
+}
+
+struct Boundary {
+    exterior: Vec<Corner>,
+    interiors: Vec<Vec<Corner>>,
+}
+
+impl Boundary {
+    fn all_rings(&self) -> impl Iterator<Item = &Vec<Corner>> {
+        [&self.exterior].into_iter().chain(&self.interiors)
+    }
+}
+
+struct Corner {
+    vertex: Handle<Vertex>,
+    position: Point<2>,
+}
+
+fn build_cycle(
+    corners: &[Corner],
+    curves: &mut Curves,
+    services: &mut Services,
+) -> Handle<Cycle> {
+    let edges = corners
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let b = &corners[(i + 1) % corners.len()];
+
+            let (curve, boundary) =
+                curves.get_or_insert([&a.vertex, &b.vertex], services);
+
+            Edge::line_segment(
+                [a.position, b.position],
+                Some(boundary),
+                services,
+            )
+            .update_curve(|_| curve)
+            .update_start_vertex(|_| a.vertex.clone())
+            .insert(services)
+        })
+        .collect::<Vec<_>>();
+
+    Cycle::new(edges).insert(services)
+}
+
+/// A planar arrangement of line segments
+///
+/// Used to find the faces, that the segments divide the plane into.
+struct Arrangement<'r> {
+    neighbors: BTreeMap<ObjectId, Vec<ObjectId>>,
+    positions: &'r BTreeMap<ObjectId, (Handle<Vertex>, Point<2>)>,
+}
+
+impl<'r> Arrangement<'r> {
+    fn new(
+        mut edges: BTreeSet<[ObjectId; 2]>,
+        positions: &'r BTreeMap<ObjectId, (Handle<Vertex>, Point<2>)>,
+    ) -> Self {
+        // Dangling edges can't be part of the boundary of any face. Remove
+        // them, until none are left.
+        loop {
+            let mut degrees = BTreeMap::new();
+            for &[a, b] in &edges {
+                *degrees.entry(a).or_insert(0) += 1;
+                *degrees.entry(b).or_insert(0) += 1;
+            }
+
+            let num_edges = edges.len();
+            edges.retain(|[a, b]| degrees[a] > 1 && degrees[b] > 1);
+
+            if edges.len() == num_edges {
+                break;
+            }
+        }
+
+        let mut neighbors = BTreeMap::new();
+        for &[a, b] in &edges {
+            neighbors.entry(a).or_insert_with(Vec::new).push(b);
+            neighbors.entry(b).or_insert_with(Vec::new).push(a);
+        }
+
+        Self {
+            neighbors,
+            positions,
+        }
+    }
+
+    /// Find the faces of the arrangement
+    ///
+    /// Returns each face as its counter-clockwise exterior boundary, together
+    /// with the clockwise boundaries of its holes.
+    #[allow(clippy::type_complexity)]
+    fn faces(&self) -> Vec<(Vec<ObjectId>, Vec<Vec<ObjectId>>)> {
+        let mut visited = BTreeSet::new();
+
+        let mut exteriors = Vec::new();
+        let mut holes = Vec::new();
+
+        for (&a, neighbors) in &self.neighbors {
+            for &b in neighbors {
+                if visited.contains(&(a, b)) {
+                    continue;
+                }
+
+                let mut cycle = Vec::new();
+                let (mut current, mut next) = (a, b);
+
+                while visited.insert((current, next)) {
+                    cycle.push(current);
+                    (current, next) = (next, self.next(current, next));
+                }
+
+                let points = cycle
+                    .iter()
+                    .map(|id| self.position(*id))
+                    .collect::<Vec<_>>();
+                let area = signed_area(&points);
+
+                match area.cmp(&Scalar::ZERO) {
+                    Ordering::Greater => exteriors.push((cycle, points, area)),
+                    Ordering::Less => holes.push(cycle),
+                    Ordering::Equal => {}
+                }
+            }
+        }
+
+        let mut faces = exteriors
+            .iter()
+            .map(|(cycle, _, _)| (cycle.clone(), Vec::new()))
+            .collect::<Vec<_>>();
+
+        for hole in holes {
+            // A clockwise cycle is either the hole of another face, or the
+            // outer boundary of a connected part of the arrangement. Different
+            // parts don't touch, so any vertex of a hole that isn't shared with
+            // an exterior is located either strictly inside or outside of it.
+            let enclosing = exteriors
+                .iter()
+                .enumerate()
+                .filter(|(_, (exterior, points, _))| {
+                    hole.iter()
+                        .find(|id| !exterior.contains(id))
+                        .map(|id| {
+                            contains(&[points.clone()], self.position(*id))
+                        })
+                        .unwrap_or(false)
+                })
+                .min_by_key(|(_, (_, _, area))| *area);
+
+            if let Some((i, _)) = enclosing {
+                faces[i].1.push(hole);
+            }
+        }
+
+        faces
+    }
+
+    /// Find the next edge of the face to the left of the directed edge `a`-`b`
+    fn next(&self, a: ObjectId, b: ObjectId) -> ObjectId {
+        let angle = |from: ObjectId, to: ObjectId| {
+            let direction = self.position(to) - self.position(from);
+            direction.v.atan2(direction.u)
+        };
+
+        let back = angle(b, a);
+
+        let candidates = self.neighbors[&b]
+            .iter()
+            .filter(|&&c| c != a)
+            .map(|&c| (angle(b, c), c))
+            .collect::<Vec<_>>();
+
+        // Take the neighbor that comes first, when turning clockwise from the
+        // edge we came from. That's the sharpest possible left turn.
+        candidates
+            .iter()
+            .filter(|(angle, _)| *angle < back)
+            .max_by_key(|(angle, _)| *angle)
+            .or_else(|| candidates.iter().max_by_key(|(angle, _)| *angle))
+            .map(|(_, c)| *c)
+            .unwrap_or(a)
+    }
+
+    fn position(&self, id: ObjectId) -> Point<2> {
+        self.positions[&id].1
+    }
+}
+
+/// Find a point within a polygon, which may have holes
+///
+/// Of all candidate points that are considered, the one furthest away from the
+/// polygon's boundary is returned. Returns `None`, if the polygon is
+/// degenerate.
+fn point_inside_polygon(rings: &[Vec<Point<2>>]) -> Option<Point<2>> {
+    let exterior = rings.first()?;
+    let winding = if signed_area(exterior) > Scalar::ZERO {
+        Winding::Ccw
+    } else {
+        Winding::Cw
+    };
+
+    let segments = rings
+        .iter()
+        .flat_map(|ring| {
+            ring.iter()
+                .enumerate()
+                .map(|(i, &a)| [a, ring[(i + 1) % ring.len()]])
+        })
+        .collect::<Vec<_>>();
+
+    let mut best: Option<(Scalar, Point<2>)> = None;
+
+    // Start in the middle of an edge of the exterior, move inward, and stop
+    // halfway to the next boundary.
+    for (i, &[a, b]) in segments.iter().enumerate().take(exterior.len()) {
+        let direction = b - a;
+        if direction.magnitude() == Scalar::ZERO {
+            continue;
+        }
+
+        let inward = match winding {
+            Winding::Ccw => Vector::from([-direction.v, direction.u]),
+            Winding::Cw => Vector::from([direction.v, -direction.u]),
+        }
+        .normalize();
+
+        let start = a + direction / 2.;
+
+        let nearest = segments
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| i != *j)
+            .filter_map(|(_, &[p, q])| {
+                let edge = q - p;
+                let denominator = inward.cross2d(&edge);
+                if denominator == Scalar::ZERO {
+                    return None;
+                }
+
+                let to_p = p - start;
+                let s = to_p.cross2d(&edge) / denominator;
+                let r = to_p.cross2d(&inward) / denominator;
+
+                (s > Scalar::ZERO && r >= Scalar::ZERO && r <= Scalar::ONE)
+                    .then_some(s)
+            })
+            .min();
+
+        let Some(distance) = nearest else {
+            continue;
+        };
+
+        let point = start + inward * distance / 2.;
+        if !contains(rings, point) {
+            continue;
+        }
+
+        let clearance = segments
+            .iter()
+            .map(|&segment| distance_to_segment(point, segment))
+            .min()
+            .unwrap_or(Scalar::ZERO);
+
+        if best.map_or(true, |(best, _)| clearance > best) {
+            best = Some((clearance, point));
+        }
+    }
+
+    best.map(|(_, point)| point)
+}
+
+fn distance_to_segment(point: Point<2>, [a, b]: [Point<2>; 2]) -> Scalar {
+    let ab = b - a;
+    let length_squared = ab.dot(&ab);
+
+    let t = if length_squared == Scalar::ZERO {
+        Scalar::ZERO
+    } else {
+        ((point - a).dot(&ab) / length_squared)
+            .max(Scalar::ZERO)
+            .min(Scalar::ONE)
+    };
+
+    point.distance_to(&(a + ab * t))
+}
+
+/// Determine whether a point is within a polygon, using the even-odd rule
+fn contains(rings: &[Vec<Point<2>>], point: Point<2>) -> bool {
+    let mut inside = false;
+
+    for ring in rings {
+        for (i, a) in ring.iter().enumerate() {
+            let b = ring[(i + 1) % ring.len()];
+
+            if (a.v > point.v) != (b.v > point.v) {
+                let u = a.u + (point.v - a.v) * (b.u - a.u) / (b.v - a.v);
+                if point.u < u {
+                    inside = !inside;
+                }
+            }
+        }
+    }
+
+    inside
+}
+
+fn signed_area(points: &[Point<2>]) -> Scalar {
+    let mut area = Scalar::ZERO;
+
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        area += a.u * b.v - b.u * a.v;
+    }
+
+    area / 2.
+}
@@ -0,0 +1,177 @@
+//! Intersection of two solids
+
+use crate::{
+    algorithms::approx::Tolerance, objects::Solid, services::Services,
+};
+
+use super::{apply, BooleanError, Classification, Operand, Selection};
+
+/// Compute the intersection of two [`Solid`]s
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait Intersection: Sized {
+    /// Compute the volume that this solid has in common with another
+    ///
+    /// The resulting solid encloses the volume that is enclosed by both of the
+    /// solids. Only the faces of each solid that are located within the other
+    /// one are kept. Where the faces of both solids coincide, only those of
+    /// this solid are kept.
+    ///
+    /// Returns an error, if faces need to be split that are not supported. See
+    /// [module documentation].
+    ///
+    /// [module documentation]: super
+    fn intersection(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BooleanError>;
+}
+
+impl Intersection for Solid {
+    fn intersection(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BooleanError> {
+        apply(
+            [self, other],
+            |operand, classification| match (operand, classification) {
+                (_, Classification::Inside) => Selection::Keep,
+                (Operand::First, Classification::OnSame) => Selection::Keep,
+                _ => Selection::Drop,
+            },
+            tolerance,
+            services,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Vector;
+
+    use crate::{
+        algorithms::transform::TransformObject,
+        operations::{
+            fixtures::{cuboid, num_faces, tolerance},
+            Insert,
+        },
+        services::Services,
+    };
+
+    use super::Intersection;
+
+    #[test]
+    fn intersection_of_disjoint_solids() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([3., 0., 0.], [2., 2., 2.], &mut services);
+
+        let intersection = a
+            .intersection(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(intersection.shells().len(), 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn intersection_of_overlapping_solids() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([1., 1., 1.], [2., 2., 2.], &mut services);
+
+        let intersection = a
+            .intersection(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // Each solid contributes the corner of three of its faces.
+        assert_eq!(intersection.shells().len(), 1);
+        assert_eq!(num_faces(&intersection), 6);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn intersection_of_solids_with_coplanar_faces() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([1., 0., 0.], [2., 2., 2.], &mut services);
+
+        let intersection = a
+            .intersection(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(intersection.shells().len(), 1);
+        assert_eq!(num_faces(&intersection), 6);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn intersection_with_contained_solid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([0.5, 0.5, 0.5], [1., 1., 1.], &mut services);
+
+        let intersection = a
+            .intersection(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(intersection.shells().len(), 1);
+        assert_eq!(num_faces(&intersection), 6);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn intersection_of_touching_solids() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([0.5, 0.5, 2.], [1., 1., 1.], &mut services);
+
+        let intersection = a
+            .intersection(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // The solids share an area, but no volume.
+        assert_eq!(intersection.shells().len(), 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn intersection_of_rotated_solids() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([0., 0., 0.], [2., 2., 2.], &mut services)
+            .rotate(Vector::from([1., 2., 3.]).normalize() * 0.5, &mut services)
+            .translate([1., 0.5, 0.7], &mut services);
+
+        let intersection = a
+            .intersection(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(intersection.shells().len(), 1);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,513 @@
+//! Boolean operations on solids
+//!
+//! All boolean operations work the same way: The faces of both solids are
+//! split along the intersections with the other solid. Each of the resulting
+//! fragments is then classified, depending on whether it is located inside or
+//! outside of the other solid, or on its boundary. Which fragments end up in
+//! the result, is what distinguishes the different operations.
+//!
+//! All boolean operations take a tolerance. Vertices that are closer to each
+//! other than that are considered identical, as are faces that are parallel
+//! and closer to each other than that.
+//!
+//! Faces are only split along straight lines. Solids whose curved faces or
+//! curved edges would need to be split are not supported, and result in a
+//! [`BooleanError`].
+
+pub mod difference;
+pub mod intersection;
+pub mod union;
+
+mod classify;
+mod fragment;
+mod registry;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_interop::ext::ArrayExt;
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{Approx, Tolerance},
+        bounding_volume::BoundingVolume,
+        intersect::{CurveFaceIntersection, FaceFaceIntersection},
+    },
+    geometry::{GlobalPath, SurfaceGeometry, SurfacePath},
+    objects::{Face, Shell, Solid, Vertex},
+    operations::{Insert, Reverse},
+    services::Services,
+    storage::{Handle, ObjectId},
+};
+
+use self::{
+    classify::classify,
+    fragment::Fragment,
+    registry::{Curves, Vertices},
+};
+
+/// One of the two solids that a boolean operation is applied to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operand {
+    /// The solid that the operation is called on
+    First,
+
+    /// The solid that is passed as an argument to the operation
+    Second,
+}
+
+/// The location of a face fragment, relative to the other solid
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Classification {
+    /// The fragment is inside of the other solid
+    Inside,
+
+    /// The fragment is outside of the other solid
+    Outside,
+
+    /// The fragment is on a face of the other solid, facing the same way
+    OnSame,
+
+    /// The fragment is on a face of the other solid, facing the other way
+    OnOpposite,
+}
+
+/// What a boolean operation does with a face fragment
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Selection {
+    /// Don't include the fragment in the result
+    Drop,
+
+    /// Include the fragment in the result
+    Keep,
+
+    /// Include the fragment in the result, with its orientation reversed
+    KeepReversed,
+}
+
+/// An error that can occur during a boolean operation
+///
+/// Returned by [`Union::union`], [`Difference::difference`], and
+/// [`Intersection::intersection`].
+///
+/// [`Union::union`]: crate::operations::Union::union
+/// [`Difference::difference`]: crate::operations::Difference::difference
+/// [`Intersection::intersection`]: crate::operations::Intersection::intersection
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum BooleanError {
+    /// A face on a curved surface touches the other solid
+    #[error(
+        "Boolean operations are not supported where faces on curved surfaces \
+        touch the other solid"
+    )]
+    CurvedFace,
+
+    /// A face with curved edges would need to be split
+    #[error(
+        "Boolean operations are not supported where faces with curved edges \
+        need to be split"
+    )]
+    CurvedEdge,
+
+    /// A fragment could not be classified relative to the other solid
+    ///
+    /// All rays that were cast from the fragment hit an edge or a vertex of
+    /// the other solid, or ran within one of its faces.
+    #[error("Failed to determine whether a face is inside the other solid")]
+    AmbiguousClassification,
+}
+
+/// Apply a boolean operation to two solids
+///
+/// `select` decides, based on the classification of a fragment, what happens
+/// with it.
+fn apply(
+    solids: [&Solid; 2],
+    select: impl Fn(Operand, Classification) -> Selection,
+    tolerance: impl Into<Tolerance>,
+    services: &mut Services,
+) -> Result<Solid, BooleanError> {
+    let tolerance = tolerance.into();
+
+    let faces = solids.map(|solid| {
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces().iter().cloned())
+            .collect::<Vec<_>>()
+    });
+
+    let mut vertices = Vertices::new(tolerance.inner());
+    let mut curves = Curves::default();
+
+    for face in faces.iter().flatten() {
+        let surface = face.surface().geometry();
+
+        for edge in face.region().all_cycles().flat_map(|cycle| cycle.edges()) {
+            let position =
+                surface.point_from_surface_coords(edge.start_position());
+            vertices.insert_original(edge.start_vertex(), position);
+        }
+    }
+    for face in faces.iter().flatten() {
+        let SurfaceGeometry::Swept {
+            u: GlobalPath::Line(_),
+            ..
+        } = face.surface().geometry()
+        else {
+            continue;
+        };
+
+        for cycle in face.region().all_cycles() {
+            for (edge, next) in cycle.edges().pairs() {
+                let SurfacePath::Line(_) = edge.path() else {
+                    continue;
+                };
+
+                curves.insert_original(
+                    [edge.start_vertex(), next.start_vertex()]
+                        .map(|vertex| vertices.original(vertex)),
+                    edge.curve(),
+                    edge.boundary().inner,
+                );
+            }
+        }
+    }
+
+    let segments = intersect(&faces, &mut vertices, tolerance, services)?;
+
+    let aabbs = faces.each_ref_ext().map(|faces| {
+        faces
+            .iter()
+            .filter_map(|face| face.aabb())
+            .reduce(|a, b| a.merged(&b))
+    });
+
+    let mut result = Vec::new();
+
+    for (operand, i) in [(Operand::First, 0), (Operand::Second, 1)] {
+        let other = 1 - i;
+
+        for face in &faces[i] {
+            let segments = segments
+                .get(&face.id())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+
+            for fragment in Fragment::split(face, segments, &vertices)? {
+                let classification = classify(
+                    &fragment,
+                    &faces[other],
+                    aabbs[other],
+                    tolerance.inner(),
+                )?;
+
+                match select(operand, classification) {
+                    Selection::Drop => {}
+                    Selection::Keep => {
+                        let face = fragment.build(&mut curves, services);
+                        result.push((operand, face));
+                    }
+                    Selection::KeepReversed => {
+                        let face = fragment.build(&mut curves, services);
+                        let face = face.reverse(services).insert(services);
+                        result.push((operand, face));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(Solid::new(group_into_shells(result, services)))
+}
+
+/// The line segments along which faces need to be split, keyed by face ID
+type Segments = BTreeMap<ObjectId, Vec<[Handle<Vertex>; 2]>>;
+
+/// Compute the line segments along which the faces need to be split
+///
+/// Returns the segments for each face, keyed by the face's ID.
+fn intersect(
+    faces: &[Vec<Handle<Face>>; 2],
+    vertices: &mut Vertices,
+    tolerance: Tolerance,
+    services: &mut Services,
+) -> Result<Segments, BooleanError> {
+    let mut segments = Segments::new();
+
+    for a in &faces[0] {
+        for b in &faces[1] {
+            match (a.aabb(), b.aabb()) {
+                (Some(aabb_a), Some(aabb_b)) if aabb_a.intersects(&aabb_b) => {}
+                _ => continue,
+            }
+
+            if are_coplanar(a, b, tolerance.inner()) {
+                // Where coplanar faces overlap, the edges of each face split
+                // the other one.
+                for (face, other) in [(a, b), (b, a)] {
+                    for segment in clip_edges(other, face, vertices, tolerance)?
+                    {
+                        let segment = segment
+                            .map(|point| vertices.insert(point, services));
+
+                        if segment[0].id() != segment[1].id() {
+                            segments
+                                .entry(face.id())
+                                .or_default()
+                                .push(segment);
+                        }
+                    }
+                }
+
+                continue;
+            }
+
+            let intersections = if is_planar(a) && is_planar(b) {
+                FaceFaceIntersection::compute([a, b], tolerance)
+            } else if can_intersect_curved(a, b) {
+                // Where a cylinder is involved, the faces intersect along
+                // curves, which we can't split faces along.
+                if !FaceFaceIntersection::compute([a, b], tolerance).is_empty()
+                {
+                    return Err(BooleanError::CurvedFace);
+                }
+
+                continue;
+            } else {
+                // We can't compute intersections with these surfaces. As far
+                // as we know, the faces touch.
+                return Err(BooleanError::CurvedFace);
+            };
+
+            for intersection in intersections {
+                let path = intersection.intersection_curves[0];
+                let surface = a.surface().geometry();
+
+                for interval in intersection.intersection_intervals.intervals {
+                    let segment = [interval.start, interval.end].map(|t| {
+                        let point = path.point_from_path_coords(t);
+                        let point = surface.point_from_surface_coords(point);
+                        vertices.insert(point, services)
+                    });
+
+                    if segment[0].id() == segment[1].id() {
+                        continue;
+                    }
+
+                    for face in [a, b] {
+                        segments
+                            .entry(face.id())
+                            .or_default()
+                            .push(segment.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+fn is_planar(face: &Face) -> bool {
+    matches!(
+        face.surface().geometry(),
+        SurfaceGeometry::Swept {
+            u: GlobalPath::Line(_),
+            ..
+        }
+    )
+}
+
+/// Determine whether we can intersect two faces, one of which is curved
+///
+/// This matches the surfaces that [`SurfaceSurfaceIntersection`] supports.
+///
+/// [`SurfaceSurfaceIntersection`]: crate::algorithms::intersect::SurfaceSurfaceIntersection
+fn can_intersect_curved(a: &Face, b: &Face) -> bool {
+    match [a, b].map(cylinder_axis) {
+        [Some(_), None] => is_planar(b),
+        [None, Some(_)] => is_planar(a),
+        [Some(axis_a), Some(axis_b)] => {
+            axis_a.cross(&axis_b).magnitude() <= epsilon()
+        }
+        [None, None] => false,
+    }
+}
+
+/// Return the axis of a face's surface, if it is a cylinder
+///
+/// Only cylinders that are swept along their axis count. `None` is returned for
+/// any other surface.
+fn cylinder_axis(face: &Face) -> Option<Vector<3>> {
+    let SurfaceGeometry::Swept {
+        u: GlobalPath::Circle(circle),
+        v,
+    } = face.surface().geometry()
+    else {
+        return None;
+    };
+
+    let axis = circle.a().cross(&circle.b()).normalize();
+    let is_swept_along_axis =
+        v.cross(&axis).magnitude() <= v.magnitude() * epsilon();
+
+    is_swept_along_axis.then_some(axis)
+}
+
+/// The tolerance used when checking vectors for being parallel
+///
+/// This is the same tolerance that [`SurfaceSurfaceIntersection`] uses.
+///
+/// [`SurfaceSurfaceIntersection`]: crate::algorithms::intersect::SurfaceSurfaceIntersection
+fn epsilon() -> Scalar {
+    Scalar::from_f64(f64::EPSILON * 16.)
+}
+
+fn are_coplanar(a: &Face, b: &Face, tolerance: Scalar) -> bool {
+    let (Some(normal_a), Some(normal_b)) = (a.normal(), b.normal()) else {
+        return false;
+    };
+
+    if normal_a.cross(&normal_b).magnitude() > tolerance {
+        return false;
+    }
+
+    let [origin_a, origin_b] = [a, b].map(|face| {
+        face.surface()
+            .geometry()
+            .point_from_surface_coords([0., 0.])
+    });
+
+    (origin_b - origin_a).dot(&normal_a).abs() <= tolerance
+}
+
+/// Clip the edges of a face to the area of another, coplanar one
+fn clip_edges(
+    face: &Face,
+    other: &Face,
+    vertices: &Vertices,
+    tolerance: Tolerance,
+) -> Result<Vec<[Point<3>; 2]>, BooleanError> {
+    assert!(is_planar(other), "Coplanar faces must be planar");
+
+    let mut segments = Vec::new();
+
+    for cycle in face.region().all_cycles() {
+        for (edge, next) in cycle.edges().pairs() {
+            let end = vertices.position(vertices.original(next.start_vertex()));
+
+            if let SurfacePath::Line(_) = edge.path() {
+                let start =
+                    vertices.position(vertices.original(edge.start_vertex()));
+                segments.extend(clip_segment([start, end], other));
+                continue;
+            }
+
+            // We can't split faces along curved edges. But as long as the
+            // edge is located outside of the other face, it doesn't need to
+            // be. Its approximation tells us whether that is the case.
+            let mut points = (&**edge, &**face.surface())
+                .approx(tolerance)
+                .points()
+                .into_iter()
+                .map(|point| point.global_form)
+                .collect::<Vec<_>>();
+            points.push(end);
+
+            for segment in points.windows(2) {
+                if !clip_segment([segment[0], segment[1]], other).is_empty() {
+                    return Err(BooleanError::CurvedEdge);
+                }
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Clip a line segment to the area of a face that it's coplanar with
+fn clip_segment(points: [Point<3>; 2], face: &Face) -> Vec<[Point<3>; 2]> {
+    let surface = face.surface().geometry();
+    let path = SurfacePath::line_from_points_with_coords([
+        ([0.], surface.project_global_point(points[0])),
+        ([1.], surface.project_global_point(points[1])),
+    ]);
+
+    CurveFaceIntersection::compute(&path, face)
+        .intervals
+        .into_iter()
+        .filter_map(|interval| {
+            let start = interval.start.max(Point::from([0.]));
+            let end = interval.end.min(Point::from([1.]));
+
+            (start < end).then(|| {
+                [start, end].map(|t| {
+                    surface.point_from_surface_coords(
+                        path.point_from_path_coords(t),
+                    )
+                })
+            })
+        })
+        .collect()
+}
+
+/// Group faces into shells, according to which faces share edges
+///
+/// Each face comes with the operand that it originates from. Usually, each
+/// curve is shared by two faces. Where the solids touch along an edge, four
+/// faces share the curve of that edge, and joining all of them would result in
+/// a shell that is not manifold. Those faces are only joined with faces from
+/// the same operand.
+fn group_into_shells(
+    faces: Vec<(Operand, Handle<Face>)>,
+    services: &mut Services,
+) -> Vec<Handle<Shell>> {
+    let mut groups = (0..faces.len()).collect::<Vec<_>>();
+    let mut faces_by_curve = BTreeMap::new();
+
+    fn root(groups: &mut [usize], mut i: usize) -> usize {
+        while groups[i] != i {
+            groups[i] = groups[groups[i]];
+            i = groups[i];
+        }
+        i
+    }
+
+    for (i, (_, face)) in faces.iter().enumerate() {
+        for edge in face.region().all_cycles().flat_map(|cycle| cycle.edges()) {
+            faces_by_curve
+                .entry(edge.curve().id())
+                .or_insert_with(BTreeSet::new)
+                .insert(i);
+        }
+    }
+
+    for sharing_faces in faces_by_curve.into_values() {
+        let is_manifold = sharing_faces.len() <= 2;
+
+        for &i in &sharing_faces {
+            for &j in &sharing_faces {
+                if !is_manifold && faces[i].0 != faces[j].0 {
+                    continue;
+                }
+
+                let (i, j) = (root(&mut groups, i), root(&mut groups, j));
+                groups[i] = j;
+            }
+        }
+    }
+
+    let mut shells = BTreeMap::new();
+    for (i, (_, face)) in faces.into_iter().enumerate() {
+        shells
+            .entry(root(&mut groups, i))
+            .or_insert_with(Vec::new)
+            .push(face);
+    }
+
+    shells
+        .into_values()
+        .map(|faces| Shell::new(faces).insert(services))
+        .collect()
+}
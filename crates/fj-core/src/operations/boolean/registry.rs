@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+
+use fj_math::{Point, Scalar};
+
+use crate::{
+    objects::{Curve, Vertex},
+    operations::Insert,
+    services::Services,
+    storage::{Handle, ObjectId},
+};
+
+/// The vertices that are relevant to a boolean operation
+///
+/// Keeps track of the vertices of both input solids, as well as the vertices
+/// that are created where those solids intersect. Any two vertices that are
+/// closer to each other than the tolerance are considered identical, and only
+/// the one that was registered first is kept.
+pub struct Vertices {
+    tolerance: Scalar,
+    vertices: Vec<(Handle<Vertex>, Point<3>)>,
+    positions: BTreeMap<ObjectId, Point<3>>,
+    originals: BTreeMap<ObjectId, Handle<Vertex>>,
+}
+
+impl Vertices {
+    /// Create an empty registry
+    pub fn new(tolerance: Scalar) -> Self {
+        Self {
+            tolerance,
+            vertices: Vec::new(),
+            positions: BTreeMap::new(),
+            originals: BTreeMap::new(),
+        }
+    }
+
+    /// Register a vertex of one of the input solids
+    pub fn insert_original(
+        &mut self,
+        vertex: &Handle<Vertex>,
+        position: Point<3>,
+    ) {
+        if self.originals.contains_key(&vertex.id()) {
+            return;
+        }
+
+        let replacement = self.find(position).unwrap_or_else(|| {
+            self.push(vertex.clone(), position);
+            vertex.clone()
+        });
+
+        self.originals.insert(vertex.id(), replacement);
+    }
+
+    /// Access the vertex that replaces a vertex of one of the input solids
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the vertex has not been registered using
+    /// [`Vertices::insert_original`].
+    pub fn original(&self, vertex: &Handle<Vertex>) -> &Handle<Vertex> {
+        self.originals
+            .get(&vertex.id())
+            .expect("Expected original vertex to be registered")
+    }
+
+    /// Access the vertex at the given position, creating it if necessary
+    pub fn insert(
+        &mut self,
+        position: Point<3>,
+        services: &mut Services,
+    ) -> Handle<Vertex> {
+        self.find(position).unwrap_or_else(|| {
+            let vertex = Vertex::new().insert(services);
+            self.push(vertex.clone(), position);
+            vertex
+        })
+    }
+
+    /// Access the position of a registered vertex
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the vertex has not been registered.
+    pub fn position(&self, vertex: &Handle<Vertex>) -> Point<3> {
+        self.positions
+            .get(&vertex.id())
+            .copied()
+            .expect("Expected vertex to be registered")
+    }
+
+    /// Find all registered vertices that lie within a line segment
+    ///
+    /// Vertices that coincide with the end points of the segment are not
+    /// returned. The vertices are ordered by their position along the segment.
+    pub fn within_segment(
+        &self,
+        segment: [Point<3>; 2],
+    ) -> Vec<Handle<Vertex>> {
+        let [start, end] = segment;
+
+        let direction = end - start;
+        let length = direction.magnitude();
+
+        if length == Scalar::ZERO {
+            return Vec::new();
+        }
+
+        let mut vertices = Vec::new();
+
+        for (vertex, position) in &self.vertices {
+            let t = (*position - start).dot(&direction) / (length * length);
+
+            if t * length <= self.tolerance
+                || (Scalar::ONE - t) * length <= self.tolerance
+            {
+                continue;
+            }
+
+            let projected = start + direction * t;
+            if projected.distance_to(position) < self.tolerance {
+                vertices.push((t, vertex.clone()));
+            }
+        }
+
+        vertices.sort_by_key(|(t, _)| *t);
+        vertices.into_iter().map(|(_, vertex)| vertex).collect()
+    }
+
+    fn find(&self, position: Point<3>) -> Option<Handle<Vertex>> {
+        self.vertices
+            .iter()
+            .find(|(_, p)| p.distance_to(&position) < self.tolerance)
+            .map(|(vertex, _)| vertex.clone())
+    }
+
+    fn push(&mut self, vertex: Handle<Vertex>, position: Point<3>) {
+        self.positions.insert(vertex.id(), position);
+        self.vertices.push((vertex, position));
+    }
+}
+
+/// The curves of the edges that make up the result of a boolean operation
+///
+/// Edges that connect the same vertices must refer to the same curve. This
+/// registry makes sure of that, by keeping track of which curve connects which
+/// vertices.
+#[derive(Default)]
+pub struct Curves {
+    curves: BTreeMap<[ObjectId; 2], (Handle<Curve>, [Point<1>; 2])>,
+}
+
+impl Curves {
+    /// Register the curve of a line segment edge from one of the input solids
+    ///
+    /// `vertices` are the vertices that bound the edge, `boundary` their
+    /// respective positions on the curve.
+    pub fn insert_original(
+        &mut self,
+        vertices: [&Handle<Vertex>; 2],
+        curve: &Handle<Curve>,
+        boundary: [Point<1>; 2],
+    ) {
+        let [a, b] = vertices;
+        if a.id() == b.id() {
+            return;
+        }
+
+        let [t_a, t_b] = boundary;
+        let boundary = if a.id() < b.id() {
+            [t_a, t_b]
+        } else {
+            [t_b, t_a]
+        };
+
+        self.curves
+            .entry(Self::key([a, b]))
+            .or_insert_with(|| (curve.clone(), boundary));
+    }
+
+    /// Access the curve that connects two vertices, creating it if necessary
+    ///
+    /// Returns the curve, together with the positions of the vertices on it.
+    pub fn get_or_insert(
+        &mut self,
+        vertices: [&Handle<Vertex>; 2],
+        services: &mut Services,
+    ) -> (Handle<Curve>, [Point<1>; 2]) {
+        let [a, b] = vertices;
+
+        let (curve, [low, high]) = self
+            .curves
+            .entry(Self::key([a, b]))
+            .or_insert_with(|| {
+                let curve = Curve::new().insert(services);
+                (curve, [[0.], [1.]].map(Point::from))
+            })
+            .clone();
+
+        let boundary = if a.id() < b.id() {
+            [low, high]
+        } else {
+            [high, low]
+        };
+
+        (curve, boundary)
+    }
+
+    fn key([a, b]: [&Handle<Vertex>; 2]) -> [ObjectId; 2] {
+        let [a, b] = [a.id(), b.id()];
+        [a.min(b), a.max(b)]
+    }
+}
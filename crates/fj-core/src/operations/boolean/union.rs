@@ -0,0 +1,274 @@
+//! Union of two solids
+
+use crate::{
+    algorithms::approx::Tolerance, objects::Solid, services::Services,
+    storage::Handle,
+};
+
+use super::{apply, BooleanError, Classification, Operand, Selection};
+
+/// Compute the union of two [`Solid`]s
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait Union: Sized {
+    /// Compute the union of this solid and another
+    ///
+    /// The resulting solid encloses the volume that is enclosed by either of
+    /// the two solids. Where the solids overlap, their faces are split along
+    /// the intersections, and the faces of each solid that are located within
+    /// the other are removed.
+    ///
+    /// Returns an error, if faces need to be split that are not supported. See
+    /// [module documentation].
+    ///
+    /// [module documentation]: super
+    fn union(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BooleanError>;
+}
+
+impl Union for Solid {
+    fn union(
+        &self,
+        other: &Self,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, BooleanError> {
+        apply(
+            [self, other],
+            |operand, classification| match (operand, classification) {
+                (_, Classification::Outside) => Selection::Keep,
+                (Operand::First, Classification::OnSame) => Selection::Keep,
+                _ => Selection::Drop,
+            },
+            tolerance,
+            services,
+        )
+    }
+}
+
+/// Compute the union of any number of [`Solid`]s
+///
+/// This is a convenience wrapper around [`Union`], which is useful for
+/// combining the results of operations that create multiple solids, like
+/// [`Pattern`].
+///
+/// [`Pattern`]: crate::operations::Pattern
+pub trait UnionAll {
+    /// Compute the union of all solids
+    ///
+    /// Returns an error, if any of the unions does. See [`Union::union`].
+    ///
+    /// # Panics
+    ///
+    /// Panics, if there are no solids.
+    fn union_all(
+        self,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Solid, BooleanError>;
+}
+
+impl<I> UnionAll for I
+where
+    I: IntoIterator<Item = Handle<Solid>>,
+{
+    fn union_all(
+        self,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Solid, BooleanError> {
+        let tolerance = tolerance.into();
+        let mut solids = self.into_iter();
+        let first = solids.next().expect("Need at least one solid");
+
+        solids.try_fold(Solid::clone(&first), |union, solid| {
+            union.union(&solid, tolerance, services)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Vector;
+
+    use crate::{
+        algorithms::transform::TransformObject,
+        operations::{
+            boolean::BooleanError,
+            fixtures::{cuboid, num_faces, spacer, tolerance},
+            Insert,
+        },
+        services::Services,
+    };
+
+    use super::Union;
+
+    #[test]
+    fn union_of_disjoint_solids() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([3., 0., 0.], [2., 2., 2.], &mut services);
+
+        let union = a
+            .union(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(union.shells().len(), 2);
+        assert_eq!(num_faces(&union), 12);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn union_of_overlapping_solids() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([1., 1., 1.], [2., 2., 2.], &mut services);
+
+        let union = a
+            .union(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // Each solid contributes the three faces that are completely outside
+        // of the other one, as well as an L-shaped remainder of each of the
+        // other three.
+        assert_eq!(union.shells().len(), 1);
+        assert_eq!(num_faces(&union), 12);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn union_of_solids_with_coplanar_faces() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([1., 0., 0.], [2., 2., 2.], &mut services);
+
+        let union = a
+            .union(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // Where the faces of the two solids coincide, only those of `a` are
+        // kept. So each of the four sides of the combined body is made up of
+        // three faces. Plus the two ends.
+        assert_eq!(union.shells().len(), 1);
+        assert_eq!(num_faces(&union), 14);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn union_with_contained_solid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([0.5, 0.5, 0.5], [1., 1., 1.], &mut services);
+
+        let union = a
+            .union(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(union.shells().len(), 1);
+        assert_eq!(num_faces(&union), 6);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn union_with_protruding_solid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([0.5, 0.5, 1.], [1., 1., 2.], &mut services);
+
+        let union = a
+            .union(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // The top face of `a` has a hole where `b` protrudes, and the bottom
+        // face of `b` is removed.
+        assert_eq!(union.shells().len(), 1);
+        assert_eq!(num_faces(&union), 11);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn union_of_rotated_solids() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([0., 0., 0.], [2., 2., 2.], &mut services)
+            .rotate(Vector::from([1., 2., 3.]).normalize() * 0.5, &mut services)
+            .translate([1., 0.5, 0.7], &mut services);
+
+        let union = a
+            .union(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(union.shells().len(), 1);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn union_of_solids_touching_along_edge() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut services);
+        let b = cuboid([2., 2., 0.], [2., 2., 2.], &mut services);
+
+        let union = a
+            .union(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // Four faces meet at the shared edge. A single shell would not be
+        // manifold there, so each solid ends up in its own shell.
+        assert_eq!(union.shells().len(), 2);
+        assert_eq!(num_faces(&union), 12);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn union_of_round_solids() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // `b` is located in the hole of `a`, without touching it.
+        let a = spacer(3., 2., 1., &mut services);
+        let b = spacer(1., 0.5, 1., &mut services);
+
+        let union = a
+            .union(&b, tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(union.shells().len(), 2);
+        assert_eq!(num_faces(&union), 8);
+
+        // If they overlap, their faces would need to be split along curves.
+        let c = spacer(2.5, 1.5, 1., &mut services);
+        assert!(matches!(
+            a.union(&c, tolerance(), &mut services),
+            Err(BooleanError::CurvedFace | BooleanError::CurvedEdge)
+        ));
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+}
@@ -24,6 +24,16 @@ pub trait BuildCycle {
         Cycle::empty().add_edges([circle])
     }
 
+    /// Build an ellipse
+    fn ellipse(
+        center: impl Into<Point<2>>,
+        radii: [impl Into<Scalar>; 2],
+        services: &mut Services,
+    ) -> Cycle {
+        let ellipse = Edge::ellipse(center, radii, services).insert(services);
+        Cycle::empty().add_edges([ellipse])
+    }
+
     /// Build a polygon
     fn polygon<P, Ps>(points: Ps, services: &mut Services) -> Cycle
     where
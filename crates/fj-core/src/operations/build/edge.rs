@@ -61,6 +61,41 @@ pub trait BuildEdge {
         Edge::unjoined(path, boundary, services)
     }
 
+    /// Create an ellipse
+    ///
+    /// The first radius is measured along the u-axis of the surface, the
+    /// second along the v-axis.
+    fn ellipse(
+        center: impl Into<Point<2>>,
+        radii: [impl Into<Scalar>; 2],
+        services: &mut Services,
+    ) -> Edge {
+        let path = SurfacePath::ellipse_from_center_and_radii(center, radii);
+        let boundary =
+            [Scalar::ZERO, Scalar::TAU].map(|coord| Point::from([coord]));
+
+        Edge::unjoined(path, boundary, services)
+    }
+
+    /// Create a B-spline curve from a degree and control points
+    ///
+    /// The edge spans the whole curve, from the first control point to the
+    /// last. See [`SurfacePath::nurbs_from_control_points`].
+    fn nurbs_from_control_points(
+        degree: usize,
+        control_points: impl IntoIterator<Item = impl Into<Point<2>>>,
+        services: &mut Services,
+    ) -> Edge {
+        let path =
+            SurfacePath::nurbs_from_control_points(degree, control_points);
+        let SurfacePath::Nurbs(curve) = &path else {
+            unreachable!("Just created a NURBS curve")
+        };
+        let boundary = curve.domain().map(|coord| Point::from([coord]));
+
+        Edge::unjoined(path, boundary, services)
+    }
+
     /// Create a line segment
     fn line_segment(
         points_surface: [impl Into<Point<2>>; 2],
@@ -27,6 +27,16 @@ pub trait BuildRegion {
         Region::new(exterior, [], None)
     }
 
+    /// Build an ellipse
+    fn ellipse(
+        center: impl Into<Point<2>>,
+        radii: [impl Into<Scalar>; 2],
+        services: &mut Services,
+    ) -> Region {
+        let exterior = Cycle::ellipse(center, radii, services).insert(services);
+        Region::new(exterior, [], None)
+    }
+
     /// Build a polygon
     fn polygon<P, Ps>(points: Ps, services: &mut Services) -> Region
     where
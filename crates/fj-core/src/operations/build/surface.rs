@@ -1,4 +1,4 @@
-use fj_math::{Point, Scalar};
+use fj_math::{Circle, Cone, NurbsSurface, Point, Scalar, Sphere, Torus};
 
 use crate::{
     geometry::{GlobalPath, SurfaceGeometry},
@@ -16,7 +16,7 @@ pub trait BuildSurface {
         let (u, u_line) = GlobalPath::line_from_points([a, b]);
         let v = c - a;
 
-        let geometry = SurfaceGeometry { u, v };
+        let geometry = SurfaceGeometry::Swept { u, v };
         let surface = Surface::new(geometry);
 
         let points_surface = {
@@ -29,6 +29,58 @@ pub trait BuildSurface {
 
         (surface, points_surface)
     }
+
+    /// Build a sphere from the provided center and radius
+    ///
+    /// The north pole of the sphere points towards positive z. See
+    /// [`SurfaceGeometry::Sphere`] for the coordinate system of the surface.
+    fn sphere_from_center_and_radius(
+        center: impl Into<Point<3>>,
+        radius: impl Into<Scalar>,
+    ) -> Surface {
+        let sphere = Sphere::from_center_and_radius(center, radius);
+        Surface::new(SurfaceGeometry::Sphere(sphere))
+    }
+
+    /// Build a cone from the provided circle and apex
+    ///
+    /// See [`SurfaceGeometry::Cone`] for the coordinate system of the surface.
+    fn cone_from_circle_and_apex(
+        circle: Circle<3>,
+        apex: impl Into<Point<3>>,
+    ) -> Surface {
+        let cone = Cone::new(circle, apex);
+        Surface::new(SurfaceGeometry::Cone(cone))
+    }
+
+    /// Build a torus from the provided center and radii
+    ///
+    /// The axis of the torus points towards positive z. See
+    /// [`SurfaceGeometry::Torus`] for the coordinate system of the surface.
+    fn torus_from_center_and_radii(
+        center: impl Into<Point<3>>,
+        major_radius: impl Into<Scalar>,
+        minor_radius: impl Into<Scalar>,
+    ) -> Surface {
+        let torus =
+            Torus::from_center_and_radii(center, major_radius, minor_radius);
+        Surface::new(SurfaceGeometry::Torus(torus))
+    }
+
+    /// Build a B-spline surface from degrees and a grid of control points
+    ///
+    /// See [`NurbsSurface::from_control_points`] and
+    /// [`SurfaceGeometry::Nurbs`] for the coordinate system of the surface.
+    fn nurbs_from_control_points(
+        degrees: [usize; 2],
+        control_points: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Point<3>>>,
+        >,
+    ) -> Surface {
+        let surface =
+            NurbsSurface::from_control_points(degrees, control_points);
+        Surface::new(SurfaceGeometry::Nurbs(surface.into()))
+    }
 }
 
 impl BuildSurface for Surface {}
@@ -0,0 +1,106 @@
+//! Fixtures for testing operations on solids
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{approx::Tolerance, sweep::Sweep, transform::TransformObject},
+    objects::{Cycle, Edge, Region, Sketch, Solid},
+    operations::{
+        BuildCycle, BuildRegion, BuildSketch, Insert, Reverse, UpdateRegion,
+        UpdateSketch,
+    },
+    services::Services,
+    storage::Handle,
+};
+
+/// Build a prism by sweeping a polygon in the xy-plane along the z-axis
+pub fn prism<const N: usize>(
+    points: [[f64; 2]; N],
+    height: f64,
+    services: &mut Services,
+) -> Handle<Solid> {
+    let sketch = Sketch::empty()
+        .add_region(Region::polygon(points, services).insert(services))
+        .insert(services);
+    let surface = services.objects.surfaces.xy_plane();
+
+    (sketch, surface).sweep([0., 0., height], services)
+}
+
+/// Build a cuboid with the given size, whose corner is at the given offset
+pub fn cuboid(
+    offset: impl Into<Vector<3>>,
+    [x, y, z]: [f64; 3],
+    services: &mut Services,
+) -> Handle<Solid> {
+    prism([[0., 0.], [x, 0.], [x, y], [0., y]], z, services)
+        .translate(offset, services)
+}
+
+/// Build a spacer, a cylinder with a cylindrical hole, around the z-axis
+pub fn spacer(
+    outer: f64,
+    inner: f64,
+    height: f64,
+    services: &mut Services,
+) -> Handle<Solid> {
+    let sketch = Sketch::empty()
+        .add_region(
+            Region::circle(Point::origin(), outer, services)
+                .add_interiors([Cycle::circle(
+                    Point::origin(),
+                    inner,
+                    services,
+                )
+                .reverse(services)
+                .insert(services)])
+                .insert(services),
+        )
+        .insert(services);
+    let surface = services.objects.surfaces.xy_plane();
+
+    (sketch, surface).sweep([0., 0., height], services)
+}
+
+/// Find an edge of the solid by the positions of its vertices
+pub fn find_edge(solid: &Solid, points: [[f64; 3]; 2]) -> Handle<Edge> {
+    let points = points.map(Point::from);
+    let is_close =
+        |a: Point<3>, b: Point<3>| (a - b).magnitude() < Scalar::from(1e-9);
+
+    for shell in solid.shells() {
+        for face in shell.faces() {
+            let surface = face.surface().geometry();
+
+            for cycle in face.region().all_cycles() {
+                for edge in cycle.edges() {
+                    let [start, end] = edge.boundary().inner.map(|t| {
+                        surface.point_from_surface_coords(
+                            edge.path().point_from_path_coords(t),
+                        )
+                    });
+
+                    let is_match = (is_close(start, points[0])
+                        && is_close(end, points[1]))
+                        || (is_close(start, points[1])
+                            && is_close(end, points[0]));
+                    if is_match {
+                        return edge.clone();
+                    }
+                }
+            }
+        }
+    }
+
+    panic!("Solid has no edge between {points:?}")
+}
+
+/// Count the faces of all shells of a solid
+pub fn num_faces(solid: &Solid) -> usize {
+    solid.shells().iter().map(|shell| shell.faces().len()).sum()
+}
+
+/// The tolerance that operations are tested with
+pub fn tolerance() -> Tolerance {
+    Tolerance::from_scalar(1e-6).unwrap()
+}
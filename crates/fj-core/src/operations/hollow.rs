@@ -0,0 +1,486 @@
+//! Hollowing of solids
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_interop::ext::ArrayExt;
+use fj_math::{Point, Scalar, Transform, Vector};
+
+use crate::{
+    algorithms::approx::Tolerance,
+    geometry::SurfacePath,
+    objects::{
+        Curve, Cycle, Edge, Face, Region, Shell, Solid, Surface, Vertex,
+    },
+    services::Services,
+    storage::{Handle, ObjectId},
+};
+
+use super::{Insert, Reverse, UpdateRegion, UpdateShell};
+
+/// Hollow out a [`Solid`]
+pub trait Hollow: Sized {
+    /// Hollow out the solid, leaving walls of the given thickness
+    ///
+    /// Each shell of the solid gets an inner shell, whose faces are offset
+    /// inward from those of the outer shell by the thickness. The faces of the
+    /// inner shell point into the hollow space, which means they face the
+    /// opposite way from the faces they were offset from.
+    ///
+    /// Any of the open faces is removed, along with the inner face that
+    /// would have been offset from it. This opens up the hollow space. Only
+    /// the rim where the walls meet the removed face is left, and the inner
+    /// shell becomes part of the outer one.
+    ///
+    /// Only solids that are bounded by planar faces with straight edges are
+    /// supported. The thickness must be small enough for the offset faces to
+    /// neither vanish nor intersect each other. Planes that are parallel
+    /// within the tolerance are considered the same, when computing where the
+    /// offset faces meet.
+    ///
+    /// Returns an error, if the solid has faces or edges that are not
+    /// supported, or if an open face has holes.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the thickness is not positive, if any of the open faces is
+    /// not part of the solid, or if any two open faces share an edge.
+    fn hollow(
+        &self,
+        thickness: impl Into<Scalar>,
+        open_faces: impl IntoIterator<Item = Handle<Face>>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, HollowError>;
+}
+
+impl Hollow for Solid {
+    fn hollow(
+        &self,
+        thickness: impl Into<Scalar>,
+        open_faces: impl IntoIterator<Item = Handle<Face>>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Self, HollowError> {
+        let thickness = thickness.into();
+        let tolerance = tolerance.into();
+        assert!(thickness > Scalar::ZERO, "Wall thickness must be positive");
+
+        let open_faces = open_faces
+            .into_iter()
+            .map(|face| face.id())
+            .collect::<BTreeSet<_>>();
+        for id in &open_faces {
+            let is_part_of_solid = self
+                .shells()
+                .iter()
+                .flat_map(|shell| shell.faces())
+                .any(|face| face.id() == *id);
+            assert!(is_part_of_solid, "Open faces must be part of the solid");
+        }
+
+        let mut shells = Vec::new();
+        for shell in self.shells() {
+            shells.extend(hollow_shell(
+                shell,
+                thickness,
+                &open_faces,
+                tolerance,
+                services,
+            )?);
+        }
+
+        Ok(Solid::new(shells))
+    }
+}
+
+/// Error hollowing out a [`Solid`]
+///
+/// Returned by [`Hollow::hollow`].
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum HollowError {
+    /// The solid has a face that is not planar
+    #[error("Hollowing solids with round faces is not supported")]
+    RoundFace,
+
+    /// The solid has an edge that is not straight
+    #[error("Hollowing solids with curved edges is not supported")]
+    CurvedEdge,
+
+    /// One of the open faces has holes
+    #[error("Leaving faces with holes open is not supported")]
+    OpenFaceWithHoles,
+
+    /// The offset faces don't meet in a single point at one of the vertices
+    ///
+    /// This is the case, if more than three faces meet at a vertex, and their
+    /// offset planes don't all pass through the same point.
+    #[error(
+        "Hollowing solids is only supported where the offset faces meet in a \
+        single point"
+    )]
+    OffsetFacesDontMeet,
+}
+
+fn hollow_shell(
+    shell: &Handle<Shell>,
+    thickness: Scalar,
+    open_faces: &BTreeSet<ObjectId>,
+    tolerance: Tolerance,
+    services: &mut Services,
+) -> Result<Vec<Handle<Shell>>, HollowError> {
+    let offset_of = |face: &Handle<Face>| {
+        if open_faces.contains(&face.id()) {
+            Scalar::ZERO
+        } else {
+            thickness
+        }
+    };
+
+    // Each vertex is moved, so it ends up on the offset planes of all the
+    // faces it is part of.
+    let mut planes = BTreeMap::new();
+    let mut open_curves = BTreeSet::new();
+
+    for face in shell.faces() {
+        let surface = face.surface().geometry();
+        let normal = face.normal().ok_or(HollowError::RoundFace)?;
+        let offset = offset_of(face);
+
+        if offset == Scalar::ZERO && !face.region().interiors().is_empty() {
+            return Err(HollowError::OpenFaceWithHoles);
+        }
+
+        for cycle in face.region().all_cycles() {
+            for edge in cycle.edges() {
+                if !matches!(edge.path(), SurfacePath::Line(_)) {
+                    return Err(HollowError::CurvedEdge);
+                }
+
+                if offset == Scalar::ZERO {
+                    assert!(
+                        open_curves.insert(edge.curve().id()),
+                        "Open faces must not share an edge"
+                    );
+                }
+
+                let position =
+                    surface.point_from_surface_coords(edge.start_position());
+                let (_, planes) = planes
+                    .entry(edge.start_vertex().id())
+                    .or_insert_with(|| (position, Vec::new()));
+                planes.push((normal, offset));
+            }
+        }
+    }
+
+    let positions = planes
+        .into_iter()
+        .map(|(vertex, (position, planes))| {
+            let displacement = displacement(&planes, tolerance)?;
+            Ok((vertex, position + displacement))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+    let mut offset = Offset {
+        positions,
+        curves: BTreeMap::new(),
+        vertices: BTreeMap::new(),
+    };
+
+    let mut outer = Shell::clone(shell);
+    let mut inner = Vec::new();
+
+    for face in shell.faces() {
+        let offset_face = offset.face(face, offset_of(face), services);
+
+        if open_faces.contains(&face.id()) {
+            // The offset face is located in the same plane as the open face,
+            // within its boundary. What remains of the open face is a rim
+            // between the two.
+            let hole = offset_face
+                .region()
+                .exterior()
+                .reverse(services)
+                .insert(services);
+            let rim = Face::new(
+                face.surface().clone(),
+                face.region().add_interiors([hole]).insert(services),
+            )
+            .insert(services);
+
+            outer = outer.update_face(face, |_| rim);
+        } else {
+            inner.push(offset_face.reverse(services).insert(services));
+        }
+    }
+
+    let is_open = shell
+        .faces()
+        .iter()
+        .any(|face| open_faces.contains(&face.id()));
+
+    let shells = if is_open {
+        vec![outer.add_faces(inner).insert(services)]
+    } else {
+        // If none of the faces are open, the inner shell is separate from the
+        // outer one.
+        vec![shell.clone(), Shell::new(inner).insert(services)]
+    };
+
+    Ok(shells)
+}
+
+/// The objects that make up the offset faces of a shell
+struct Offset {
+    positions: BTreeMap<ObjectId, Point<3>>,
+    curves: BTreeMap<ObjectId, Handle<Curve>>,
+    vertices: BTreeMap<ObjectId, Handle<Vertex>>,
+}
+
+impl Offset {
+    /// Create the offset version of a face
+    ///
+    /// The offset face has the same orientation as the original one. Curves
+    /// and vertices are shared between all offset faces.
+    fn face(
+        &mut self,
+        face: &Face,
+        offset: Scalar,
+        services: &mut Services,
+    ) -> Face {
+        let surface = if offset == Scalar::ZERO {
+            face.surface().clone()
+        } else {
+            let normal = face
+                .normal()
+                .expect("Faces have been checked to be planar before");
+            let translation = Transform::translation(-normal * offset);
+            Surface::new(face.surface().geometry().transform(&translation))
+                .insert(services)
+        };
+        let geometry = surface.geometry();
+
+        let mut cycles = Vec::new();
+        for cycle in face.region().all_cycles() {
+            let mut edges = Vec::new();
+
+            for (edge, next) in cycle.edges().pairs() {
+                let points =
+                    [edge.start_vertex(), next.start_vertex()].map(|vertex| {
+                        geometry
+                            .project_global_point(self.positions[&vertex.id()])
+                    });
+                let path = SurfacePath::line_from_points_with_coords(
+                    edge.boundary().inner.zip_ext(points),
+                );
+
+                let curve = self
+                    .curves
+                    .entry(edge.curve().id())
+                    .or_insert_with(|| Curve::new().insert(services))
+                    .clone();
+                let vertex = self
+                    .vertices
+                    .entry(edge.start_vertex().id())
+                    .or_insert_with(|| Vertex::new().insert(services))
+                    .clone();
+
+                edges.push(
+                    Edge::new(path, edge.boundary(), curve, vertex)
+                        .insert(services),
+                );
+            }
+
+            cycles.push(Cycle::new(edges).insert(services));
+        }
+
+        let mut cycles = cycles.into_iter();
+        let exterior = cycles
+            .next()
+            .expect("A region always has an exterior cycle");
+        let region = Region::new(exterior, cycles, face.region().color())
+            .insert(services);
+
+        Face::new(surface, region)
+    }
+}
+
+/// Compute how far a vertex moves, to end up on all of the offset planes
+///
+/// Expects the normals of the planes that the vertex is on, and how far each
+/// of them is offset against its normal.
+fn displacement(
+    planes: &[(Vector<3>, Scalar)],
+    tolerance: Tolerance,
+) -> Result<Vector<3>, HollowError> {
+    let tolerance = tolerance.inner();
+
+    // Only three planes with independent normals are required to determine
+    // the displacement. Any further planes must agree.
+    let mut independent: Vec<(Vector<3>, Scalar)> = Vec::new();
+    for &(normal, offset) in planes {
+        let is_independent = match independent.as_slice() {
+            [] => true,
+            [(n1, _)] => n1.cross(&normal).magnitude() > tolerance,
+            [(n1, _), (n2, _)] => n1.cross(n2).dot(&normal).abs() > tolerance,
+            _ => false,
+        };
+
+        if is_independent {
+            independent.push((normal, -offset));
+        }
+    }
+
+    let displacement = match independent.as_slice() {
+        [(n, b)] => *n * *b,
+        [(n1, b1), (n2, b2)] => {
+            let cos = n1.dot(n2);
+            let det = Scalar::ONE - cos * cos;
+
+            *n1 * ((*b1 - cos * *b2) / det) + *n2 * ((*b2 - cos * *b1) / det)
+        }
+        [(n1, b1), (n2, b2), (n3, b3)] => {
+            let det = n1.dot(&n2.cross(n3));
+
+            (n2.cross(n3) * *b1 + n3.cross(n1) * *b2 + n1.cross(n2) * *b3) / det
+        }
+        _ => unreachable!("Every vertex is part of at least one face"),
+    };
+
+    for (normal, offset) in planes {
+        if (normal.dot(&displacement) + *offset).abs() > tolerance {
+            return Err(HollowError::OffsetFacesDontMeet);
+        }
+    }
+
+    Ok(displacement)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance, sweep::Sweep, triangulate::Triangulate,
+        },
+        objects::{Face, Region, Sketch, Solid},
+        operations::{
+            fixtures::{prism, tolerance},
+            BuildRegion, BuildSketch, Insert, UpdateSketch,
+        },
+        services::Services,
+        storage::Handle,
+    };
+
+    use super::{Hollow, HollowError};
+
+    #[test]
+    fn hollow_closed_cuboid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+
+        let hollow = cuboid
+            .hollow(0.25, [], tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // The inner shell is separate from the outer one.
+        assert_eq!(hollow.shells().len(), 2);
+        for shell in hollow.shells() {
+            assert_eq!(shell.faces().len(), 6);
+        }
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn hollow_cuboid_with_open_face() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cuboid =
+            prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2., &mut services);
+        let top = find_face(&cuboid, 2.);
+
+        let hollow = cuboid
+            .hollow(0.25, [top], tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // The top face is replaced by a rim, and connects the inner faces to
+        // the outer ones. There's no inner top face.
+        assert_eq!(hollow.shells().len(), 1);
+        assert_eq!(hollow.shells().only().faces().len(), 11);
+
+        let mesh = (&*hollow, Tolerance::from_scalar(0.01)?).triangulate();
+        assert!(mesh.triangles().count() > 0);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn hollow_concave_prism_with_open_face() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let prism = prism(
+            [[0., 0.], [3., 0.], [3., 1.], [1., 1.], [1., 3.], [0., 3.]],
+            1.,
+            &mut services,
+        );
+        let bottom = find_face(&prism, 0.);
+
+        let hollow = prism
+            .hollow(0.2, [bottom], tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(hollow.shells().only().faces().len(), 15);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn hollow_cylinder() {
+        let mut services = Services::new();
+
+        let sketch = Sketch::empty()
+            .add_region(
+                Region::circle([0., 0.], 1., &mut services)
+                    .insert(&mut services),
+            )
+            .insert(&mut services);
+        let surface = services.objects.surfaces.xy_plane();
+        let cylinder = (sketch, surface).sweep([0., 0., 1.], &mut services);
+
+        // Cylinders have both round faces and curved edges. Which of those is
+        // reported depends on which face is checked first.
+        let result = cylinder.hollow(0.25, [], tolerance(), &mut services);
+        assert!(matches!(
+            result,
+            Err(HollowError::RoundFace | HollowError::CurvedEdge)
+        ));
+    }
+
+    /// Find the horizontal face of the solid at the given height
+    fn find_face(solid: &Solid, z: f64) -> Handle<Face> {
+        let is_at_height = |face: &Face| {
+            let surface = face.surface().geometry();
+
+            face.region().exterior().edges().iter().all(|edge| {
+                let position =
+                    surface.point_from_surface_coords(edge.start_position());
+                position.z == Scalar::from(z)
+            })
+        };
+
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .find(|face| is_at_height(face))
+            .expect("Solid has no face at the given height")
+            .clone()
+    }
+}
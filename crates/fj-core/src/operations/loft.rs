@@ -0,0 +1,647 @@
+//! Lofts between profiles
+
+use std::iter;
+
+use fj_interop::mesh::Color;
+use fj_math::{Point, Scalar, Vector, Winding};
+
+use crate::{
+    algorithms::approx::{Approx, Tolerance},
+    objects::{Cycle, Face, Region, Shell, Solid, Surface},
+    services::Services,
+    storage::Handle,
+    validate::ValidationConfig,
+};
+
+use super::{
+    reverse::ReverseCurveCoordinateSystems, BuildCycle, BuildFace, BuildSolid,
+    BuildSurface, Insert, JoinCycle, UpdateCycle, UpdateSolid,
+};
+
+/// Build a loft that connects profiles
+pub trait Loft {
+    /// Build a loft that connects the provided profiles
+    ///
+    /// Each profile is a region within a planar surface. The boundaries of
+    /// consecutive profiles are connected by planar faces, which approximate
+    /// the surface that blends from one boundary into the next. The first and
+    /// the last profile close off the loft at its ends.
+    ///
+    /// The cycles of the profiles are matched by their relative position along
+    /// each cycle. Each cycle starts at the point that best matches the start of
+    /// the corresponding cycle in the previous profile, to keep the loft from
+    /// twisting. Curved edges are approximated within the provided tolerance,
+    /// which means that the profiles end up as polygons in the resulting loft.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if fewer than two profiles are provided, or if the profiles
+    /// don't all have the same number of interior cycles.
+    fn loft(
+        profiles: impl IntoIterator<Item = (Handle<Region>, Handle<Surface>)>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Self;
+}
+
+impl Loft for Shell {
+    fn loft(
+        profiles: impl IntoIterator<Item = (Handle<Region>, Handle<Surface>)>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Self {
+        let profiles = profiles.into_iter().collect::<Vec<_>>();
+        assert!(profiles.len() >= 2, "Loft requires at least two profiles");
+
+        let rings = loft_rings(&profiles, tolerance.into());
+        let last = rings.len() - 1;
+
+        let mut faces = Vec::new();
+
+        // The cap at the start of the loft faces backwards, so its cycles run
+        // through the rings in reverse. Edge `i` of each ring, which connects
+        // its points `i` and `i + 1`, ends up at the opposite index.
+        let (region, surface) = &profiles[0];
+        let cycles = rings[0]
+            .iter()
+            .map(|ring| {
+                let points = iter::once(ring[0])
+                    .chain(ring[1..].iter().rev().copied())
+                    .collect::<Vec<_>>();
+                Cycle::polygon(points, services).insert(services)
+            })
+            .collect::<Vec<_>>();
+        let mut ring_edges = cycles
+            .iter()
+            .zip(&rings[0])
+            .map(|(cycle, ring)| {
+                let num = ring.len();
+                (0..num)
+                    .map(|i| (cycle.clone(), num - 1 - i))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        faces.push(cap(surface, cycles, region.color(), services));
+
+        // The side faces connect each ring to the next. Each of them is joined
+        // to the edges of the faces that have been built before it, so they
+        // all share their curves and vertices.
+        //
+        // Unlike the other distances here, this doesn't depend on the provided
+        // tolerance. A planar face is only valid, if its points are within the
+        // distance that validation considers identical.
+        let max_distance_from_plane =
+            ValidationConfig::default().identical_max_distance / 2.;
+        for k in 0..last {
+            let color = profiles[k].0.color();
+
+            for (c, edges) in ring_edges.iter_mut().enumerate() {
+                let num = rings[k][c].len();
+
+                let mut top_edges = Vec::new();
+                let mut first_left = None;
+                let mut previous_right = None;
+
+                for (i, bottom) in edges.iter().cloned().enumerate() {
+                    let j = (i + 1) % num;
+
+                    let [a, b, b2, a2] =
+                        [(k, i), (k, j), (k + 1, j), (k + 1, i)].map(
+                            |(k, i)| {
+                                profiles[k]
+                                    .1
+                                    .geometry()
+                                    .point_from_surface_coords(rings[k][c][i])
+                            },
+                        );
+
+                    let left = previous_right.take();
+                    let right = if j == 0 { first_left.clone() } else { None };
+
+                    let normal = (b - a).cross(&(a2 - a)).normalize();
+                    let is_planar =
+                        (b2 - a).dot(&normal).abs() <= max_distance_from_plane;
+
+                    let (top, right, left) = if is_planar {
+                        let (surface, [p_a, p_b, p_a2]) =
+                            Surface::plane_from_points([a, b, a2]);
+                        let p_b2 = surface.geometry().project_global_point(b2);
+
+                        let face = Face::polygon(
+                            surface.insert(services),
+                            [p_a, p_b, p_b2, p_a2],
+                            services,
+                        );
+                        let (face, cycle) = side_face(
+                            face,
+                            [(0, Some(bottom)), (1, right), (3, left)],
+                            color,
+                            services,
+                        );
+                        faces.push(face);
+
+                        ((cycle.clone(), 2), (cycle.clone(), 1), (cycle, 3))
+                    } else {
+                        // The boundaries don't form a planar quadrilateral, so
+                        // we need to split it into two triangles.
+                        let lower = Face::triangle([a, b, b2], services).face;
+                        let (lower, lower_cycle) = side_face(
+                            lower,
+                            [(0, Some(bottom)), (1, right)],
+                            color,
+                            services,
+                        );
+                        faces.push(lower);
+
+                        let upper = Face::triangle([a, b2, a2], services).face;
+                        let (upper, upper_cycle) = side_face(
+                            upper,
+                            [(0, Some((lower_cycle.clone(), 2))), (2, left)],
+                            color,
+                            services,
+                        );
+                        faces.push(upper);
+
+                        (
+                            (upper_cycle.clone(), 1),
+                            (lower_cycle, 1),
+                            (upper_cycle, 2),
+                        )
+                    };
+
+                    if i == 0 {
+                        first_left = Some(left);
+                    }
+                    previous_right = Some(right);
+                    top_edges.push(top);
+                }
+
+                *edges = top_edges;
+            }
+        }
+
+        // The cap at the end of the loft faces forward, so its cycles run
+        // through the rings in their original direction.
+        let (region, surface) = &profiles[last];
+        let cycles = rings[last]
+            .iter()
+            .zip(ring_edges)
+            .map(|(ring, edges)| {
+                let cycle = Cycle::polygon(ring.iter().copied(), services);
+                join(&cycle, edges.into_iter().enumerate(), services)
+            })
+            .collect::<Vec<_>>();
+        faces.push(cap(surface, cycles, region.color(), services));
+
+        Shell::new(faces)
+    }
+}
+
+impl Loft for Solid {
+    fn loft(
+        profiles: impl IntoIterator<Item = (Handle<Region>, Handle<Surface>)>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Self {
+        let shell = Shell::loft(profiles, tolerance, services).insert(services);
+        Solid::empty().add_shells([shell])
+    }
+}
+
+/// An edge of a cycle, identified by its index
+type EdgeRef = (Handle<Cycle>, usize);
+
+/// Join the edges at the provided indices to the provided edges of other cycles
+///
+/// The edges of neighboring faces run in opposite directions. To make their
+/// curve coordinate systems match, the ones of the joined edges are reversed.
+fn join(
+    cycle: &Cycle,
+    joins: impl IntoIterator<Item = (usize, EdgeRef)>,
+    services: &mut Services,
+) -> Handle<Cycle> {
+    joins
+        .into_iter()
+        .fold(cycle.clone(), |cycle, (index, (other, index_other))| {
+            cycle
+                .update_edge(cycle.edges().nth_circular(index), |edge| {
+                    edge.reverse_curve_coordinate_systems(services)
+                        .insert(services)
+                })
+                .join_to(
+                    &other,
+                    index..=index,
+                    index_other..=index_other,
+                    services,
+                )
+        })
+        .insert(services)
+}
+
+/// Join a side face to the faces next to it, that have been built already
+///
+/// Returns the side face, along with its exterior cycle, which later faces are
+/// joined to.
+fn side_face(
+    face: Face,
+    joins: impl IntoIterator<Item = (usize, Option<EdgeRef>)>,
+    color: Option<Color>,
+    services: &mut Services,
+) -> (Handle<Face>, Handle<Cycle>) {
+    let joins = joins
+        .into_iter()
+        .filter_map(|(index, other)| Some((index, other?)));
+    let exterior = join(face.region().exterior(), joins, services);
+
+    let region = Region::new(exterior.clone(), [], color).insert(services);
+    let face = Face::new(face.surface().clone(), region).insert(services);
+
+    (face, exterior)
+}
+
+/// Build a cap that closes off the loft at one of its ends
+fn cap(
+    surface: &Handle<Surface>,
+    cycles: Vec<Handle<Cycle>>,
+    color: Option<Color>,
+    services: &mut Services,
+) -> Handle<Face> {
+    let mut cycles = cycles.into_iter();
+    let exterior = cycles.next().expect("Region has exterior cycle");
+    let region = Region::new(exterior, cycles, color).insert(services);
+
+    Face::new(surface.clone(), region).insert(services)
+}
+
+/// Compute the rings of points that a loft connects
+///
+/// Returns the points of each cycle of each profile, in surface coordinates.
+/// The rings of corresponding cycles have the same number of points, and they
+/// are oriented such that they run counter-clockwise, when viewed from behind
+/// the loft.
+fn loft_rings(
+    profiles: &[(Handle<Region>, Handle<Surface>)],
+    tolerance: Tolerance,
+) -> Vec<Vec<Vec<Point<2>>>> {
+    let num_cycles = profiles[0].0.all_cycles().count();
+    assert!(
+        profiles
+            .iter()
+            .all(|(region, _)| region.all_cycles().count() == num_cycles),
+        "Profiles of a loft must have the same number of interior cycles"
+    );
+
+    let mut rings = profiles
+        .iter()
+        .map(|(region, surface)| {
+            region
+                .all_cycles()
+                .map(|cycle| {
+                    let mut points = (&**cycle, &**surface)
+                        .approx(tolerance)
+                        .points()
+                        .into_iter()
+                        .map(|point| point.local_form)
+                        .collect::<Vec<_>>();
+
+                    // The approximation of a cycle is closed, which means the
+                    // first point is repeated at the end.
+                    points.pop();
+
+                    points
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // All rings need to run in the same direction, relative to the direction
+    // of the loft. The direction of each ring is determined by the front side
+    // of its profile. Where that faces backwards, we reverse the ring, while
+    // keeping its first point in place.
+    let centers = profiles
+        .iter()
+        .zip(&rings)
+        .map(|((_, surface), profile)| {
+            profile
+                .iter()
+                .map(|ring| center(ring, surface))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for (k, ((region, surface), profile)) in
+        profiles.iter().zip(&mut rings).enumerate()
+    {
+        let Some(normal) = surface.geometry().plane_normal() else {
+            todo!("Lofting between profiles in round surfaces is not supported")
+        };
+
+        let normal = match region.exterior().winding() {
+            Winding::Ccw => normal,
+            Winding::Cw => -normal,
+        };
+        let direction = if k + 1 < centers.len() {
+            centers[k + 1][0] - centers[k][0]
+        } else {
+            centers[k][0] - centers[k - 1][0]
+        };
+
+        if normal.dot(&direction) < Scalar::ZERO {
+            for ring in profile {
+                ring[1..].reverse();
+            }
+        }
+    }
+
+    // If the first points of corresponding rings are located in different
+    // directions from their centers, the loft would twist. Let's start each
+    // ring at the point that best matches the start of the previous ring.
+    for k in 1..rings.len() {
+        for c in 0..num_cycles {
+            let previous = {
+                let start = profiles[k - 1]
+                    .1
+                    .geometry()
+                    .point_from_surface_coords(rings[k - 1][c][0]);
+                (start - centers[k - 1][c]).normalize()
+            };
+
+            let surface = profiles[k].1.geometry();
+            let ring = &mut rings[k][c];
+
+            let start = ring
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, &point)| {
+                    let direction = surface.point_from_surface_coords(point)
+                        - centers[k][c];
+                    direction.normalize().dot(&previous)
+                })
+                .map(|(i, _)| i)
+                .unwrap_or_default();
+
+            ring.rotate_left(start);
+        }
+    }
+
+    // The rings of corresponding cycles can have different numbers of points,
+    // in different places. We parametrize each ring by its relative length,
+    // then sample all rings at the parameters of all of their points. That
+    // way, the points of all rings are preserved.
+    let mut resampled = vec![Vec::new(); rings.len()];
+    for c in 0..num_cycles {
+        let mut params = Vec::new();
+        let mut all_params = Vec::new();
+        let mut min_length = Scalar::MAX;
+
+        for ((_, surface), profile) in profiles.iter().zip(&rings) {
+            let ring = &profile[c];
+            let surface = surface.geometry();
+
+            let mut lengths = vec![Scalar::ZERO];
+            for (i, &point) in ring.iter().enumerate() {
+                let next = ring[(i + 1) % ring.len()];
+                let distance = (surface.point_from_surface_coords(next)
+                    - surface.point_from_surface_coords(point))
+                .magnitude();
+
+                lengths.push(lengths[i] + distance);
+            }
+
+            let length = lengths[ring.len()];
+            min_length = min_length.min(length);
+
+            let ring_params = lengths
+                .into_iter()
+                .map(|partial| partial / length)
+                .collect::<Vec<_>>();
+
+            all_params.extend_from_slice(&ring_params[..ring.len()]);
+            params.push(ring_params);
+        }
+
+        // Points that are closer to each other than the tolerance are merged.
+        // Keeping both wouldn't make the loft more accurate, and they could
+        // result in edges that are too short to be valid.
+        all_params.sort();
+        all_params.dedup_by(|param, prev| {
+            (*param - *prev) * min_length < tolerance.inner()
+        });
+
+        for ((profile, ring_params), resampled) in
+            rings.iter().zip(&params).zip(&mut resampled)
+        {
+            let ring = &profile[c];
+
+            let points = all_params
+                .iter()
+                .map(|&param| {
+                    let i = ring_params
+                        .partition_point(|&p| p <= param)
+                        .saturating_sub(1)
+                        .min(ring.len() - 1);
+
+                    let [a, b] = [ring[i], ring[(i + 1) % ring.len()]];
+                    let t = (param - ring_params[i])
+                        / (ring_params[i + 1] - ring_params[i]);
+
+                    a + (b - a) * t
+                })
+                .collect();
+
+            resampled.push(points);
+        }
+    }
+
+    resampled
+}
+
+/// Compute the center of a ring of points
+fn center(ring: &[Point<2>], surface: &Surface) -> Point<3> {
+    let sum = ring
+        .iter()
+        .map(|&point| {
+            surface.geometry().point_from_surface_coords(point).coords
+        })
+        .fold(Vector::from([0., 0., 0.]), |sum, point| sum + point);
+
+    Point {
+        coords: sum / ring.len() as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use fj_math::{Scalar, Vector};
+
+    use crate::{
+        algorithms::{approx::Tolerance, transform::TransformObject},
+        objects::{Cycle, Region, Shell},
+        operations::{BuildCycle, BuildRegion, Insert, UpdateRegion},
+        services::Services,
+    };
+
+    use super::Loft;
+
+    #[test]
+    fn loft_between_similar_profiles() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let bottom = Region::polygon(
+            [[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]],
+            &mut services,
+        )
+        .insert(&mut services);
+        let top = Region::polygon(
+            [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]],
+            &mut services,
+        )
+        .insert(&mut services);
+
+        let xy_plane = services.objects.surfaces.xy_plane();
+        let shell = Shell::loft(
+            [
+                (bottom, xy_plane.clone()),
+                (top, xy_plane.translate([0., 0., 1.], &mut services)),
+            ],
+            Tolerance::from_scalar(0.01)?,
+            &mut services,
+        )
+        .insert(&mut services);
+
+        // A frustum: The corresponding edges are parallel, so each pair of
+        // them is connected by a single face.
+        assert_eq!(shell.faces().len(), 6);
+
+        // Neighboring faces share their curves and vertices, so there's one of
+        // each per edge and corner of the frustum.
+        assert_eq!(curves_and_vertices(&shell), [12, 8]);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn loft_from_square_to_circle() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let square = Region::polygon(
+            [[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]],
+            &mut services,
+        )
+        .insert(&mut services);
+        let circle =
+            Region::circle([0., 0.], 1., &mut services).insert(&mut services);
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        let xy_plane = services.objects.surfaces.xy_plane();
+        let shell = Shell::loft(
+            [
+                (square, xy_plane.clone()),
+                (circle, xy_plane.translate([0., 0., 2.], &mut services)),
+            ],
+            tolerance,
+            &mut services,
+        )
+        .insert(&mut services);
+
+        // The circle is approximated by 23 points. Both rings start at the
+        // same relative position, so the other three corners of the square
+        // add to that, for 26 points per ring.
+        let [bottom, top] = [0, shell.faces().len() - 1]
+            .map(|i| shell.faces().nth(i).unwrap().region().exterior());
+        assert_eq!(bottom.edges().len(), 26);
+        assert_eq!(top.edges().len(), 26);
+
+        // Of the quadrilaterals that connect both rings, only one is planar.
+        // The others are split into two triangles each.
+        assert_eq!(shell.faces().len(), 2 + 1 + 25 * 2);
+
+        // The edges of both rings, those that connect them, and those that
+        // split the quadrilaterals.
+        assert_eq!(curves_and_vertices(&shell), [26 * 3 + 25, 26 * 2]);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn loft_between_profiles_with_holes() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let xy_plane = services.objects.surfaces.xy_plane();
+        let surfaces = [
+            xy_plane.clone(),
+            xy_plane
+                .clone()
+                .rotate(Vector::unit_x() * Scalar::from(0.3), &mut services)
+                .translate([0., 0., 2.], &mut services),
+            // Facing the other way, which the loft needs to compensate for.
+            xy_plane
+                .rotate(Vector::unit_x() * Scalar::PI, &mut services)
+                .translate([0., 1., 4.], &mut services),
+        ];
+
+        let profiles = surfaces.map(|surface| {
+            let region = Region::polygon(
+                [[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]],
+                &mut services,
+            )
+            .add_interiors([Cycle::polygon(
+                [[-0.5, -0.5], [-0.5, 0.5], [0.5, 0.5], [0.5, -0.5]],
+                &mut services,
+            )
+            .insert(&mut services)])
+            .insert(&mut services);
+
+            (region, surface)
+        });
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let shell = Shell::loft(profiles, tolerance, &mut services)
+            .insert(&mut services);
+
+        // Each of the three profiles has two rings of four points. All
+        // quadrilaterals that connect them are planar.
+        let [bottom, top] = [0, shell.faces().len() - 1]
+            .map(|i| shell.faces().nth(i).unwrap().region());
+        assert_eq!(bottom.interiors().len(), 1);
+        assert_eq!(top.interiors().len(), 1);
+        assert_eq!(shell.faces().len(), 2 + 2 * (4 + 4));
+
+        // The edges of all rings, and those that connect consecutive ones.
+        assert_eq!(curves_and_vertices(&shell), [3 * 8 + 2 * 8, 3 * 8]);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    /// Count the curves and vertices of a shell
+    ///
+    /// Also checks that each curve is referenced by two edges, as it's shared
+    /// between neighboring faces.
+    fn curves_and_vertices(shell: &Shell) -> [usize; 2] {
+        let edges = shell
+            .faces()
+            .iter()
+            .flat_map(|face| face.region().all_cycles())
+            .flat_map(|cycle| cycle.edges().iter().cloned())
+            .collect::<Vec<_>>();
+
+        let mut curves = BTreeMap::new();
+        for edge in &edges {
+            *curves.entry(edge.curve().id()).or_insert(0) += 1;
+        }
+        assert!(curves.values().all(|&num| num == 2));
+
+        let vertices = edges
+            .iter()
+            .map(|edge| edge.start_vertex().id())
+            .collect::<BTreeSet<_>>();
+
+        [curves.len(), vertices.len()]
+    }
+}
@@ -0,0 +1,238 @@
+//! Mirroring of objects
+
+use std::collections::BTreeMap;
+
+use fj_math::{Circle, Ellipse, Line, Plane, Point, Transform, Vector};
+
+use crate::{
+    algorithms::transform::TransformObject,
+    geometry::SurfacePath,
+    objects::{Curve, Cycle, Edge, Region, Shell, Sketch, Solid, Vertex},
+    services::Services,
+    storage::ObjectId,
+};
+
+use super::{Insert, Reverse};
+
+/// Mirror an object
+///
+/// Mirroring reverses the handedness of the object's coordinate systems. Left
+/// alone, this would turn the object inside out: Faces would point to the
+/// inside of a solid, and the exterior cycles of regions would wind the wrong
+/// way. The mirrored object is reversed to compensate, so its faces and
+/// regions keep the same orientation as those of the original object.
+pub trait Mirror {
+    /// The geometry that the object is mirrored across
+    ///
+    /// This is a plane for objects that are defined in 3D space, and a line
+    /// for objects that are defined in surface coordinates, like sketches.
+    type Across;
+
+    /// Mirror the object
+    #[must_use]
+    fn mirror(&self, across: Self::Across, services: &mut Services) -> Self;
+}
+
+impl Mirror for Solid {
+    type Across = Plane;
+
+    fn mirror(&self, plane: Plane, services: &mut Services) -> Self {
+        let shells = self
+            .shells()
+            .iter()
+            .map(|shell| shell.mirror(plane, services).insert(services))
+            .collect::<Vec<_>>();
+
+        Solid::new(shells)
+    }
+}
+
+impl Mirror for Shell {
+    type Across = Plane;
+
+    fn mirror(&self, plane: Plane, services: &mut Services) -> Self {
+        let origin = plane.origin().coords;
+        let transform = Transform::translation(origin)
+            * Transform::reflection(plane.normal())
+            * Transform::translation(-origin);
+
+        let mirrored = self.clone().transform(&transform, services);
+        let faces = mirrored
+            .faces()
+            .iter()
+            .map(|face| face.reverse(services).insert(services))
+            .collect::<Vec<_>>();
+
+        Shell::new(faces)
+    }
+}
+
+impl Mirror for Sketch {
+    type Across = Line<2>;
+
+    fn mirror(&self, line: Line<2>, services: &mut Services) -> Self {
+        let direction = line.direction().normalize();
+        let mirror_vector = |vector: Vector<2>| {
+            direction * vector.dot(&direction) * 2. - vector
+        };
+        let mirror_point = |point: Point<2>| {
+            line.origin() + mirror_vector(point - line.origin())
+        };
+
+        // Curves and vertices that are shared within the sketch, need to be
+        // shared within the mirrored sketch too.
+        let mut curves: BTreeMap<ObjectId, _> = BTreeMap::new();
+        let mut vertices: BTreeMap<ObjectId, _> = BTreeMap::new();
+
+        let mut regions = Vec::new();
+        for region in self.regions() {
+            let mut cycles = Vec::new();
+
+            for cycle in region.all_cycles() {
+                let mut edges = Vec::new();
+
+                for edge in cycle.edges() {
+                    let path = match edge.path() {
+                        SurfacePath::Circle(circle) => {
+                            SurfacePath::Circle(Circle::new(
+                                mirror_point(circle.center()),
+                                mirror_vector(circle.a()),
+                                mirror_vector(circle.b()),
+                            ))
+                        }
+                        SurfacePath::Ellipse(ellipse) => {
+                            SurfacePath::Ellipse(Ellipse::new(
+                                mirror_point(ellipse.center()),
+                                mirror_vector(ellipse.a()),
+                                mirror_vector(ellipse.b()),
+                            ))
+                        }
+                        SurfacePath::Nurbs(curve) => SurfacePath::Nurbs(
+                            curve.map_control_points(mirror_point).into(),
+                        ),
+                        SurfacePath::Line(line) => {
+                            SurfacePath::Line(Line::from_origin_and_direction(
+                                mirror_point(line.origin()),
+                                mirror_vector(line.direction()),
+                            ))
+                        }
+                    };
+                    let curve = curves
+                        .entry(edge.curve().id())
+                        .or_insert_with(|| Curve::new().insert(services))
+                        .clone();
+                    let vertex = vertices
+                        .entry(edge.start_vertex().id())
+                        .or_insert_with(|| Vertex::new().insert(services))
+                        .clone();
+
+                    edges.push(
+                        Edge::new(path, edge.boundary(), curve, vertex)
+                            .insert(services),
+                    );
+                }
+
+                cycles.push(Cycle::new(edges).insert(services));
+            }
+
+            let mut cycles = cycles.into_iter();
+            let exterior = cycles
+                .next()
+                .expect("A region always has an exterior cycle");
+            let mirrored = Region::new(exterior, cycles, region.color());
+
+            regions.push(mirrored.reverse(services).insert(services));
+        }
+
+        Sketch::new(regions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Line, Plane, Point, Vector, Winding};
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance, sweep::Sweep, triangulate::Triangulate,
+        },
+        objects::{Region, Sketch, Solid},
+        operations::{BuildRegion, BuildSketch, Insert, UpdateSketch},
+        services::Services,
+        storage::Handle,
+    };
+
+    use super::Mirror;
+
+    #[test]
+    fn mirror_solid() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let sketch = wedge(&mut services);
+        let solid = (sketch, services.objects.surfaces.xy_plane())
+            .sweep([0., 0., 1.], &mut services);
+
+        let plane = Plane::from_parametric(
+            Point::from([-1., 0., 0.]),
+            Vector::unit_y(),
+            Vector::unit_z(),
+        );
+        let mirrored = solid.mirror(plane, &mut services).insert(&mut services);
+
+        // If the faces weren't reversed, the mirrored solid would be inside
+        // out, and its volume negative.
+        let volume = volume(&mirrored)?;
+        assert!(volume > 0.);
+        assert!((volume - self::volume(&solid)?).abs() < 1e-12);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn mirror_sketch() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let sketch = wedge(&mut services);
+        let line = Line::from_origin_and_direction(
+            Point::from([0., 1.]),
+            Vector::from([1., 1.]),
+        );
+        let mirrored = sketch.mirror(line, &mut services).insert(&mut services);
+
+        for region in mirrored.regions() {
+            assert_eq!(region.exterior().winding(), Winding::Ccw);
+        }
+
+        let solid = (mirrored, services.objects.surfaces.xy_plane())
+            .sweep([0., 0., 1.], &mut services);
+        assert!(volume(&solid)? > 0.);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    /// A sketch of a right triangle, which is not symmetric to the planes and
+    /// lines it is mirrored across
+    fn wedge(services: &mut Services) -> Handle<Sketch> {
+        Sketch::empty()
+            .add_region(
+                Region::polygon([[0., 0.], [2., 0.], [0., 1.]], services)
+                    .insert(services),
+            )
+            .insert(services)
+    }
+
+    /// Compute the volume of a solid, which is negative if it's inside out
+    fn volume(solid: &Solid) -> anyhow::Result<f64> {
+        let mesh = (solid, Tolerance::from_scalar(0.01)?).triangulate();
+
+        Ok(mesh
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points();
+                a.coords.dot(&b.coords.cross(&c.coords)).into_f64() / 6.
+            })
+            .sum())
+    }
+}
@@ -1,13 +1,34 @@
 //! Operations to update shapes
 
+mod blend;
+mod boolean;
 mod build;
+mod hollow;
 mod insert;
 mod join;
+mod loft;
 mod merge;
+mod mirror;
+mod pattern;
 mod reverse;
+mod thread;
 mod update;
 
+#[cfg(test)]
+pub(crate) mod fixtures;
+
 pub use self::{
+    blend::{
+        chamfer::{Chamfer, ChamferSize},
+        fillet::Fillet,
+        BlendError,
+    },
+    boolean::{
+        difference::Difference,
+        intersection::Intersection,
+        union::{Union, UnionAll},
+        BooleanError,
+    },
     build::{
         cycle::BuildCycle,
         edge::BuildEdge,
@@ -18,10 +39,15 @@ pub use self::{
         solid::{BuildSolid, Tetrahedron},
         surface::BuildSurface,
     },
+    hollow::{Hollow, HollowError},
     insert::{Insert, IsInserted, IsInsertedNo, IsInsertedYes},
     join::cycle::JoinCycle,
+    loft::Loft,
     merge::Merge,
+    mirror::Mirror,
+    pattern::Pattern,
     reverse::Reverse,
+    thread::{IsoMetricThread, ThreadError, ThreadKind},
     update::{
         cycle::UpdateCycle, edge::UpdateEdge, face::UpdateFace,
         region::UpdateRegion, shell::UpdateShell, sketch::UpdateSketch,
@@ -0,0 +1,207 @@
+//! Regular patterns of copies of objects
+
+use fj_math::{Line, Scalar, Transform, Vector};
+
+use crate::{
+    algorithms::transform::{TransformCache, TransformObject},
+    services::Services,
+    storage::Handle,
+};
+
+use super::Insert;
+
+/// Create copies of an object, arranged in a regular pattern
+///
+/// Each copy is created by transforming the previous one, which means all of
+/// them are created by the same transform. They share a single
+/// [`TransformCache`], which maps each object to its transformed version.
+/// Objects that are shared within the original object, like the curves and
+/// vertices of adjacent faces, are therefore shared within each copy too. The
+/// copies don't share any objects with each other.
+///
+/// The copies are returned as separate objects. To combine copies of a solid
+/// into one body, use [`Merge`] or [`UnionAll`].
+///
+/// [`Merge`]: super::Merge
+/// [`UnionAll`]: super::UnionAll
+pub trait Pattern: Sized {
+    /// Create copies of the object along a straight line
+    ///
+    /// Returns `count` objects, starting with the original one. Each further
+    /// copy is translated by `offset` relative to the previous one.
+    fn linear_pattern(
+        &self,
+        count: usize,
+        offset: impl Into<Vector<3>>,
+        services: &mut Services,
+    ) -> Vec<Self>;
+
+    /// Create copies of the object around an axis
+    ///
+    /// Returns `count` objects, starting with the original one. Each further
+    /// copy is rotated by `angle` around the axis relative to the previous
+    /// one. The direction of the axis and the sign of the angle define the
+    /// direction of the rotation, according to the right-hand rule.
+    ///
+    /// To spread the copies evenly around the axis, pass a full turn divided
+    /// by `count` as the angle.
+    fn circular_pattern(
+        &self,
+        count: usize,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        services: &mut Services,
+    ) -> Vec<Self>;
+}
+
+impl<T> Pattern for Handle<T>
+where
+    T: Clone + Insert<Inserted = Handle<T>> + TransformObject + 'static,
+{
+    fn linear_pattern(
+        &self,
+        count: usize,
+        offset: impl Into<Vector<3>>,
+        services: &mut Services,
+    ) -> Vec<Self> {
+        let offset = offset.into();
+
+        copies(self, count, &Transform::translation(offset), services)
+    }
+
+    fn circular_pattern(
+        &self,
+        count: usize,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        services: &mut Services,
+    ) -> Vec<Self> {
+        let angle = angle.into();
+        let direction = axis.direction().normalize();
+
+        let transform = Transform::translation(axis.origin().coords)
+            * Transform::rotation(direction * angle)
+            * Transform::translation(-axis.origin().coords);
+
+        copies(self, count, &transform, services)
+    }
+}
+
+fn copies<T>(
+    original: &Handle<T>,
+    count: usize,
+    transform: &Transform,
+    services: &mut Services,
+) -> Vec<Handle<T>>
+where
+    T: Clone + Insert<Inserted = Handle<T>> + TransformObject + 'static,
+{
+    let mut cache = TransformCache::default();
+    let mut copies: Vec<Handle<T>> = Vec::new();
+
+    for _ in 0..count {
+        let copy = match copies.last() {
+            Some(previous) => previous
+                .clone()
+                .transform_with_cache(transform, services, &mut cache),
+            None => original.clone(),
+        };
+
+        copies.push(copy);
+    }
+
+    copies
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Line, Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::sweep::Sweep,
+        objects::{Region, Sketch, Solid},
+        operations::{
+            fixtures::tolerance, BuildRegion, BuildSketch, Insert, Merge,
+            UnionAll, UpdateSketch,
+        },
+        services::Services,
+        storage::Handle,
+    };
+
+    use super::Pattern;
+
+    #[test]
+    fn linear_pattern() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cube = cube(&mut services);
+        let copies = cube.linear_pattern(3, [2., 0., 0.], &mut services);
+
+        assert_eq!(copies.len(), 3);
+        assert_eq!(copies[0].id(), cube.id());
+
+        let merged = copies
+            .iter()
+            .skip(1)
+            .fold(Solid::clone(&cube), |merged, copy| merged.merge(copy))
+            .insert(&mut services);
+        assert_eq!(merged.shells().len(), 3);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn linear_pattern_with_union() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        // The copies overlap, so their union is a single body.
+        let cube = cube(&mut services);
+        let union = cube
+            .linear_pattern(3, [0.5, 0., 0.], &mut services)
+            .union_all(tolerance(), &mut services)?
+            .insert(&mut services);
+
+        assert_eq!(union.shells().len(), 1);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn circular_pattern() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let cube = cube(&mut services);
+        let axis = Line::from_origin_and_direction(
+            Point::from([-1., 0., 0.]),
+            Vector::unit_z(),
+        );
+        let union = cube
+            .circular_pattern(4, axis, Scalar::TAU / 4., &mut services)
+            .union_all(tolerance(), &mut services)?
+            .insert(&mut services);
+
+        // The copies are located on the four sides of the axis, without
+        // touching each other.
+        assert_eq!(union.shells().len(), 4);
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    fn cube(services: &mut Services) -> Handle<Solid> {
+        let sketch = Sketch::empty()
+            .add_region(
+                Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    services,
+                )
+                .insert(services),
+            )
+            .insert(services);
+        let surface = services.objects.surfaces.xy_plane();
+
+        (sketch, surface).sweep([0., 0., 1.], services)
+    }
+}
@@ -0,0 +1,764 @@
+//! ISO metric screw threads
+
+use std::iter;
+
+use fj_math::{Helix, Line, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::Tolerance,
+        sweep::{Screw, SweepAlongHelix},
+        transform::TransformObject,
+    },
+    geometry::{SurfaceGeometry, SurfacePath},
+    objects::{
+        Curve, Cycle, Edge, Face, Region, Shell, Sketch, Solid, Surface, Vertex,
+    },
+    services::Services,
+    storage::Handle,
+};
+
+use super::{BuildRegion, BuildSketch, Insert, UpdateSketch};
+
+/// An ISO metric screw thread, as defined by ISO 68-1
+///
+/// The thread is right-handed, and its axis is the z-axis. It uses the basic
+/// profile, without any tolerances applied.
+///
+/// A thread is built as a separate solid, which consists of the ridge of the
+/// thread only. Boolean operations don't support curved faces yet, so the
+/// thread can't be combined with the body it belongs to. Instead, the body is
+/// expected to be shaped such that the thread touches it:
+///
+/// - An external thread, like on a bolt, sits on a cylinder whose diameter is
+///   the [minor diameter](Self::minor_diameter).
+/// - An internal thread, like in a nut, lines a hole whose diameter is the
+///   nominal [diameter](Self::diameter).
+///
+/// For external threads, [`IsoMetricThread::build_on`] builds the thread along
+/// with its body instead, as a single shell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct IsoMetricThread {
+    diameter: Scalar,
+    pitch: Scalar,
+    kind: ThreadKind,
+}
+
+impl IsoMetricThread {
+    /// Construct an `IsoMetricThread`
+    ///
+    /// The diameter is the nominal (major) diameter of the thread, so an M6
+    /// thread has a diameter of `6.`. The pitch is the distance between
+    /// consecutive turns.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the pitch is not positive, or if it is too large for the
+    /// diameter, leaving the thread without a positive minor diameter.
+    pub fn new(
+        diameter: impl Into<Scalar>,
+        pitch: impl Into<Scalar>,
+        kind: ThreadKind,
+    ) -> Self {
+        let thread = Self {
+            diameter: diameter.into(),
+            pitch: pitch.into(),
+            kind,
+        };
+
+        assert!(thread.pitch > Scalar::ZERO, "Thread pitch must be positive");
+        assert!(
+            thread.minor_diameter() > Scalar::ZERO,
+            "Thread pitch is too large for its diameter"
+        );
+
+        thread
+    }
+
+    /// Access the nominal diameter of the thread
+    pub fn diameter(&self) -> Scalar {
+        self.diameter
+    }
+
+    /// Access the pitch of the thread
+    pub fn pitch(&self) -> Scalar {
+        self.pitch
+    }
+
+    /// Access the kind of the thread
+    pub fn kind(&self) -> ThreadKind {
+        self.kind
+    }
+
+    /// Compute the height of the fundamental triangle of the thread profile
+    pub fn fundamental_height(&self) -> Scalar {
+        Scalar::from(3.).sqrt() / 2. * self.pitch
+    }
+
+    /// Compute the minor diameter of the thread
+    pub fn minor_diameter(&self) -> Scalar {
+        self.diameter - self.fundamental_height() * 5. / 4.
+    }
+
+    /// Build the thread as a solid of the given length
+    ///
+    /// The thread starts at `z = 0` and extends toward positive z. The length
+    /// doesn't need to be a multiple of the pitch. The ridge just ends where
+    /// the sweep along the helix does, without being cut off square.
+    ///
+    /// The surfaces of the thread are approximated within the given tolerance.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length is not positive.
+    pub fn build(
+        &self,
+        length: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Handle<Solid> {
+        let length = length.into();
+        assert!(length > Scalar::ZERO, "Thread length must be positive");
+
+        let major = self.diameter / 2.;
+        let minor = self.minor_diameter() / 2.;
+        let p = self.pitch;
+
+        // The profile is defined in the xz-plane, in terms of radius and
+        // height. Its flanks are at 30 degrees to the radial direction. The
+        // crest of the ridge, where it's farthest from its root, is as wide as
+        // the basic profile defines for that diameter. The root is as wide as
+        // the remainder of the pitch, minus the crest of the mating thread.
+        let profile = match self.kind {
+            ThreadKind::External => [
+                [minor, Scalar::ZERO],
+                [major, p * 5. / 16.],
+                [major, p * 7. / 16.],
+                [minor, p * 3. / 4.],
+            ],
+            ThreadKind::Internal => [
+                [major, Scalar::ZERO],
+                [major, p * 7. / 8.],
+                [minor, p * 9. / 16.],
+                [minor, p * 5. / 16.],
+            ],
+        };
+
+        let region = Region::polygon(profile, services).insert(services);
+        let sketch = Sketch::empty().add_region(region).insert(services);
+        let surface = services.objects.surfaces.xz_plane();
+
+        let helix = Helix::from_radius_and_pitch(major, p);
+        (sketch, surface).sweep_along_helix(
+            &helix,
+            length / p,
+            tolerance,
+            services,
+        )
+    }
+
+    /// Build an external thread on the body it belongs to
+    ///
+    /// Builds a solid of the [minor diameter](Self::minor_diameter), with the
+    /// thread on its surface. Instead of combining a cylinder with the ridge
+    /// that [`IsoMetricThread::build`] creates, the solid is swept as a whole:
+    /// The profile of a full turn, from the axis out to the ridge and the root
+    /// that follows it, and back to the axis, moves along the helix. The
+    /// result is a single shell, whose bottom and top are helical ramps that
+    /// each span one turn.
+    ///
+    /// The solid starts at `z = 0` and extends toward positive z. Where its
+    /// top ramp ends, it reaches the given length plus one pitch.
+    ///
+    /// The surfaces of the solid are approximated within the given tolerance.
+    ///
+    /// Returns an error, if the thread is an internal one, or if the length is
+    /// not larger than the pitch.
+    pub fn build_on(
+        &self,
+        length: impl Into<Scalar>,
+        tolerance: impl Into<Tolerance>,
+        services: &mut Services,
+    ) -> Result<Handle<Solid>, ThreadError> {
+        if self.kind == ThreadKind::Internal {
+            return Err(ThreadError::Internal);
+        }
+
+        let turns = length.into() / self.pitch;
+        if turns <= Scalar::ONE {
+            return Err(ThreadError::TooShort);
+        }
+
+        let major = self.diameter / 2.;
+        let minor = self.minor_diameter() / 2.;
+        let p = self.pitch;
+
+        // The profile is defined in the xz-plane, in terms of radius and
+        // height. The ridge is the same as that of an external thread built by
+        // `build`. The end of the root is where the next turn starts.
+        let profile = [
+            [Scalar::ZERO, Scalar::ZERO],
+            [minor, Scalar::ZERO],
+            [major, p * 5. / 16.],
+            [major, p * 7. / 16.],
+            [minor, p * 3. / 4.],
+            [minor, p],
+            [Scalar::ZERO, p],
+        ]
+        .map(Point::from);
+        let surface = services.objects.surfaces.xz_plane();
+        let points = profile
+            .map(|point| surface.geometry().point_from_surface_coords(point));
+
+        // Sweep coordinates count turns.
+        let screw = Screw {
+            origin: Point::origin(),
+            axis: Vector::unit_z(),
+            angle: Scalar::TAU,
+            translation: Vector::unit_z() * p,
+        };
+        let [zero, one, last] = [Scalar::ZERO, Scalar::ONE, turns - 1.];
+
+        // Wherever surfaces share a helix, their approximations of it need to
+        // be made up of the same segments. The end of the root follows the
+        // same helix as the start of the ridge, one turn later. So the
+        // segments that divide each turn evenly are mirrored at the end of the
+        // sweep, which lines them up with themselves, shifted by one turn.
+        let breakpoints = {
+            let per_turn =
+                Scalar::from_u64(screw.num_segments(points, tolerance.into()));
+            let landmarks = [zero, one, last, turns];
+
+            // Breakpoints that are closer than this, would result in segments
+            // that only differ from a point by floating point inaccuracy.
+            let epsilon = Scalar::from(f64::EPSILON * 16.) * turns;
+
+            let mut breakpoints = (0..=(turns * per_turn).floor().into_u64())
+                .flat_map(|i| {
+                    let s = Scalar::from_u64(i) / per_turn;
+                    [s, turns - s]
+                })
+                .filter(|s| {
+                    landmarks
+                        .iter()
+                        .all(|landmark| (*s - *landmark).abs() > epsilon)
+                })
+                .chain(landmarks)
+                .collect::<Vec<_>>();
+            breakpoints.sort();
+            breakpoints.dedup_by(|b, a| *b - *a <= epsilon);
+
+            breakpoints
+        };
+        let breakpoints_in = |[a, b]: [Scalar; 2]| {
+            breakpoints
+                .iter()
+                .copied()
+                .filter(|s| (a..=b).contains(s))
+                .collect::<Vec<_>>()
+        };
+
+        let mut vertices = || [(); 7].map(|()| Vertex::new().insert(services));
+        let [start, end] = [vertices(), vertices()];
+        let mut curves = || [(); 7].map(|()| Curve::new().insert(services));
+        let [start_curves, end_curves] = [curves(), curves()];
+        let [first_turn, shared, last_turn, helices @ ..] =
+            [(); 6].map(|()| Curve::new().insert(services));
+
+        // The helix that the start of the ridge follows is split where the
+        // bottom ramp ends, the one that the end of the root follows where the
+        // top ramp starts. In between, both are the same.
+        let ridge = helices.iter().zip(2..).map(|(helix, i)| {
+            vec![Piece::new(
+                helix,
+                [zero, turns],
+                [zero, turns],
+                [&start[i], &end[i]],
+            )]
+        });
+        let along_profile = iter::once(vec![
+            Piece::new(
+                &first_turn,
+                [zero, one],
+                [zero, one],
+                [&start[1], &start[5]],
+            ),
+            Piece::new(
+                &shared,
+                [one, turns],
+                [one, turns],
+                [&start[5], &end[1]],
+            ),
+        ])
+        .chain(ridge)
+        .chain(iter::once(vec![
+            Piece::new(
+                &shared,
+                [zero, last],
+                [one, turns],
+                [&start[5], &end[1]],
+            ),
+            Piece::new(
+                &last_turn,
+                [last, turns],
+                [last, turns],
+                [&end[1], &end[5]],
+            ),
+        ]))
+        .collect::<Vec<_>>();
+        let bottom_ramp = (
+            [zero, one],
+            [
+                (&start_curves[0], [zero, one]),
+                (&start_curves[5], [one, zero]),
+            ],
+            [
+                vec![Piece::new(
+                    &start_curves[6],
+                    [zero, one],
+                    [zero, one],
+                    [&start[0], &start[6]],
+                )],
+                vec![Piece::new(
+                    &first_turn,
+                    [zero, one],
+                    [zero, one],
+                    [&start[1], &start[5]],
+                )],
+            ],
+        );
+        let top_ramp = (
+            [last, turns],
+            [(&end_curves[0], [one, zero]), (&end_curves[5], [zero, one])],
+            [
+                vec![Piece::new(
+                    &last_turn,
+                    [last, turns],
+                    [last, turns],
+                    [&end[1], &end[5]],
+                )],
+                vec![Piece::new(
+                    &end_curves[6],
+                    [last, turns],
+                    [last, turns],
+                    [&end[0], &end[6]],
+                )],
+            ],
+        );
+
+        let mut faces = vec![
+            profile_face(
+                surface.clone(),
+                profile,
+                &start,
+                &start_curves,
+                [one, zero],
+                false,
+                services,
+            ),
+            profile_face(
+                surface.transform(&screw.transform_at(turns), services),
+                profile,
+                &end,
+                &end_curves,
+                [turns, last],
+                true,
+                services,
+            ),
+        ];
+
+        for (i, segment) in points.windows(2).enumerate() {
+            let (range, ends, sides) = match i {
+                0 => bottom_ramp.clone(),
+                5 => top_ramp.clone(),
+                i => (
+                    [zero, turns],
+                    [
+                        (&start_curves[i], [zero, one]),
+                        (&end_curves[i], [zero, one]),
+                    ],
+                    [along_profile[i - 1].clone(), along_profile[i].clone()],
+                ),
+            };
+
+            faces.push(side_face(
+                &screw,
+                [segment[0], segment[1]],
+                &breakpoints_in(range),
+                ends,
+                sides,
+                services,
+            ));
+        }
+
+        let shell = Shell::new(faces).insert(services);
+        Ok(Solid::new([shell]).insert(services))
+    }
+}
+
+/// The kind of a thread
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum ThreadKind {
+    /// An external thread, like on a bolt
+    ///
+    /// The ridge of the thread points away from the axis.
+    External,
+
+    /// An internal thread, like in a nut
+    ///
+    /// The ridge of the thread points toward the axis.
+    Internal,
+}
+
+/// Error building a thread on its body
+///
+/// Returned by [`IsoMetricThread::build_on`].
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ThreadError {
+    /// The thread is an internal one
+    #[error("Building internal threads on their body is not supported")]
+    Internal,
+
+    /// The thread is not longer than its pitch
+    ///
+    /// The ramps at the bottom and top of the body each span one turn. They
+    /// would overlap otherwise.
+    #[error(
+        "Threads must be longer than their pitch to be built on their body"
+    )]
+    TooShort,
+}
+
+/// A part of the edges of a side face, that runs along a helix
+#[derive(Clone)]
+struct Piece {
+    curve: Handle<Curve>,
+
+    /// The sweep coordinates where the piece starts and ends
+    s: [Scalar; 2],
+
+    /// The curve coordinates where the piece starts and ends
+    t: [Scalar; 2],
+
+    /// The vertices where the piece starts and ends
+    vertices: [Handle<Vertex>; 2],
+}
+
+impl Piece {
+    fn new(
+        curve: &Handle<Curve>,
+        s: [Scalar; 2],
+        t: [Scalar; 2],
+        vertices: [&Handle<Vertex>; 2],
+    ) -> Self {
+        Self {
+            curve: curve.clone(),
+            s,
+            t,
+            vertices: vertices.map(Clone::clone),
+        }
+    }
+}
+
+/// Build a face from the profile of a thread
+///
+/// Each edge is a line from one point of the profile to the next, with curve
+/// coordinate `1` at its start and `0` at its end. The exception is the last
+/// one, which runs along the axis, from the end of the profile back to its
+/// start, and has the given curve coordinates there.
+///
+/// If the face is reversed, its edges run in the opposite direction.
+fn profile_face(
+    surface: Handle<Surface>,
+    profile: [Point<2>; 7],
+    vertices: &[Handle<Vertex>; 7],
+    curves: &[Handle<Curve>; 7],
+    axis: [Scalar; 2],
+    reverse: bool,
+    services: &mut Services,
+) -> Handle<Face> {
+    let mut edges = (0..profile.len())
+        .map(|i| {
+            let j = (i + 1) % profile.len();
+            let coords = if j == 0 {
+                axis
+            } else {
+                [Scalar::ONE, Scalar::ZERO]
+            };
+
+            let path = SurfacePath::line_from_points_with_coords([
+                ([coords[0]], profile[i]),
+                ([coords[1]], profile[j]),
+            ]);
+            let (boundary, start) = if reverse {
+                ([coords[1], coords[0]], &vertices[j])
+            } else {
+                (coords, &vertices[i])
+            };
+
+            Edge::new(
+                path,
+                boundary.map(|t| Point::from([t])),
+                curves[i].clone(),
+                start.clone(),
+            )
+            .insert(services)
+        })
+        .collect::<Vec<_>>();
+    if reverse {
+        edges.reverse();
+    }
+
+    let region = Region::new(Cycle::new(edges).insert(services), [], None)
+        .insert(services);
+    Face::new(surface, region).insert(services)
+}
+
+/// Build the face that a segment of the profile sweeps out
+///
+/// The u-coordinates of its surface go from `0` at the end of the segment to
+/// `1` at its start, the v-coordinates are the sweep coordinates between the
+/// first and last breakpoint. That orients the face away from the body.
+///
+/// Where the sweep starts and ends, the face is bounded by the segment. Its
+/// curves come with their curve coordinates at `u = 0` and `u = 1`. Along the
+/// helices of the start and end of the segment, it is bounded by pieces of
+/// edges.
+fn side_face(
+    screw: &Screw,
+    segment: [Point<3>; 2],
+    breakpoints: &[Scalar],
+    ends: [(&Handle<Curve>, [Scalar; 2]); 2],
+    sides: [Vec<Piece>; 2],
+    services: &mut Services,
+) -> Handle<Face> {
+    let [a, b] = segment;
+    let surface = Surface::new(SurfaceGeometry::Nurbs(
+        screw
+            .surface_with_breakpoints(
+                &Line::from_points_with_line_coords([([0.], b), ([1.], a)]),
+                [[0.], [1.]].map(Point::from),
+                breakpoints,
+            )
+            .into(),
+    ))
+    .insert(services);
+
+    let [zero, one] = [Scalar::ZERO, Scalar::ONE];
+    let [first, last] = [breakpoints[0], breakpoints[breakpoints.len() - 1]];
+    let line = |t: [Scalar; 2], points: [[Scalar; 2]; 2]| {
+        SurfacePath::line_from_points_with_coords([
+            ([t[0]], points[0]),
+            ([t[1]], points[1]),
+        ])
+    };
+    let boundary = |[a, b]: [Scalar; 2]| [a, b].map(|t| Point::from([t]));
+
+    let [(bottom, bottom_coords), (top, top_coords)] = ends;
+    let [along_a, along_b] = sides;
+
+    let mut edges = vec![Edge::new(
+        line(bottom_coords, [[zero, first], [one, first]]),
+        boundary(bottom_coords),
+        bottom.clone(),
+        along_b[0].vertices[0].clone(),
+    )];
+    edges.extend(along_a.iter().map(|piece| {
+        Edge::new(
+            line(piece.t, piece.s.map(|s| [one, s])),
+            boundary(piece.t),
+            piece.curve.clone(),
+            piece.vertices[0].clone(),
+        )
+    }));
+    edges.push(Edge::new(
+        line(top_coords, [[zero, last], [one, last]]),
+        boundary([top_coords[1], top_coords[0]]),
+        top.clone(),
+        along_a[along_a.len() - 1].vertices[1].clone(),
+    ));
+    edges.extend(along_b.iter().rev().map(|piece| {
+        Edge::new(
+            line(piece.t, piece.s.map(|s| [zero, s])),
+            boundary([piece.t[1], piece.t[0]]),
+            piece.curve.clone(),
+            piece.vertices[1].clone(),
+        )
+    }));
+
+    let edges = edges.into_iter().map(|edge| edge.insert(services));
+    let region = Region::new(
+        Cycle::new(edges.collect::<Vec<_>>()).insert(services),
+        [],
+        None,
+    )
+    .insert(services);
+    Face::new(surface, region).insert(services)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::approx::Tolerance, objects::Solid, services::Services,
+    };
+
+    use super::{IsoMetricThread, ThreadError, ThreadKind};
+
+    #[test]
+    fn minor_diameter() {
+        let thread = IsoMetricThread::new(6., 1., ThreadKind::External);
+
+        // ISO 724 lists a basic minor diameter of 4.917 for M6.
+        assert!((thread.minor_diameter() - 4.917).abs() < Scalar::from(1e-3));
+    }
+
+    #[test]
+    fn build() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        for kind in [ThreadKind::External, ThreadKind::Internal] {
+            let thread = IsoMetricThread::new(6., 1., kind);
+            let solid =
+                thread.build(2., Tolerance::from_scalar(0.01)?, &mut services);
+
+            let shells = solid.shells();
+            assert_eq!(shells.len(), 1);
+            assert_eq!(shells.first().faces().len(), 6);
+        }
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn build_profile() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let thread = IsoMetricThread::new(6., 1., ThreadKind::External);
+        let solid =
+            thread.build(2., Tolerance::from_scalar(0.01)?, &mut services);
+
+        let [major, minor] =
+            [thread.diameter(), thread.minor_diameter()].map(|d| d / 2.);
+        let points = vertex_positions(&solid);
+
+        // The thread reaches from the minor to the major diameter.
+        let radii = points
+            .iter()
+            .map(|point| Vector::from([point.x, point.y]).magnitude());
+        assert_close(radii.clone().fold(Scalar::ZERO, Scalar::max), major);
+        assert_close(radii.fold(Scalar::MAX, Scalar::min), minor);
+
+        // Where it starts, in the xz-plane, the thread has the basic profile.
+        // Its crest is an eighth of the pitch wide, its root three quarters.
+        let mut start = points
+            .iter()
+            .filter(|point| point.y.abs() < Scalar::from(1e-9))
+            .filter(|point| point.x > Scalar::ZERO && point.z < Scalar::ONE)
+            .map(|point| [point.x, point.z])
+            .collect::<Vec<_>>();
+        start.sort();
+        start.dedup();
+
+        let expected = [
+            [minor, Scalar::ZERO],
+            [minor, Scalar::from(0.75)],
+            [major, Scalar::from(0.3125)],
+            [major, Scalar::from(0.4375)],
+        ];
+        assert_eq!(start.len(), expected.len());
+        for ([x, z], [r, h]) in start.into_iter().zip(expected) {
+            assert_close(x, r);
+            assert_close(z, h);
+        }
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn build_on() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let thread = IsoMetricThread::new(6., 1., ThreadKind::External);
+        for length in [2., 2.5, 2.75] {
+            let solid = thread.build_on(
+                length,
+                Tolerance::from_scalar(0.01)?,
+                &mut services,
+            )?;
+
+            // The profile where the sweep starts and where it ends, the four
+            // faces of ridge and root, and the ramps at the bottom and top.
+            let shells = solid.shells();
+            assert_eq!(shells.len(), 1);
+            assert_eq!(shells.first().faces().len(), 8);
+
+            // The body reaches from the axis to the crest of the thread, and
+            // one pitch beyond the length.
+            let points = vertex_positions(&solid);
+            let radii = points
+                .iter()
+                .map(|point| Vector::from([point.x, point.y]).magnitude());
+            assert_close(radii.clone().fold(Scalar::ZERO, Scalar::max), 3.);
+            assert_close(radii.fold(Scalar::MAX, Scalar::min), 0.);
+
+            let heights = points.iter().map(|point| point.z);
+            assert_close(heights.clone().fold(Scalar::MAX, Scalar::min), 0.);
+            assert_close(heights.fold(Scalar::ZERO, Scalar::max), length + 1.);
+        }
+
+        services.drop_and_validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn build_on_internal() {
+        let mut services = Services::new();
+
+        let thread = IsoMetricThread::new(6., 1., ThreadKind::Internal);
+        assert!(matches!(
+            thread.build_on(2., 0.01, &mut services),
+            Err(ThreadError::Internal)
+        ));
+    }
+
+    #[test]
+    fn build_on_too_short() {
+        let mut services = Services::new();
+
+        let thread = IsoMetricThread::new(6., 1., ThreadKind::External);
+        for length in [0.5, 1.] {
+            assert!(matches!(
+                thread.build_on(length, 0.01, &mut services),
+                Err(ThreadError::TooShort)
+            ));
+        }
+    }
+
+    fn vertex_positions(solid: &Solid) -> Vec<Point<3>> {
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .flat_map(|face| {
+                face.region()
+                    .all_cycles()
+                    .flat_map(|cycle| cycle.edges().iter().cloned())
+                    .map(|edge| {
+                        face.surface()
+                            .geometry()
+                            .point_from_surface_coords(edge.start_position())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn assert_close(a: Scalar, b: impl Into<Scalar>) {
+        let b = b.into();
+        assert!((a - b).abs() < Scalar::from(1e-9), "{a} != {b}");
+    }
+}
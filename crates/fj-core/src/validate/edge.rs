@@ -35,7 +35,7 @@ pub enum EdgeValidationError {
         distance: Scalar,
 
         /// The edge
-        edge: Edge,
+        edge: Box<Edge>,
     },
 }
 
@@ -54,7 +54,7 @@ impl EdgeValidationError {
                     back_position,
                     front_position,
                     distance,
-                    edge: edge.clone(),
+                    edge: Box::new(edge.clone()),
                 }
                 .into(),
             );
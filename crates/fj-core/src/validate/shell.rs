@@ -86,7 +86,7 @@ fn distances(
 ) -> impl Iterator<Item = Scalar> {
     fn sample(
         percent: f64,
-        (edge, surface): (&Handle<Edge>, SurfaceGeometry),
+        (edge, surface): (&Handle<Edge>, &SurfaceGeometry),
     ) -> Point<3> {
         let [start, end] = edge.boundary().inner;
         let path_coords = start + (end - start) * percent;
@@ -103,13 +103,43 @@ fn distances(
     let mut distances = Vec::new();
     for i in 0..sample_count {
         let percent = i as f64 * step;
-        let sample1 = sample(percent, (&edge_a, surface_a.geometry()));
-        let sample2 = sample(1.0 - percent, (&edge_b, surface_b.geometry()));
+        let sample1 = sample(percent, (&edge_a, &surface_a.geometry()));
+        let sample2 = sample(1.0 - percent, (&edge_b, &surface_b.geometry()));
         distances.push(sample1.distance_to(&sample2))
     }
     distances.into_iter()
 }
 
+/// Check whether an edge collapses into a single point
+///
+/// Where a face reaches a place at which its surface is degenerate, like the
+/// apex of a cone, it is bounded by an edge that has a length in surface
+/// coordinates, but not in model coordinates. Such an edge has no counterpart
+/// in a neighboring face.
+///
+/// An edge that has no length in surface coordinates either doesn't reach such
+/// a place. It is just degenerate, and is not considered collapsed.
+fn is_collapsed(
+    edge: &Edge,
+    surface: &SurfaceGeometry,
+    config: &ValidationConfig,
+) -> bool {
+    let [start, end] = edge.boundary().inner;
+    let [start, middle, end] = [start, start + (end - start) / 2., end]
+        .map(|point_curve| edge.path().point_from_path_coords(point_curve));
+
+    let has_length_in_surface =
+        start.distance_to(&middle) > config.identical_max_distance;
+
+    let [start, middle, end] = [start, middle, end]
+        .map(|point_surface| surface.point_from_surface_coords(point_surface));
+    let has_length_in_model = [middle, end]
+        .into_iter()
+        .any(|point| start.distance_to(&point) > config.identical_max_distance);
+
+    has_length_in_surface && !has_length_in_model
+}
+
 impl ShellValidationError {
     fn validate_curve_coordinates(
         shell: &Shell,
@@ -221,6 +251,14 @@ impl ShellValidationError {
                     continue;
                 }
 
+                // Edges that collapse into a point can coincide with other
+                // such edges, without being identical.
+                if is_collapsed(edge_a, &surface_a.geometry(), config)
+                    && is_collapsed(edge_b, &surface_b.geometry(), config)
+                {
+                    continue;
+                }
+
                 let identical = {
                     let on_same_curve =
                         edge_a.curve().id() == edge_b.curve().id();
@@ -291,7 +329,7 @@ impl ShellValidationError {
 
     fn validate_watertight(
         shell: &Shell,
-        _: &ValidationConfig,
+        config: &ValidationConfig,
         errors: &mut Vec<ValidationError>,
     ) {
         let mut num_edges = BTreeMap::new();
@@ -299,6 +337,10 @@ impl ShellValidationError {
         for face in shell.faces() {
             for cycle in face.region().all_cycles() {
                 for edge in cycle.edges() {
+                    if is_collapsed(edge, &face.surface().geometry(), config) {
+                        continue;
+                    }
+
                     let curve = HandleWrapper::from(edge.curve().clone());
                     let bounding_vertices = cycle
                         .bounding_vertices_of_edge(edge)
@@ -374,10 +416,11 @@ pub struct CurveCoordinateSystemMismatch {
 mod tests {
     use crate::{
         assert_contains_err,
-        objects::{Curve, Shell},
+        geometry::SurfacePath,
+        objects::{Curve, Edge, Shell},
         operations::{
-            BuildShell, Insert, Reverse, UpdateCycle, UpdateEdge, UpdateFace,
-            UpdateRegion, UpdateShell,
+            BuildEdge, BuildShell, Insert, Reverse, UpdateCycle, UpdateEdge,
+            UpdateFace, UpdateRegion, UpdateShell,
         },
         services::Services,
         validate::{shell::ShellValidationError, Validate, ValidationError},
@@ -482,6 +525,47 @@ mod tests {
 
         Ok(())
     }
+    #[test]
+    fn shell_not_watertight_because_of_degenerate_edge() -> anyhow::Result<()> {
+        let mut services = Services::new();
+
+        let valid = Shell::tetrahedron(
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.], [0., 0., 1.]],
+            &mut services,
+        );
+
+        // An edge without length, neither in surface nor in model coordinates,
+        // doesn't collapse into a place where the surface is degenerate. It has
+        // no counterpart in another face, so the shell is not watertight.
+        let invalid = valid.shell.update_face(&valid.abc.face, |face| {
+            face.update_region(|region| {
+                region
+                    .update_exterior(|cycle| {
+                        cycle
+                            .add_edges([Edge::unjoined(
+                                SurfacePath::u_axis(),
+                                [[0.], [0.]],
+                                &mut services,
+                            )
+                            .insert(&mut services)])
+                            .insert(&mut services)
+                    })
+                    .insert(&mut services)
+            })
+            .insert(&mut services)
+        });
+
+        valid.shell.validate_and_return_first_error()?;
+        assert_contains_err!(
+            invalid,
+            ValidationError::Shell(ShellValidationError::NotWatertight)
+        );
+
+        services.only_validate(&*valid.abc.face);
+
+        Ok(())
+    }
+
     #[test]
     fn shell_mixed_orientations() -> anyhow::Result<()> {
         let mut services = Services::new();
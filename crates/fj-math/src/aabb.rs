@@ -45,6 +45,26 @@ impl<const D: usize> Aabb<D> {
 
         true
     }
+
+    /// Determine whether the AABB intersects another AABB
+    ///
+    /// AABBs that merely touch are considered to be intersecting.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let min = self.min.coords.components.into_iter();
+        let max = self.max.coords.components.into_iter();
+        let other_min = other.min.coords.components.into_iter();
+        let other_max = other.max.coords.components.into_iter();
+
+        for (((min, max), other_min), other_max) in
+            min.zip(max).zip(other_min).zip(other_max)
+        {
+            if min > other_max || other_min > max {
+                return false;
+            }
+        }
+
+        true
+    }
 }
 
 impl Aabb<2> {
@@ -174,4 +194,16 @@ mod tests {
         assert!(!aabb.contains([0., 2.]));
         assert!(!aabb.contains([4., 2.]));
     }
+
+    #[test]
+    fn intersects() {
+        let aabb = Aabb::<2>::from_points([[1., 1.], [3., 3.]]);
+
+        assert!(aabb.intersects(&Aabb::<2>::from_points([[2., 2.], [4., 4.]])));
+        assert!(aabb.intersects(&Aabb::<2>::from_points([[3., 0.], [4., 1.]])));
+        assert!(aabb.intersects(&Aabb::<2>::from_points([[0., 0.], [4., 4.]])));
+
+        assert!(!aabb.intersects(&Aabb::<2>::from_points([[4., 0.], [5., 4.]])));
+        assert!(!aabb.intersects(&Aabb::<2>::from_points([[0., 4.], [4., 5.]])));
+    }
 }
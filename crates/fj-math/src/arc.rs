@@ -66,13 +66,22 @@ impl Arc {
         };
         let end_angle = {
             let from_center = p1 - center;
-            let offset = if more_than_half_turn {
-                Scalar::TAU
+            let mut end_angle = from_center.v.atan2(from_center.u);
+
+            // The angles are in the range (-pi, pi]. Make sure the end angle
+            // relates to the start angle in the same way as the points do, even
+            // if the arc crosses that range's boundary.
+            if angle_rad > Scalar::ZERO {
+                while end_angle < start_angle {
+                    end_angle += Scalar::TAU;
+                }
             } else {
-                Scalar::ZERO
-            };
+                while end_angle > start_angle {
+                    end_angle -= Scalar::TAU;
+                }
+            }
 
-            from_center.v.atan2(from_center.u) + offset
+            end_angle
         };
         Self {
             center,
@@ -123,6 +132,18 @@ mod tests {
             0_f64.to_radians(),
             270_f64.to_radians(),
         );
+        check_arc_calculation(
+            [-2., 2.],
+            2.,
+            180_f64.to_radians(),
+            270_f64.to_radians(),
+        );
+        check_arc_calculation(
+            [1., 1.],
+            1.,
+            -135_f64.to_radians(),
+            -225_f64.to_radians(),
+        );
     }
 
     fn check_arc_calculation(
@@ -33,21 +33,26 @@ impl<const D: usize> Circle<D> {
         let a = a.into();
         let b = b.into();
 
-        assert_eq!(
-            a.magnitude(),
-            b.magnitude(),
-            "`a` and `b` must be of equal length"
-        );
         assert_ne!(
             a.magnitude(),
             Scalar::ZERO,
             "circle radius must not be zero"
         );
-        // Requiring the vector to be *precisely* perpendicular is not
-        // practical, because of numerical inaccuracy. This epsilon value seems
-        // seems to work for now, but maybe it needs to become configurable.
+
+        // Requiring the vectors to be *precisely* of equal length and
+        // perpendicular is not practical, because of numerical inaccuracy. For
+        // example, rotating a circle will rarely preserve either property
+        // exactly. This epsilon value seems to work for now, but maybe it needs
+        // to become configurable.
+        let epsilon = Scalar::default_epsilon() * 16.;
+        assert!(
+            (a.magnitude() - b.magnitude()).abs() <= a.magnitude() * epsilon,
+            "`a` and `b` must be of equal length"
+        );
         assert!(
-            a.dot(&b) < Scalar::default_epsilon(),
+            a.dot(&b)
+                < Scalar::default_epsilon()
+                    .max(a.magnitude() * b.magnitude() * epsilon),
             "`a` and `b` must be perpendicular to each other"
         );
 
@@ -123,8 +128,10 @@ impl<const D: usize> Circle<D> {
         &self,
         point: impl Into<Point<D>>,
     ) -> Point<1> {
-        let vector = (point.into() - self.center).to_uv();
-        let atan = Scalar::atan2(vector.v, vector.u);
+        // `a` and `b` are of equal length, so their dot products with the
+        // vector are scaled the same way.
+        let vector = point.into() - self.center;
+        let atan = Scalar::atan2(vector.dot(&self.b), vector.dot(&self.a));
         let coord = if atan >= Scalar::ZERO {
             atan
         } else {
@@ -209,5 +216,12 @@ mod tests {
             circle.point_to_circle_coords([1., 1., 3.]),
             Point::from([FRAC_PI_2 * 3.]),
         );
+
+        // The coordinates follow the circle's direction, not the axes.
+        let reversed = circle.reverse();
+        assert_eq!(
+            reversed.point_to_circle_coords([1., 1., 3.]),
+            Point::from([FRAC_PI_2]),
+        );
     }
 }
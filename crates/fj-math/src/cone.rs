@@ -0,0 +1,204 @@
+use approx::AbsDiffEq;
+
+use crate::{Aabb, Circle, Point, Scalar, Vector};
+
+/// A cone
+///
+/// The cone is defined by a circle and an apex, which is located on the axis
+/// of that circle.
+///
+/// Cone coordinates consist of an angle (`u`) and a position along the axis
+/// (`v`). The angle is measured around the axis, as defined by the circle. The
+/// position along the axis is zero at the circle and one at the apex. Negative
+/// values lead away from the apex, where the cone gets wider than the circle.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Cone {
+    circle: Circle<3>,
+    apex: Point<3>,
+}
+
+impl Cone {
+    /// Construct a cone
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the apex is not located on the axis of the circle, or if it
+    /// coincides with the center of the circle.
+    pub fn new(circle: Circle<3>, apex: impl Into<Point<3>>) -> Self {
+        let apex = apex.into();
+        let axis = apex - circle.center();
+
+        assert_ne!(
+            axis.magnitude(),
+            Scalar::ZERO,
+            "apex must not coincide with the center of the circle"
+        );
+
+        // Same tolerance as for circles. See `Circle::new`.
+        let epsilon = Scalar::default_epsilon() * 16.;
+        for vector in [circle.a(), circle.b()] {
+            assert!(
+                axis.dot(&vector).abs()
+                    < Scalar::default_epsilon()
+                        .max(axis.magnitude() * vector.magnitude() * epsilon),
+                "apex must be located on the axis of the circle"
+            );
+        }
+
+        Self { circle, apex }
+    }
+
+    /// Access the circle of the cone
+    pub fn circle(&self) -> Circle<3> {
+        self.circle
+    }
+
+    /// Access the apex of the cone
+    pub fn apex(&self) -> Point<3> {
+        self.apex
+    }
+
+    /// Access the axis of the cone
+    ///
+    /// Points from the center of the circle to the apex.
+    pub fn axis(&self) -> Vector<3> {
+        self.apex - self.circle.center()
+    }
+
+    /// Compute the radius of the cone at the given position along its axis
+    pub fn radius_at(&self, v: impl Into<Scalar>) -> Scalar {
+        (self.circle.radius() * (Scalar::ONE - v.into())).abs()
+    }
+
+    /// Compute the distance from the circle to the apex, along the cone
+    pub fn slant_height(&self) -> Scalar {
+        (self.axis() - self.circle.a()).magnitude()
+    }
+
+    /// Convert a point to cone coordinates
+    ///
+    /// Converts the provided point into an angle between `0.` (inclusive) and
+    /// `PI * 2.` (exclusive), and a position along the axis.
+    ///
+    /// Callers are advised to be careful about the points they pass, as the
+    /// point not being on the cone, intentional or not, will not result in an
+    /// error.
+    pub fn point_to_cone_coords(&self, point: impl Into<Point<3>>) -> Point<2> {
+        let vector = point.into() - self.circle.center();
+
+        let axis = self.axis();
+        let v = vector.dot(&axis) / axis.dot(&axis);
+
+        let [x, y] = [self.circle.a(), self.circle.b()]
+            .map(|direction| vector.dot(&direction));
+        let mut u = Scalar::atan2(y, x);
+
+        // Past the apex, the cone continues in the opposite direction.
+        if v > Scalar::ONE {
+            u += Scalar::PI;
+        }
+
+        let u = if u >= Scalar::ZERO {
+            u % Scalar::TAU
+        } else {
+            u + Scalar::TAU
+        };
+
+        Point::from([u, v])
+    }
+
+    /// Convert a point in cone coordinates into a 3-dimensional point
+    pub fn point_from_cone_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        self.circle.center() + self.vector_from_cone_coords(point.into().coords)
+    }
+
+    /// Convert a vector in cone coordinates into a 3-dimensional vector
+    ///
+    /// The resulting vector points from the center of the circle to the point
+    /// at the given cone coordinates.
+    pub fn vector_from_cone_coords(
+        &self,
+        vector: impl Into<Vector<2>>,
+    ) -> Vector<3> {
+        let vector = vector.into();
+
+        self.circle.vector_from_circle_coords([vector.u])
+            * (Scalar::ONE - vector.v)
+            + self.axis() * vector.v
+    }
+
+    /// Calculate an AABB for the part of the cone between two positions
+    pub fn aabb(&self, v: [impl Into<Scalar>; 2]) -> Aabb<3> {
+        let [a, b] = v.map(|v| {
+            let v = v.into();
+
+            let center = self.circle.center() + self.axis() * v;
+            let center_to_min_max = Vector::from_component(self.radius_at(v));
+
+            Aabb {
+                min: center - center_to_min_max,
+                max: center + center_to_min_max,
+            }
+        });
+
+        a.merged(&b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Circle, Point, Scalar};
+
+    use super::Cone;
+
+    #[test]
+    fn point_from_cone_coords() {
+        let cone = Cone::new(
+            Circle::from_center_and_radius([0., 0., 1.], 2.),
+            [0., 0., 3.],
+        );
+
+        assert_eq!(
+            cone.point_from_cone_coords([0., 0.]),
+            Point::from([2., 0., 1.])
+        );
+        assert_eq!(
+            cone.point_from_cone_coords([0., 0.5]),
+            Point::from([1., 0., 2.])
+        );
+        assert_eq!(
+            cone.point_from_cone_coords([PI, 1.]),
+            Point::from([0., 0., 3.])
+        );
+        assert_eq!(
+            cone.point_from_cone_coords([0., -1.]),
+            Point::from([4., 0., -1.])
+        );
+    }
+
+    #[test]
+    fn point_to_cone_coords_round_trip() {
+        let cone = Cone::new(
+            Circle::from_center_and_radius([1., 2., 3.], 2.),
+            [1., 2., 1.],
+        );
+
+        for point in [[0.5, 0.25], [3., -1.], [FRAC_PI_2, 0.75], [1., 1.5]] {
+            let point = Point::from(point);
+            let global = cone.point_from_cone_coords(point);
+
+            assert_abs_diff_eq!(
+                cone.point_to_cone_coords(global),
+                point,
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use approx::AbsDiffEq;
+
+use crate::{Aabb, Circle, Point, Scalar, Vector};
+
+/// An n-dimensional ellipse
+///
+/// The ellipse is defined by its center and two vectors, `a` and `b`. Those
+/// are conjugate semi-diameters of the ellipse. They don't need to be
+/// perpendicular to each other, nor of any particular length, but they must
+/// not be parallel. This means that an ellipse stays an ellipse under any
+/// affine transformation, which is just a matter of transforming its center,
+/// `a`, and `b`.
+///
+/// Ellipse coordinates are an angle, in radians, that is not measured in
+/// space, but in the parametrization of the ellipse: The point at angle `t` is
+/// `center + a * cos(t) + b * sin(t)`. If `a` and `b` are perpendicular and of
+/// equal length, this is the same as the coordinates of a [`Circle`].
+///
+/// The dimensionality of the ellipse is defined by the const generic `D`
+/// parameter.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Ellipse<const D: usize> {
+    center: Point<D>,
+    a: Vector<D>,
+    b: Vector<D>,
+}
+
+impl<const D: usize> Ellipse<D> {
+    /// Construct an ellipse
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `a` or `b` are zero, or if they are parallel to each other.
+    pub fn new(
+        center: impl Into<Point<D>>,
+        a: impl Into<Vector<D>>,
+        b: impl Into<Vector<D>>,
+    ) -> Self {
+        let center = center.into();
+        let a = a.into();
+        let b = b.into();
+
+        assert_ne!(a.magnitude(), Scalar::ZERO, "`a` must not be zero");
+        assert_ne!(b.magnitude(), Scalar::ZERO, "`b` must not be zero");
+
+        // Same tolerance as for circles. See `Circle::new`.
+        let epsilon = Scalar::default_epsilon() * 16.;
+        assert!(
+            a.dot(&b).abs()
+                < a.magnitude() * b.magnitude() * (Scalar::ONE - epsilon),
+            "`a` and `b` must not be parallel"
+        );
+
+        Self { center, a, b }
+    }
+
+    /// Construct an `Ellipse` from a center point and two radii
+    ///
+    /// The first radius is measured along the first axis of the coordinate
+    /// system, the second along the second.
+    pub fn from_center_and_radii(
+        center: impl Into<Point<D>>,
+        radii: [impl Into<Scalar>; 2],
+    ) -> Self {
+        let [radius_a, radius_b] = radii.map(Into::into);
+
+        let mut a = [Scalar::ZERO; D];
+        let mut b = [Scalar::ZERO; D];
+
+        a[0] = radius_a;
+        b[1] = radius_b;
+
+        Self::new(center, a, b)
+    }
+
+    /// Access the center point of the ellipse
+    pub fn center(&self) -> Point<D> {
+        self.center
+    }
+
+    /// Access the vector that defines the starting point of the ellipse
+    ///
+    /// The point where this vector points from the ellipse center, is the zero
+    /// coordinate of the ellipse's coordinate system.
+    pub fn a(&self) -> Vector<D> {
+        self.a
+    }
+
+    /// Access the vector that defines the plane of the ellipse
+    ///
+    /// Also defines the direction of the ellipse's coordinate system. The
+    /// point where this vector points from the ellipse center, is at
+    /// coordinate `PI / 2.`.
+    pub fn b(&self) -> Vector<D> {
+        self.b
+    }
+
+    /// Compute the major radius of the ellipse
+    ///
+    /// This is the largest distance between the center and any point on the
+    /// ellipse. If `a` and `b` are perpendicular, it's the length of the
+    /// longer one.
+    pub fn major_radius(&self) -> Scalar {
+        let [aa, ab, bb] =
+            [(self.a, self.a), (self.a, self.b), (self.b, self.b)]
+                .map(|(x, y)| x.dot(&y));
+
+        // The largest eigenvalue of the Gram matrix of `a` and `b` is the
+        // square of the major radius.
+        let half_trace = (aa + bb) / 2.;
+        let determinant = aa * bb - ab * ab;
+        let discriminant =
+            (half_trace * half_trace - determinant).max(Scalar::ZERO);
+
+        (half_trace + discriminant.sqrt()).sqrt()
+    }
+
+    /// Create a new instance that is reversed
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.b = -self.b;
+        self
+    }
+
+    /// Convert a `D`-dimensional point to ellipse coordinates
+    ///
+    /// Converts the provided point into ellipse coordinates between `0.`
+    /// (inclusive) and `PI * 2.` (exclusive).
+    ///
+    /// Points that are not on the ellipse are projected onto it along the
+    /// direction from the center, as measured in the ellipse's
+    /// parametrization. Callers are advised to be careful about the points they
+    /// pass, as the point not being on the curve, intentional or not, will not
+    /// result in an error.
+    pub fn point_to_ellipse_coords(
+        &self,
+        point: impl Into<Point<D>>,
+    ) -> Point<1> {
+        let vector = point.into() - self.center;
+
+        // Express the vector in terms of `a` and `b`, by solving the normal
+        // equations. This works, even if `a` and `b` aren't perpendicular.
+        let [aa, ab, bb] =
+            [(self.a, self.a), (self.a, self.b), (self.b, self.b)]
+                .map(|(x, y)| x.dot(&y));
+        let [va, vb] = [self.a, self.b].map(|axis| vector.dot(&axis));
+
+        let x = va * bb - vb * ab;
+        let y = vb * aa - va * ab;
+
+        let atan = Scalar::atan2(y, x);
+        let coord = if atan >= Scalar::ZERO {
+            atan
+        } else {
+            atan + Scalar::TAU
+        };
+        Point::from([coord])
+    }
+
+    /// Convert a point in ellipse coordinates into a `D`-dimensional point
+    pub fn point_from_ellipse_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        self.center + self.vector_from_ellipse_coords(point.into().coords)
+    }
+
+    /// Convert a vector in ellipse coordinates into a `D`-dimensional point
+    pub fn vector_from_ellipse_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<D> {
+        let angle = vector.into().t;
+        let (sin, cos) = angle.sin_cos();
+
+        self.a * cos + self.b * sin
+    }
+
+    /// Calculate an AABB for the ellipse
+    ///
+    /// Unlike [`Circle::aabb`], this is the tight AABB of the ellipse.
+    pub fn aabb(&self) -> Aabb<D> {
+        let mut center_to_max = Vector::from_component(Scalar::ZERO);
+        for (component, (a, b)) in center_to_max
+            .components
+            .iter_mut()
+            .zip(self.a.components.into_iter().zip(self.b.components))
+        {
+            *component = (a * a + b * b).sqrt();
+        }
+
+        Aabb {
+            min: self.center() - center_to_max,
+            max: self.center() + center_to_max,
+        }
+    }
+}
+
+impl<const D: usize> From<Circle<D>> for Ellipse<D> {
+    fn from(circle: Circle<D>) -> Self {
+        Self::new(circle.center(), circle.a(), circle.b())
+    }
+}
+
+impl<const D: usize> approx::AbsDiffEq for Ellipse<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.center.abs_diff_eq(&other.center, epsilon)
+            && self.a.abs_diff_eq(&other.a, epsilon)
+            && self.b.abs_diff_eq(&other.b, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Point, Scalar};
+
+    use super::Ellipse;
+
+    #[test]
+    fn point_from_ellipse_coords() {
+        let ellipse = Ellipse::from_center_and_radii([1., 2.], [3., 1.]);
+
+        assert_eq!(
+            ellipse.point_from_ellipse_coords([0.]),
+            Point::from([4., 2.])
+        );
+        assert_abs_diff_eq!(
+            ellipse.point_from_ellipse_coords([FRAC_PI_2]),
+            Point::from([1., 3.]),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert_abs_diff_eq!(
+            ellipse.point_from_ellipse_coords([PI]),
+            Point::from([-2., 2.]),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+
+    #[test]
+    fn point_to_ellipse_coords_round_trip() {
+        // `a` and `b` are not perpendicular.
+        let ellipse = Ellipse::new([1., 2., 3.], [2., 0., 0.], [1., 1., 1.]);
+
+        for point in [[0.], [0.5], [FRAC_PI_2], [PI], [5.]] {
+            let point = Point::from(point);
+            let global = ellipse.point_from_ellipse_coords(point);
+
+            assert_abs_diff_eq!(
+                ellipse.point_to_ellipse_coords(global),
+                point,
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+
+    #[test]
+    fn major_radius() {
+        let ellipse = Ellipse::from_center_and_radii([0., 0.], [1., 3.]);
+        assert_abs_diff_eq!(
+            ellipse.major_radius(),
+            Scalar::from(3.),
+            epsilon = Scalar::from(1e-12),
+        );
+
+        // Conjugate semi-diameters of the same ellipse.
+        let ellipse = Ellipse::new([0., 0.], [1., 0.], [1., 3.]);
+        assert_abs_diff_eq!(
+            ellipse.major_radius(),
+            ellipse
+                .point_from_ellipse_coords([ellipse_angle_of_major_axis(
+                    &ellipse
+                )])
+                .coords
+                .magnitude(),
+            epsilon = Scalar::from(1e-12),
+        );
+
+        fn ellipse_angle_of_major_axis(ellipse: &Ellipse<2>) -> Scalar {
+            // The distance from the center is largest where its derivative,
+            // `(a·b) * cos(2t) + (b·b - a·a) / 2 * sin(2t)`, is zero.
+            let [aa, ab, bb] = [
+                (ellipse.a(), ellipse.a()),
+                (ellipse.a(), ellipse.b()),
+                (ellipse.b(), ellipse.b()),
+            ]
+            .map(|(x, y)| x.dot(&y));
+
+            Scalar::atan2(ab * 2., aa - bb) / 2.
+        }
+    }
+
+    #[test]
+    fn aabb() {
+        let ellipse = Ellipse::new([1., 1.], [2., 0.], [1., 1.]);
+        let aabb = ellipse.aabb();
+
+        assert_abs_diff_eq!(
+            aabb.min,
+            Point::from([1. - 5_f64.sqrt(), 0.]),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert_abs_diff_eq!(
+            aabb.max,
+            Point::from([1. + 5_f64.sqrt(), 2.]),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+}
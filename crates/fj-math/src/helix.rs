@@ -0,0 +1,171 @@
+use approx::AbsDiffEq;
+
+use crate::{Aabb, Circle, Point, Scalar, Vector};
+
+/// A helix
+///
+/// The helix winds around an axis at a constant distance, while advancing
+/// along that axis at a constant rate. It is defined by a circle, which is the
+/// helix as seen along its axis, and by its pitch. The pitch is a vector along
+/// the axis, and defines how far the helix advances during a full turn.
+///
+/// Helix coordinates are angles in radians, as defined by the circle. Unlike
+/// circle coordinates, coordinates that differ by a multiple of `PI * 2.`
+/// refer to different points, one or more turns apart.
+///
+/// The helix is right-handed, if its pitch points in the direction of the
+/// normal of the circle (the cross product of `a` and `b`), left-handed
+/// otherwise.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Helix {
+    circle: Circle<3>,
+    pitch: Vector<3>,
+}
+
+impl Helix {
+    /// Construct a helix
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the pitch is zero, or if it isn't parallel to the axis of the
+    /// circle.
+    pub fn new(circle: Circle<3>, pitch: impl Into<Vector<3>>) -> Self {
+        let pitch = pitch.into();
+
+        assert_ne!(pitch.magnitude(), Scalar::ZERO, "pitch must not be zero");
+
+        // Same tolerance as for circles. See `Circle::new`.
+        let epsilon = Scalar::default_epsilon() * 16.;
+        for vector in [circle.a(), circle.b()] {
+            assert!(
+                pitch.dot(&vector).abs()
+                    < Scalar::default_epsilon()
+                        .max(pitch.magnitude() * vector.magnitude() * epsilon),
+                "pitch must be parallel to the axis of the circle"
+            );
+        }
+
+        Self { circle, pitch }
+    }
+
+    /// Construct a right-handed `Helix` around the z-axis
+    ///
+    /// The helix starts on the positive x-axis, and advances toward positive z.
+    pub fn from_radius_and_pitch(
+        radius: impl Into<Scalar>,
+        pitch: impl Into<Scalar>,
+    ) -> Self {
+        let circle = Circle::from_center_and_radius(Point::origin(), radius);
+        Self::new(circle, Vector::unit_z() * pitch.into())
+    }
+
+    /// Access the circle of the helix
+    pub fn circle(&self) -> Circle<3> {
+        self.circle
+    }
+
+    /// Access the pitch of the helix
+    pub fn pitch(&self) -> Vector<3> {
+        self.pitch
+    }
+
+    /// Access the radius of the helix
+    pub fn radius(&self) -> Scalar {
+        self.circle.radius()
+    }
+
+    /// Create a new instance that is reversed
+    ///
+    /// The reversed helix passes through the same points, in the opposite
+    /// direction.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        Self {
+            circle: self.circle.reverse(),
+            pitch: -self.pitch,
+        }
+    }
+
+    /// Convert a point in helix coordinates into a 3-dimensional point
+    pub fn point_from_helix_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<3> {
+        self.circle.center()
+            + self.vector_from_helix_coords(point.into().coords)
+    }
+
+    /// Convert a vector in helix coordinates into a 3-dimensional vector
+    ///
+    /// The resulting vector points from the center of the circle to the point
+    /// at the given angle.
+    pub fn vector_from_helix_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<3> {
+        let angle = vector.into().t;
+
+        self.circle.vector_from_circle_coords([angle])
+            + self.pitch * angle / Scalar::TAU
+    }
+
+    /// Calculate an AABB for the part of the helix within the given range
+    pub fn aabb(&self, range: [impl Into<Scalar>; 2]) -> Aabb<3> {
+        let [a, b] = range.map(|angle| {
+            let offset = self.pitch * angle.into() / Scalar::TAU;
+            let aabb = self.circle.aabb();
+
+            Aabb {
+                min: aabb.min + offset,
+                max: aabb.max + offset,
+            }
+        });
+
+        a.merged(&b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Point, Scalar};
+
+    use super::Helix;
+
+    #[test]
+    fn point_from_helix_coords() {
+        let helix = Helix::from_radius_and_pitch(2., 1.);
+
+        assert_eq!(
+            helix.point_from_helix_coords([0.]),
+            Point::from([2., 0., 0.])
+        );
+        assert_abs_diff_eq!(
+            helix.point_from_helix_coords([FRAC_PI_2]),
+            Point::from([0., 2., 0.25]),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert_abs_diff_eq!(
+            helix.point_from_helix_coords([TAU * 2. + PI]),
+            Point::from([-2., 0., 2.5]),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+
+    #[test]
+    fn reverse() {
+        let helix = Helix::from_radius_and_pitch(2., 1.);
+        let reversed = helix.reverse();
+
+        for t in [0., 1., -3.] {
+            assert_abs_diff_eq!(
+                reversed.point_from_helix_coords([t]),
+                helix.point_from_helix_coords([-t]),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+}
@@ -36,13 +36,20 @@
 mod aabb;
 mod arc;
 mod circle;
+mod cone;
 mod coordinates;
+mod ellipse;
+mod helix;
 mod line;
+mod nurbs;
+mod nurbs_surface;
 mod plane;
 mod point;
 mod poly_chain;
 mod scalar;
 mod segment;
+mod sphere;
+mod torus;
 mod transform;
 mod triangle;
 mod vector;
@@ -51,13 +58,20 @@ pub use self::{
     aabb::Aabb,
     arc::Arc,
     circle::Circle,
+    cone::Cone,
     coordinates::{Uv, Xyz, T},
+    ellipse::Ellipse,
+    helix::Helix,
     line::Line,
+    nurbs::NurbsCurve,
+    nurbs_surface::NurbsSurface,
     plane::Plane,
     point::Point,
     poly_chain::PolyChain,
     scalar::{Scalar, Sign},
     segment::Segment,
+    sphere::Sphere,
+    torus::Torus,
     transform::Transform,
     triangle::{Triangle, Winding},
     vector::Vector,
@@ -0,0 +1,596 @@
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// An n-dimensional NURBS curve
+///
+/// A non-uniform rational B-spline curve is defined by its degree, a list of
+/// control points, a weight for each control point, and a knot vector.
+///
+/// The curve coordinate (`t`) is the parameter of the curve. The curve is
+/// defined within the range of its knot vector that [`NurbsCurve::domain`]
+/// returns. Coordinates outside of that range are clamped to it.
+///
+/// The dimensionality of the curve is defined by the const generic `D`
+/// parameter.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct NurbsCurve<const D: usize> {
+    degree: usize,
+    control_points: Vec<Point<D>>,
+    weights: Vec<Scalar>,
+    knots: Vec<Scalar>,
+}
+
+impl<const D: usize> NurbsCurve<D> {
+    /// Construct a NURBS curve
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the following requirements are not met:
+    ///
+    /// - The degree must not be zero.
+    /// - There must be more control points than the degree.
+    /// - There must be one weight per control point, and all weights must be
+    ///   positive.
+    /// - The number of knots must be the number of control points, plus the
+    ///   degree, plus one.
+    /// - The knots must not be decreasing, and the domain that they define must
+    ///   not be empty.
+    pub fn new(
+        degree: usize,
+        control_points: impl IntoIterator<Item = impl Into<Point<D>>>,
+        weights: impl IntoIterator<Item = impl Into<Scalar>>,
+        knots: impl IntoIterator<Item = impl Into<Scalar>>,
+    ) -> Self {
+        let control_points: Vec<_> =
+            control_points.into_iter().map(Into::into).collect();
+        let weights: Vec<_> = weights.into_iter().map(Into::into).collect();
+        let knots: Vec<_> = knots.into_iter().map(Into::into).collect();
+
+        assert_ne!(degree, 0, "degree must not be zero");
+        assert!(
+            control_points.len() > degree,
+            "need more control points than the degree"
+        );
+        assert_eq!(
+            weights.len(),
+            control_points.len(),
+            "need one weight per control point"
+        );
+        assert!(
+            weights.iter().all(|weight| *weight > Scalar::ZERO),
+            "weights must be positive"
+        );
+        assert_eq!(
+            knots.len(),
+            control_points.len() + degree + 1,
+            "number of knots must be number of control points plus degree \
+            plus one"
+        );
+        assert!(
+            knots.windows(2).all(|knots| knots[0] <= knots[1]),
+            "knots must not be decreasing"
+        );
+        assert!(
+            knots[degree] < knots[control_points.len()],
+            "domain of curve must not be empty"
+        );
+
+        Self {
+            degree,
+            control_points,
+            weights,
+            knots,
+        }
+    }
+
+    /// Construct a B-spline curve from a degree and control points
+    ///
+    /// All weights are one, and the knot vector is uniform and clamped, so the
+    /// curve starts at the first control point and ends at the last one. The
+    /// domain of the curve is `0` to `1`.
+    pub fn from_control_points(
+        degree: usize,
+        control_points: impl IntoIterator<Item = impl Into<Point<D>>>,
+    ) -> Self {
+        let control_points: Vec<_> =
+            control_points.into_iter().map(Into::into).collect();
+
+        let num_segments = control_points.len().saturating_sub(degree).max(1);
+        let knots = (0..control_points.len() + degree + 1).map(|i| {
+            let i = i.clamp(degree, degree + num_segments) - degree;
+            Scalar::from_u64(i as u64) / Scalar::from_u64(num_segments as u64)
+        });
+        let weights = vec![Scalar::ONE; control_points.len()];
+
+        Self::new(degree, control_points, weights, knots)
+    }
+
+    /// Access the degree of the curve
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Access the control points of the curve
+    pub fn control_points(&self) -> &[Point<D>] {
+        &self.control_points
+    }
+
+    /// Access the weights of the control points
+    pub fn weights(&self) -> &[Scalar] {
+        &self.weights
+    }
+
+    /// Access the knot vector of the curve
+    pub fn knots(&self) -> &[Scalar] {
+        &self.knots
+    }
+
+    /// Access the range of curve coordinates, in which the curve is defined
+    pub fn domain(&self) -> [Scalar; 2] {
+        [
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        ]
+    }
+
+    /// Create a new curve by mapping the control points of this one
+    ///
+    /// Keeps the degree, weights, and knots. NURBS curves are invariant under
+    /// affine transformations, so if `f` is an affine map, the new curve is
+    /// the result of applying that map to this curve.
+    pub fn map_control_points<const E: usize>(
+        &self,
+        f: impl FnMut(Point<D>) -> Point<E>,
+    ) -> NurbsCurve<E> {
+        NurbsCurve {
+            degree: self.degree,
+            control_points: self
+                .control_points
+                .iter()
+                .copied()
+                .map(f)
+                .collect(),
+            weights: self.weights.clone(),
+            knots: self.knots.clone(),
+        }
+    }
+
+    /// Create a new instance that is reversed
+    ///
+    /// The reversed curve has the same domain. The point at coordinate `t` of
+    /// the original curve is at coordinate `a + b - t` of the reversed one,
+    /// where `a` and `b` are the limits of the domain.
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        let [a, b] = self.domain();
+
+        self.control_points.reverse();
+        self.weights.reverse();
+        self.knots =
+            self.knots.iter().rev().map(|knot| a + b - *knot).collect();
+
+        self
+    }
+
+    /// Create a new instance with an additional knot
+    ///
+    /// The shape of the curve doesn't change, but it gets an additional
+    /// control point.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the knot is not located within the domain of the curve, or
+    /// if the knot is already present as often as the degree of the curve.
+    #[must_use]
+    pub fn insert_knot(&self, knot: impl Into<Scalar>) -> Self {
+        let knot = knot.into();
+        let [a, b] = self.domain();
+
+        assert!(
+            a < knot && knot < b,
+            "knot must be located within the domain of the curve"
+        );
+        assert!(
+            self.knots.iter().filter(|k| **k == knot).count() < self.degree,
+            "knot must not be present as often as the degree of the curve"
+        );
+
+        let p = self.degree;
+        let span = find_span(p, &self.knots, knot);
+        let homogeneous = self.homogeneous_control_points();
+
+        let mut control_points = Vec::with_capacity(homogeneous.len() + 1);
+        for i in 0..=homogeneous.len() {
+            let point = if i + p <= span {
+                homogeneous[i]
+            } else if i > span {
+                homogeneous[i - 1]
+            } else {
+                let alpha = (knot - self.knots[i])
+                    / (self.knots[i + p] - self.knots[i]);
+                let (point_a, weight_a) = homogeneous[i];
+                let (point_b, weight_b) = homogeneous[i - 1];
+
+                (
+                    point_a * alpha + point_b * (Scalar::ONE - alpha),
+                    weight_a * alpha + weight_b * (Scalar::ONE - alpha),
+                )
+            };
+
+            control_points.push(point);
+        }
+
+        let mut knots = self.knots.clone();
+        knots.insert(span + 1, knot);
+
+        let weights = control_points.iter().map(|(_, weight)| *weight);
+        let control_points =
+            control_points.iter().map(|(point, weight)| Point {
+                coords: *point / *weight,
+            });
+
+        Self::new(p, control_points, weights, knots)
+    }
+
+    /// Convert a point in curve coordinates into a `D`-dimensional point
+    pub fn point_from_nurbs_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        let position = self.derivatives_with_position(point.into().t, 0)[0];
+        Point { coords: position }
+    }
+
+    /// Compute the derivatives of the curve at the given curve coordinate
+    ///
+    /// Returns the derivatives from the first to the given order, in that
+    /// order. Derivatives are computed with respect to the curve coordinate.
+    pub fn derivatives(
+        &self,
+        point: impl Into<Point<1>>,
+        order: usize,
+    ) -> Vec<Vector<D>> {
+        let mut derivatives =
+            self.derivatives_with_position(point.into().t, order);
+        derivatives.remove(0);
+        derivatives
+    }
+
+    /// Calculate an AABB for the curve
+    ///
+    /// This is the AABB of the control points, which contains the curve, but
+    /// might not be tight.
+    pub fn aabb(&self) -> Aabb<D> {
+        let first = self.control_points[0];
+        let mut aabb = Aabb {
+            min: first,
+            max: first,
+        };
+
+        for point in &self.control_points {
+            for i in 0..D {
+                aabb.min.coords.components[i] = aabb.min.coords.components[i]
+                    .min(point.coords.components[i]);
+                aabb.max.coords.components[i] = aabb.max.coords.components[i]
+                    .max(point.coords.components[i]);
+            }
+        }
+
+        aabb
+    }
+
+    /// Compute the position and the derivatives up to the given order
+    fn derivatives_with_position(
+        &self,
+        t: Scalar,
+        order: usize,
+    ) -> Vec<Vector<D>> {
+        let p = self.degree;
+        let span = find_span(p, &self.knots, t);
+        let [a, b] = self.domain();
+        let t = t.clamp(a, b);
+
+        let basis = basis_function_derivatives(p, &self.knots, span, t, order);
+        let homogeneous = self.homogeneous_control_points();
+
+        // Derivatives of the weighted sum of control points and of the weight
+        // function, which the curve is the quotient of.
+        let mut weighted = Vec::with_capacity(order + 1);
+        let mut weight = Vec::with_capacity(order + 1);
+        for basis in &basis {
+            let mut sum_weighted = Vector::from_component(Scalar::ZERO);
+            let mut sum_weight = Scalar::ZERO;
+
+            for (j, basis) in basis.iter().enumerate() {
+                let (point, w) = homogeneous[span - p + j];
+                sum_weighted = sum_weighted + point * *basis;
+                sum_weight += w * *basis;
+            }
+
+            weighted.push(sum_weighted);
+            weight.push(sum_weight);
+        }
+
+        // Apply the quotient rule, as generalized to higher derivatives.
+        let mut derivatives: Vec<Vector<D>> = Vec::with_capacity(order + 1);
+        for k in 0..=order {
+            let mut derivative = weighted[k];
+            for i in 1..=k {
+                derivative = derivative
+                    - derivatives[k - i] * (binomial(k, i) * weight[i]);
+            }
+            derivatives.push(derivative / weight[0]);
+        }
+
+        derivatives
+    }
+
+    /// Compute the control points in homogeneous coordinates
+    ///
+    /// Returns the weighted position of each control point, and its weight.
+    fn homogeneous_control_points(&self) -> Vec<(Vector<D>, Scalar)> {
+        self.control_points
+            .iter()
+            .zip(&self.weights)
+            .map(|(point, weight)| (point.coords * *weight, *weight))
+            .collect()
+    }
+}
+
+/// Find the knot span that contains the given coordinate
+///
+/// Returns the index `i` of the knot span `[knots[i], knots[i + 1])`. The end
+/// of the domain is considered to be part of the last span. Coordinates outside
+/// of the domain are clamped to it.
+pub(crate) fn find_span(degree: usize, knots: &[Scalar], t: Scalar) -> usize {
+    let p = degree;
+    let n = knots.len() - degree - 1;
+    let [a, b] = [knots[p], knots[n]];
+    let t = t.clamp(a, b);
+
+    if t == b {
+        return (p..n)
+            .rev()
+            .find(|&i| knots[i] < b)
+            .expect("Domain is not empty");
+    }
+
+    let num_knots_before = knots[p..n].partition_point(|k| *k <= t);
+    p + num_knots_before - 1
+}
+
+/// Compute the non-zero basis functions and their derivatives
+///
+/// Returns one list per order of derivative, starting with the basis
+/// functions themselves. Each list contains the values for the basis
+/// functions of the control points `span - degree` to `span`.
+///
+/// This is algorithm A2.3 from "The NURBS Book", by Piegl and Tiller.
+#[allow(clippy::needless_range_loop)] // keep close to the book's indexing
+pub(crate) fn basis_function_derivatives(
+    degree: usize,
+    knots: &[Scalar],
+    span: usize,
+    t: Scalar,
+    order: usize,
+) -> Vec<Vec<Scalar>> {
+    let p = degree;
+
+    // The basis functions of all degrees up to `p` (upper triangle), and
+    // the knot differences (lower triangle).
+    let mut ndu = vec![vec![Scalar::ZERO; p + 1]; p + 1];
+    let mut left = vec![Scalar::ZERO; p + 1];
+    let mut right = vec![Scalar::ZERO; p + 1];
+
+    ndu[0][0] = Scalar::ONE;
+    for j in 1..=p {
+        left[j] = t - knots[span + 1 - j];
+        right[j] = knots[span + j] - t;
+
+        let mut saved = Scalar::ZERO;
+        for r in 0..j {
+            ndu[j][r] = right[r + 1] + left[j - r];
+            let temp = ndu[r][j - 1] / ndu[j][r];
+
+            ndu[r][j] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        ndu[j][j] = saved;
+    }
+
+    let mut derivatives = vec![vec![Scalar::ZERO; p + 1]; order + 1];
+    for (derivative, ndu) in derivatives[0].iter_mut().zip(&ndu) {
+        *derivative = ndu[p];
+    }
+
+    // Derivatives of an order higher than the degree are zero.
+    let max_order = order.min(p);
+
+    for r in 0..=p {
+        let mut a = [vec![Scalar::ZERO; p + 1], vec![Scalar::ZERO; p + 1]];
+        let (mut s1, mut s2) = (0, 1);
+        a[0][0] = Scalar::ONE;
+
+        for k in 1..=max_order {
+            let mut d = Scalar::ZERO;
+            let pk = p - k;
+
+            if r >= k {
+                let rk = r - k;
+                a[s2][0] = a[s1][0] / ndu[pk + 1][rk];
+                d = a[s2][0] * ndu[rk][pk];
+            }
+
+            let j1 = if r + 1 >= k { 1 } else { k - r };
+            let j2 = if r <= pk + 1 { k - 1 } else { p - r };
+
+            for j in j1..=j2 {
+                let rkj = r + j - k;
+                a[s2][j] = (a[s1][j] - a[s1][j - 1]) / ndu[pk + 1][rkj];
+                d += a[s2][j] * ndu[rkj][pk];
+            }
+
+            if r <= pk {
+                a[s2][k] = -a[s1][k - 1] / ndu[pk + 1][r];
+                d += a[s2][k] * ndu[r][pk];
+            }
+
+            derivatives[k][r] = d;
+            std::mem::swap(&mut s1, &mut s2);
+        }
+    }
+
+    let mut factor = Scalar::from_u64(p as u64);
+    for (k, derivatives) in
+        derivatives.iter_mut().enumerate().skip(1).take(max_order)
+    {
+        for derivative in derivatives.iter_mut() {
+            *derivative *= factor;
+        }
+        factor *= Scalar::from_u64((p - k) as u64);
+    }
+
+    derivatives
+}
+
+pub(crate) fn binomial(n: usize, k: usize) -> Scalar {
+    let mut result = Scalar::ONE;
+    for i in 0..k {
+        result = result * Scalar::from_u64((n - i) as u64)
+            / Scalar::from_u64((i + 1) as u64);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Point, Scalar};
+
+    use super::NurbsCurve;
+
+    #[test]
+    fn point_from_nurbs_coords() {
+        // A quadratic Bézier curve.
+        let curve =
+            NurbsCurve::from_control_points(2, [[0., 0.], [1., 2.], [2., 0.]]);
+
+        assert_eq!(curve.domain(), [Scalar::ZERO, Scalar::ONE]);
+        assert_eq!(curve.point_from_nurbs_coords([0.]), Point::from([0., 0.]));
+        assert_eq!(curve.point_from_nurbs_coords([0.5]), Point::from([1., 1.]));
+        assert_eq!(curve.point_from_nurbs_coords([1.]), Point::from([2., 0.]));
+    }
+
+    #[test]
+    fn rational_curve() {
+        let quarter_circle = quarter_circle();
+
+        for t in [0., 0.25, 0.5, 0.75, 1.] {
+            let point = quarter_circle.point_from_nurbs_coords([t]);
+            assert_abs_diff_eq!(
+                point.coords.magnitude(),
+                Scalar::ONE,
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+
+    #[test]
+    fn derivatives() {
+        let curves = [
+            quarter_circle(),
+            NurbsCurve::from_control_points(
+                3,
+                [[0., 0.], [1., 2.], [2., -1.], [4., 0.], [5., 3.]],
+            ),
+        ];
+
+        for curve in curves {
+            for t in [0.1, 0.4, 0.7] {
+                let h = 1e-5;
+                let [before, at, after] = [t - h, t, t + h]
+                    .map(|t| curve.point_from_nurbs_coords([t]));
+
+                let first = (after - before) / (2. * h);
+                let second = (after - at * 2. + before.coords) / (h * h);
+
+                let derivatives = curve.derivatives([t], 2);
+                assert_eq!(derivatives.len(), 2);
+                assert_abs_diff_eq!(
+                    derivatives[0],
+                    first,
+                    epsilon = Scalar::from(1e-6),
+                );
+                assert_abs_diff_eq!(
+                    derivatives[1],
+                    second,
+                    epsilon = Scalar::from(1e-3),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn insert_knot() {
+        let curve = NurbsCurve::from_control_points(
+            3,
+            [[0., 0.], [1., 2.], [2., -1.], [4., 0.], [5., 3.]],
+        );
+
+        let refined = curve.insert_knot(0.3).insert_knot(0.5).insert_knot(0.5);
+        assert_eq!(
+            refined.control_points().len(),
+            curve.control_points().len() + 3
+        );
+
+        for t in [0., 0.2, 0.3, 0.45, 0.5, 0.9, 1.] {
+            assert_abs_diff_eq!(
+                refined.point_from_nurbs_coords([t]),
+                curve.point_from_nurbs_coords([t]),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+
+        let quarter_circle = quarter_circle();
+        let refined = quarter_circle.insert_knot(0.5);
+
+        for t in [0., 0.25, 0.5, 0.75, 1.] {
+            assert_abs_diff_eq!(
+                refined.point_from_nurbs_coords([t]),
+                quarter_circle.point_from_nurbs_coords([t]),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+
+    #[test]
+    fn reverse() {
+        let curve = NurbsCurve::new(
+            2,
+            [[0., 0.], [1., 2.], [2., -1.], [4., 0.]],
+            [1., 2., 1., 1.],
+            [1., 1., 1., 1.5, 3., 3., 3.],
+        );
+        let reversed = curve.clone().reverse();
+
+        assert_eq!(reversed.domain(), curve.domain());
+        for t in [1., 1.2, 1.5, 2., 3.] {
+            assert_abs_diff_eq!(
+                reversed.point_from_nurbs_coords([4. - t]),
+                curve.point_from_nurbs_coords([t]),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+
+    fn quarter_circle() -> NurbsCurve<2> {
+        NurbsCurve::new(
+            2,
+            [[1., 0.], [1., 1.], [0., 1.]],
+            [1., FRAC_1_SQRT_2, 1.],
+            [0., 0., 0., 1., 1., 1.],
+        )
+    }
+}
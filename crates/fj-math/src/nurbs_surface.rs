@@ -0,0 +1,580 @@
+use crate::{
+    nurbs::{basis_function_derivatives, binomial, find_span},
+    Aabb, Point, Scalar, Vector,
+};
+
+/// A NURBS surface
+///
+/// A tensor-product non-uniform rational B-spline surface is defined by a grid
+/// of control points, a weight for each control point, and a degree and a knot
+/// vector for each of its two axes.
+///
+/// The surface coordinates (`u` and `v`) are the parameters of the surface
+/// along both axes. The surface is defined within the ranges of its knot
+/// vectors that [`NurbsSurface::domain`] returns. Coordinates outside of those
+/// ranges are clamped to them.
+///
+/// The control points are organized in rows. Each row contains the control
+/// points for one index along the u-axis, and all indices along the v-axis.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct NurbsSurface {
+    degrees: [usize; 2],
+    control_points: Vec<Vec<Point<3>>>,
+    weights: Vec<Vec<Scalar>>,
+    knots: [Vec<Scalar>; 2],
+}
+
+impl NurbsSurface {
+    /// Construct a NURBS surface
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the following requirements are not met, along either
+    /// axis:
+    ///
+    /// - The degree must not be zero.
+    /// - There must be more control points than the degree.
+    /// - All rows of control points must have the same length.
+    /// - There must be one weight per control point, and all weights must be
+    ///   positive.
+    /// - The number of knots must be the number of control points, plus the
+    ///   degree, plus one.
+    /// - The knots must not be decreasing, and the domain that they define must
+    ///   not be empty.
+    pub fn new(
+        degrees: [usize; 2],
+        control_points: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Point<3>>>,
+        >,
+        weights: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Scalar>>,
+        >,
+        knots: [Vec<Scalar>; 2],
+    ) -> Self {
+        let control_points: Vec<Vec<_>> = control_points
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect())
+            .collect();
+        let weights: Vec<Vec<_>> = weights
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect())
+            .collect();
+
+        let num_control_points = [
+            control_points.len(),
+            control_points.first().map(Vec::len).unwrap_or_default(),
+        ];
+
+        assert!(
+            control_points
+                .iter()
+                .all(|row| row.len() == num_control_points[1]),
+            "rows of control points must have the same length"
+        );
+        assert!(
+            weights.len() == num_control_points[0]
+                && weights.iter().all(|row| row.len() == num_control_points[1]),
+            "need one weight per control point"
+        );
+        assert!(
+            weights
+                .iter()
+                .flatten()
+                .all(|weight| *weight > Scalar::ZERO),
+            "weights must be positive"
+        );
+
+        for ((degree, num_control_points), knots) in
+            degrees.iter().zip(num_control_points).zip(&knots)
+        {
+            assert_ne!(*degree, 0, "degree must not be zero");
+            assert!(
+                num_control_points > *degree,
+                "need more control points than the degree"
+            );
+            assert_eq!(
+                knots.len(),
+                num_control_points + degree + 1,
+                "number of knots must be number of control points plus \
+                degree plus one"
+            );
+            assert!(
+                knots.windows(2).all(|knots| knots[0] <= knots[1]),
+                "knots must not be decreasing"
+            );
+            assert!(
+                knots[*degree] < knots[num_control_points],
+                "domain of surface must not be empty"
+            );
+        }
+
+        Self {
+            degrees,
+            control_points,
+            weights,
+            knots,
+        }
+    }
+
+    /// Construct a B-spline surface from degrees and control points
+    ///
+    /// All weights are one, and the knot vectors are uniform and clamped, so
+    /// the corners of the surface are located at the corners of the grid of
+    /// control points. The domain of the surface is `0` to `1` along both axes.
+    pub fn from_control_points(
+        degrees: [usize; 2],
+        control_points: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Point<3>>>,
+        >,
+    ) -> Self {
+        let control_points: Vec<Vec<Point<3>>> = control_points
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect())
+            .collect();
+
+        let num_control_points = [
+            control_points.len(),
+            control_points.first().map(Vec::len).unwrap_or_default(),
+        ];
+        let [knots_u, knots_v] = [0, 1].map(|axis| {
+            let degree = degrees[axis];
+            let num_control_points = num_control_points[axis];
+
+            let num_segments = num_control_points.saturating_sub(degree).max(1);
+            (0..num_control_points + degree + 1)
+                .map(|i| {
+                    let i = i.clamp(degree, degree + num_segments) - degree;
+                    Scalar::from_u64(i as u64)
+                        / Scalar::from_u64(num_segments as u64)
+                })
+                .collect()
+        });
+        let weights = control_points
+            .iter()
+            .map(|row| vec![Scalar::ONE; row.len()])
+            .collect::<Vec<_>>();
+
+        Self::new(degrees, control_points, weights, [knots_u, knots_v])
+    }
+
+    /// Access the degrees of the surface along both axes
+    pub fn degrees(&self) -> [usize; 2] {
+        self.degrees
+    }
+
+    /// Access the control points of the surface
+    pub fn control_points(&self) -> &[Vec<Point<3>>] {
+        &self.control_points
+    }
+
+    /// Access the weights of the control points
+    pub fn weights(&self) -> &[Vec<Scalar>] {
+        &self.weights
+    }
+
+    /// Access the knot vectors of the surface along both axes
+    pub fn knots(&self) -> &[Vec<Scalar>; 2] {
+        &self.knots
+    }
+
+    /// Access the ranges of surface coordinates, in which the surface is
+    /// defined
+    ///
+    /// Returns one range per axis.
+    pub fn domain(&self) -> [[Scalar; 2]; 2] {
+        [0, 1].map(|axis| {
+            let knots = &self.knots[axis];
+            let degree = self.degrees[axis];
+            [knots[degree], knots[knots.len() - degree - 1]]
+        })
+    }
+
+    /// Create a new surface by mapping the control points of this one
+    ///
+    /// Keeps the degrees, weights, and knots. NURBS surfaces are invariant
+    /// under affine transformations, so if `f` is an affine map, the new
+    /// surface is the result of applying that map to this surface.
+    pub fn map_control_points(
+        &self,
+        mut f: impl FnMut(Point<3>) -> Point<3>,
+    ) -> Self {
+        Self {
+            degrees: self.degrees,
+            control_points: self
+                .control_points
+                .iter()
+                .map(|row| row.iter().copied().map(&mut f).collect())
+                .collect(),
+            weights: self.weights.clone(),
+            knots: self.knots.clone(),
+        }
+    }
+
+    /// Convert a point in surface coordinates into a 3-dimensional point
+    pub fn point_from_nurbs_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        Point {
+            coords: self.derivatives(point, 0)[0][0],
+        }
+    }
+
+    /// Convert a 3-dimensional point into surface coordinates
+    ///
+    /// Returns the coordinates of the point on the surface that is closest to
+    /// the provided point. The search starts at the closest point of a grid
+    /// that is placed on the surface, and refines that using Newton's method.
+    /// If there are multiple closest points, the result is one of them.
+    pub fn point_to_nurbs_coords(
+        &self,
+        point: impl Into<Point<3>>,
+    ) -> Point<2> {
+        const MAX_ITERATIONS: usize = 64;
+
+        let point = point.into();
+        let domain = self.domain();
+
+        let [coords_u, coords_v] =
+            [0, 1].map(|axis| self.sample_coords(axis, 2));
+        let mut closest = coords_u
+            .iter()
+            .flat_map(|u| coords_v.iter().map(|v| Point::from([*u, *v])))
+            .min_by_key(|coords| {
+                (self.point_from_nurbs_coords(*coords) - point).magnitude()
+            })
+            .expect("Surface has at least one sample");
+
+        for _ in 0..MAX_ITERATIONS {
+            let derivatives = self.derivatives(closest, 2);
+            let to_point = derivatives[0][0] - point.coords;
+            let [s_u, s_v] = [derivatives[1][0], derivatives[0][1]];
+            let [s_uu, s_uv, s_vv] =
+                [derivatives[2][0], derivatives[1][1], derivatives[0][2]];
+
+            // We're looking for the point where the vector to the point is
+            // perpendicular to both partial derivatives.
+            let f = [to_point.dot(&s_u), to_point.dot(&s_v)];
+            let j_uu = s_u.dot(&s_u) + to_point.dot(&s_uu);
+            let j_uv = s_u.dot(&s_v) + to_point.dot(&s_uv);
+            let j_vv = s_v.dot(&s_v) + to_point.dot(&s_vv);
+
+            let determinant = j_uu * j_vv - j_uv * j_uv;
+            if determinant == Scalar::ZERO {
+                break;
+            }
+
+            let step = [
+                (j_uv * f[1] - j_vv * f[0]) / determinant,
+                (j_uv * f[0] - j_uu * f[1]) / determinant,
+            ];
+
+            let previous = closest;
+            closest = Point::from([0, 1].map(|axis| {
+                let [min, max] = domain[axis];
+                (closest.coords.components[axis] + step[axis]).clamp(min, max)
+            }));
+
+            let epsilon = Scalar::from(1e-15);
+            let converged = [0, 1].into_iter().all(|axis| {
+                let [min, max] = domain[axis];
+                (closest.coords.components[axis]
+                    - previous.coords.components[axis])
+                    .abs()
+                    <= (max - min) * epsilon
+            });
+            if converged {
+                break;
+            }
+        }
+
+        closest
+    }
+
+    /// Compute the derivatives of the surface at the given surface coordinates
+    ///
+    /// Returns the derivatives up to the given order. The element `[k][l]` of
+    /// the result is the derivative of order `k` along the u-axis and of order
+    /// `l` along the v-axis, for all `k + l <= order`. The element `[0][0]` is
+    /// the position of the point on the surface, relative to the origin.
+    pub fn derivatives(
+        &self,
+        point: impl Into<Point<2>>,
+        order: usize,
+    ) -> Vec<Vec<Vector<3>>> {
+        let point = point.into();
+        let [p, q] = self.degrees;
+        let [knots_u, knots_v] = &self.knots;
+
+        let [[min_u, max_u], [min_v, max_v]] = self.domain();
+        let u = point.u.clamp(min_u, max_u);
+        let v = point.v.clamp(min_v, max_v);
+
+        let span_u = find_span(p, knots_u, u);
+        let span_v = find_span(q, knots_v, v);
+        let basis_u = basis_function_derivatives(p, knots_u, span_u, u, order);
+        let basis_v = basis_function_derivatives(q, knots_v, span_v, v, order);
+
+        // Derivatives of the weighted sum of control points and of the weight
+        // function, which the surface is the quotient of.
+        let mut weighted =
+            vec![
+                vec![Vector::from_component(Scalar::ZERO); order + 1];
+                order + 1
+            ];
+        let mut weight = vec![vec![Scalar::ZERO; order + 1]; order + 1];
+        for k in 0..=order {
+            for l in 0..=order - k {
+                for (i, basis_u) in basis_u[k].iter().enumerate() {
+                    for (j, basis_v) in basis_v[l].iter().enumerate() {
+                        let row = span_u - p + i;
+                        let column = span_v - q + j;
+
+                        let w = self.weights[row][column];
+                        let point = self.control_points[row][column].coords;
+                        let basis = *basis_u * *basis_v;
+
+                        weighted[k][l] = weighted[k][l] + point * w * basis;
+                        weight[k][l] += w * basis;
+                    }
+                }
+            }
+        }
+
+        // Apply the quotient rule, as generalized to higher derivatives in two
+        // variables. This is algorithm A4.4 from "The NURBS Book", by Piegl
+        // and Tiller.
+        let mut derivatives =
+            vec![
+                vec![Vector::from_component(Scalar::ZERO); order + 1];
+                order + 1
+            ];
+        for k in 0..=order {
+            for l in 0..=order - k {
+                let mut derivative = weighted[k][l];
+
+                for j in 1..=l {
+                    derivative = derivative
+                        - derivatives[k][l - j]
+                            * (binomial(l, j) * weight[0][j]);
+                }
+                for i in 1..=k {
+                    derivative = derivative
+                        - derivatives[k - i][l]
+                            * (binomial(k, i) * weight[i][0]);
+
+                    let mut mixed = Vector::from_component(Scalar::ZERO);
+                    for j in 1..=l {
+                        mixed = mixed
+                            + derivatives[k - i][l - j]
+                                * (binomial(l, j) * weight[i][j]);
+                    }
+                    derivative = derivative - mixed * binomial(k, i);
+                }
+
+                derivatives[k][l] = derivative / weight[0][0];
+            }
+        }
+
+        derivatives
+    }
+
+    /// Calculate an AABB for the surface
+    ///
+    /// This is the AABB of the control points, which contains the surface, but
+    /// might not be tight.
+    pub fn aabb(&self) -> Aabb<3> {
+        Aabb::<3>::from_points(self.control_points.iter().flatten().copied())
+    }
+
+    /// Sample coordinates along one axis of the surface
+    ///
+    /// Returns the limits of all knot spans within the domain, plus the given
+    /// number of coordinates per degree and knot span in between.
+    fn sample_coords(
+        &self,
+        axis: usize,
+        samples_per_degree: usize,
+    ) -> Vec<Scalar> {
+        let [min, max] = self.domain()[axis];
+        let num_samples = self.degrees[axis] * samples_per_degree + 1;
+
+        let mut knots = self.knots[axis]
+            .iter()
+            .copied()
+            .filter(|knot| min <= *knot && *knot <= max)
+            .collect::<Vec<_>>();
+        knots.dedup();
+
+        let mut coords = Vec::new();
+        for span in knots.windows(2) {
+            let [a, b] = [span[0], span[1]];
+
+            for i in 0..num_samples {
+                coords.push(
+                    a + (b - a) * Scalar::from_u64(i as u64)
+                        / Scalar::from_u64(num_samples as u64),
+                );
+            }
+        }
+        coords.push(max);
+
+        coords
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Point, Scalar, Vector};
+
+    use super::NurbsSurface;
+
+    #[test]
+    fn point_from_nurbs_coords() {
+        // A bilinear patch is just an interpolation of its corners.
+        let surface = NurbsSurface::from_control_points(
+            [1, 1],
+            [[[0., 0., 0.], [0., 1., 0.]], [[1., 0., 0.], [1., 1., 1.]]],
+        );
+
+        assert_eq!(
+            surface.domain(),
+            [[Scalar::ZERO, Scalar::ONE], [Scalar::ZERO, Scalar::ONE]]
+        );
+        assert_eq!(
+            surface.point_from_nurbs_coords([0., 0.]),
+            Point::from([0., 0., 0.])
+        );
+        assert_eq!(
+            surface.point_from_nurbs_coords([1., 1.]),
+            Point::from([1., 1., 1.])
+        );
+        assert_eq!(
+            surface.point_from_nurbs_coords([0.5, 0.5]),
+            Point::from([0.5, 0.5, 0.25])
+        );
+    }
+
+    #[test]
+    fn rational_surface() {
+        let cylinder = quarter_cylinder();
+
+        for u in [0., 0.25, 0.5, 0.75, 1.] {
+            for v in [0., 0.5, 1.] {
+                let point = cylinder.point_from_nurbs_coords([u, v]);
+                let radius = Vector::from([point.x, point.y]).magnitude();
+
+                assert_abs_diff_eq!(
+                    radius,
+                    Scalar::ONE,
+                    epsilon = Scalar::from(1e-12),
+                );
+                assert_abs_diff_eq!(
+                    point.z,
+                    Scalar::from(v * 2.),
+                    epsilon = Scalar::from(1e-12),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn derivatives() {
+        let surfaces = [quarter_cylinder(), wavy_surface()];
+
+        for surface in surfaces {
+            for [u, v] in [[0.1, 0.2], [0.4, 0.7], [0.8, 0.6]] {
+                let h = 1e-4;
+                let at = |u: f64, v: f64| {
+                    surface.point_from_nurbs_coords([u, v]).coords
+                };
+
+                let s_u = (at(u + h, v) - at(u - h, v)) / (2. * h);
+                let s_v = (at(u, v + h) - at(u, v - h)) / (2. * h);
+                let s_uu =
+                    (at(u + h, v) - at(u, v) * 2. + at(u - h, v)) / (h * h);
+                let s_uv =
+                    (at(u + h, v + h) - at(u + h, v - h) - at(u - h, v + h)
+                        + at(u - h, v - h))
+                        / (4. * h * h);
+                let s_vv =
+                    (at(u, v + h) - at(u, v) * 2. + at(u, v - h)) / (h * h);
+
+                let derivatives = surface.derivatives([u, v], 2);
+                let epsilon = Scalar::from(1e-5);
+                assert_abs_diff_eq!(derivatives[0][0], at(u, v));
+                assert_abs_diff_eq!(derivatives[1][0], s_u, epsilon = epsilon);
+                assert_abs_diff_eq!(derivatives[0][1], s_v, epsilon = epsilon);
+
+                let epsilon = Scalar::from(1e-3);
+                assert_abs_diff_eq!(derivatives[2][0], s_uu, epsilon = epsilon);
+                assert_abs_diff_eq!(derivatives[1][1], s_uv, epsilon = epsilon);
+                assert_abs_diff_eq!(derivatives[0][2], s_vv, epsilon = epsilon);
+            }
+        }
+    }
+
+    #[test]
+    fn point_to_nurbs_coords() {
+        let surface = wavy_surface();
+
+        for point in [[0., 0.], [0.3, 0.6], [0.75, 0.2], [1., 1.]] {
+            let point = Point::from(point);
+            let point_global = surface.point_from_nurbs_coords(point);
+
+            assert_abs_diff_eq!(
+                surface.point_to_nurbs_coords(point_global),
+                point,
+                epsilon = Scalar::from(1e-9),
+            );
+        }
+
+        // Points off the surface are projected onto the closest point.
+        let cylinder = quarter_cylinder();
+        let point = Point::from([0.5, 0.5]);
+        let point_global = cylinder.point_from_nurbs_coords(point);
+        let outside = Point::from([
+            point_global.x * 3.,
+            point_global.y * 3.,
+            point_global.z,
+        ]);
+        assert_abs_diff_eq!(
+            cylinder.point_to_nurbs_coords(outside),
+            point,
+            epsilon = Scalar::from(1e-9),
+        );
+    }
+
+    /// A quarter of a cylinder around the z-axis, with a radius of 1 and a
+    /// height of 2
+    fn quarter_cylinder() -> NurbsSurface {
+        let arc = [[1., 0.], [1., 1.], [0., 1.]];
+
+        NurbsSurface::new(
+            [2, 1],
+            arc.map(|[x, y]| [[x, y, 0.], [x, y, 2.]]),
+            [1., FRAC_1_SQRT_2, 1.].map(|weight| [weight; 2]),
+            [
+                [0., 0., 0., 1., 1., 1.].map(Scalar::from).to_vec(),
+                [0., 0., 1., 1.].map(Scalar::from).to_vec(),
+            ],
+        )
+    }
+
+    /// A non-rational surface with a few bumps
+    fn wavy_surface() -> NurbsSurface {
+        NurbsSurface::from_control_points(
+            [3, 2],
+            (0..5).map(|i| {
+                (0..4).map(move |j| {
+                    let height = if (i + j) % 2 == 0 { 0.5 } else { -0.5 };
+                    [i as f64, j as f64, height]
+                })
+            }),
+        )
+    }
+}
@@ -0,0 +1,250 @@
+use approx::AbsDiffEq;
+
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// A sphere
+///
+/// The sphere has a coordinate system that is defined by three vectors, `a`,
+/// `b`, and `c`. `a` and `b` define the plane of the sphere's equator, while
+/// `c` points to its north pole.
+///
+/// Sphere coordinates consist of longitude (`u`) and latitude (`v`), both in
+/// radians. The longitude is measured around `c`, starting at `a` and turning
+/// towards `b`. The latitude is measured from the equator, and is positive
+/// towards `c`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Sphere {
+    center: Point<3>,
+    a: Vector<3>,
+    b: Vector<3>,
+    c: Vector<3>,
+}
+
+impl Sphere {
+    /// Construct a sphere
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the following requirements are not met:
+    ///
+    /// - The sphere radius (defined by the length of `a`, `b`, and `c`) must
+    ///   not be zero.
+    /// - `a`, `b`, and `c` must be of equal length.
+    /// - `a`, `b`, and `c` must be perpendicular to each other.
+    pub fn new(
+        center: impl Into<Point<3>>,
+        a: impl Into<Vector<3>>,
+        b: impl Into<Vector<3>>,
+        c: impl Into<Vector<3>>,
+    ) -> Self {
+        let center = center.into();
+        let a = a.into();
+        let b = b.into();
+        let c = c.into();
+
+        assert_ne!(
+            a.magnitude(),
+            Scalar::ZERO,
+            "sphere radius must not be zero"
+        );
+
+        // Same tolerance as for circles. See `Circle::new`.
+        let epsilon = Scalar::default_epsilon() * 16.;
+        for (x, y) in [(a, b), (a, c), (b, c)] {
+            assert!(
+                (x.magnitude() - y.magnitude()).abs()
+                    <= a.magnitude() * epsilon,
+                "`a`, `b`, and `c` must be of equal length"
+            );
+            assert!(
+                x.dot(&y).abs()
+                    < Scalar::default_epsilon()
+                        .max(x.magnitude() * y.magnitude() * epsilon),
+                "`a`, `b`, and `c` must be perpendicular to each other"
+            );
+        }
+
+        Self { center, a, b, c }
+    }
+
+    /// Construct a `Sphere` from a center point and a radius
+    ///
+    /// The coordinate system of the sphere is aligned with the axes of the
+    /// global coordinate system, with the north pole pointing towards positive
+    /// z.
+    pub fn from_center_and_radius(
+        center: impl Into<Point<3>>,
+        radius: impl Into<Scalar>,
+    ) -> Self {
+        let radius = radius.into();
+
+        Self::new(
+            center,
+            [radius, Scalar::ZERO, Scalar::ZERO],
+            [Scalar::ZERO, radius, Scalar::ZERO],
+            [Scalar::ZERO, Scalar::ZERO, radius],
+        )
+    }
+
+    /// Access the center point of the sphere
+    pub fn center(&self) -> Point<3> {
+        self.center
+    }
+
+    /// Access the radius of the sphere
+    pub fn radius(&self) -> Scalar {
+        self.a().magnitude()
+    }
+
+    /// Access the vector that points to the zero coordinate of the sphere
+    ///
+    /// The point where this vector points from the sphere center, is where
+    /// longitude and latitude are both zero.
+    pub fn a(&self) -> Vector<3> {
+        self.a
+    }
+
+    /// Access the vector that defines the direction of the longitude
+    ///
+    /// The point where this vector points from the sphere center, is on the
+    /// equator, a quarter turn from [`Self::a`].
+    pub fn b(&self) -> Vector<3> {
+        self.b
+    }
+
+    /// Access the vector that points to the north pole of the sphere
+    pub fn c(&self) -> Vector<3> {
+        self.c
+    }
+
+    /// Convert a point to sphere coordinates
+    ///
+    /// Converts the provided point into a longitude between `0.` (inclusive)
+    /// and `PI * 2.` (exclusive), and a latitude between `-PI / 2.` and
+    /// `PI / 2.` (both inclusive). The longitude of points on the axis of the
+    /// sphere is zero.
+    ///
+    /// Projects the point onto the sphere before computing sphere coordinates,
+    /// ignoring the radius. Callers are advised to be careful about the points
+    /// they pass, as the point not being on the sphere, intentional or not,
+    /// will not result in an error.
+    pub fn point_to_sphere_coords(
+        &self,
+        point: impl Into<Point<3>>,
+    ) -> Point<2> {
+        let vector = point.into() - self.center;
+        let [x, y, z] = [self.a, self.b, self.c].map(|axis| vector.dot(&axis));
+
+        let longitude = Scalar::atan2(y, x);
+        let longitude = if longitude >= Scalar::ZERO {
+            longitude
+        } else {
+            longitude + Scalar::TAU
+        };
+        let latitude = Scalar::atan2(z, Vector::from([x, y]).magnitude());
+
+        Point::from([longitude, latitude])
+    }
+
+    /// Convert a point in sphere coordinates into a 3-dimensional point
+    pub fn point_from_sphere_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        self.center + self.vector_from_sphere_coords(point.into().coords)
+    }
+
+    /// Convert a vector in sphere coordinates into a 3-dimensional vector
+    ///
+    /// The resulting vector points from the center of the sphere to the point
+    /// at the given longitude and latitude.
+    pub fn vector_from_sphere_coords(
+        &self,
+        vector: impl Into<Vector<2>>,
+    ) -> Vector<3> {
+        let vector = vector.into();
+        let (sin_u, cos_u) = vector.u.sin_cos();
+        let (sin_v, cos_v) = vector.v.sin_cos();
+
+        (self.a * cos_u + self.b * sin_u) * cos_v + self.c * sin_v
+    }
+
+    /// Calculate an AABB for the sphere
+    pub fn aabb(&self) -> Aabb<3> {
+        let center_to_min_max = Vector::from_component(self.radius());
+
+        Aabb {
+            min: self.center() - center_to_min_max,
+            max: self.center() + center_to_min_max,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Point, Scalar, Vector};
+
+    use super::Sphere;
+
+    #[test]
+    fn point_to_sphere_coords() {
+        let sphere = Sphere::new(
+            [1., 2., 3.],
+            [0., 2., 0.],
+            [-2., 0., 0.],
+            [0., 0., 2.],
+        );
+
+        assert_eq!(
+            sphere.point_to_sphere_coords([1., 4., 3.]),
+            Point::from([0., 0.]),
+        );
+        assert_eq!(
+            sphere.point_to_sphere_coords([-1., 2., 3.]),
+            Point::from([FRAC_PI_2, 0.]),
+        );
+        assert_eq!(
+            sphere.point_to_sphere_coords([1., 0., 3.]),
+            Point::from([PI, 0.]),
+        );
+        assert_eq!(
+            sphere.point_to_sphere_coords([1., 2., 5.]),
+            Point::from([0., FRAC_PI_2]),
+        );
+        assert_eq!(
+            sphere.point_to_sphere_coords([1., 2., 1.]),
+            Point::from([0., -FRAC_PI_2]),
+        );
+    }
+
+    #[test]
+    fn point_from_sphere_coords_round_trip() {
+        let sphere = Sphere::from_center_and_radius([1., 2., 3.], 2.);
+
+        for point in [[0.5, 0.25], [3., -1.], [5., FRAC_PI_4]] {
+            let point = Point::from(point);
+            let global = sphere.point_from_sphere_coords(point);
+
+            assert_abs_diff_eq!(
+                (global - sphere.center()).magnitude(),
+                sphere.radius(),
+                epsilon = Scalar::from(1e-12),
+            );
+            assert_abs_diff_eq!(
+                sphere.point_to_sphere_coords(global),
+                point,
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+
+        assert_abs_diff_eq!(
+            sphere.vector_from_sphere_coords([FRAC_PI_2, FRAC_PI_4]),
+            Vector::from([0., 2_f64.sqrt(), 2_f64.sqrt()]),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+}
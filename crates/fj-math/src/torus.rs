@@ -0,0 +1,246 @@
+use approx::AbsDiffEq;
+
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// A torus
+///
+/// The torus has a coordinate system that is defined by three vectors, `a`,
+/// `b`, and `c`. `a` and `b` define the plane of the circle that runs through
+/// the middle of the tube, and their length defines the major radius. `c`
+/// points along the axis of the torus, and its length defines the minor
+/// radius, the radius of the tube.
+///
+/// Torus coordinates consist of two angles, both in radians. The first (`u`)
+/// is measured around the axis, starting at `a` and turning towards `b`. The
+/// second (`v`) is measured around the tube, starting at the outside of the
+/// torus and turning towards `c`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Torus {
+    center: Point<3>,
+    a: Vector<3>,
+    b: Vector<3>,
+    c: Vector<3>,
+}
+
+impl Torus {
+    /// Construct a torus
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the following requirements are not met:
+    ///
+    /// - The major radius (defined by the length of `a` and `b`) and the minor
+    ///   radius (defined by the length of `c`) must not be zero.
+    /// - `a` and `b` must be of equal length.
+    /// - `a`, `b`, and `c` must be perpendicular to each other.
+    pub fn new(
+        center: impl Into<Point<3>>,
+        a: impl Into<Vector<3>>,
+        b: impl Into<Vector<3>>,
+        c: impl Into<Vector<3>>,
+    ) -> Self {
+        let center = center.into();
+        let a = a.into();
+        let b = b.into();
+        let c = c.into();
+
+        assert_ne!(
+            a.magnitude(),
+            Scalar::ZERO,
+            "major radius must not be zero"
+        );
+        assert_ne!(
+            c.magnitude(),
+            Scalar::ZERO,
+            "minor radius must not be zero"
+        );
+
+        // Same tolerance as for circles. See `Circle::new`.
+        let epsilon = Scalar::default_epsilon() * 16.;
+        assert!(
+            (a.magnitude() - b.magnitude()).abs() <= a.magnitude() * epsilon,
+            "`a` and `b` must be of equal length"
+        );
+        for (x, y) in [(a, b), (a, c), (b, c)] {
+            assert!(
+                x.dot(&y).abs()
+                    < Scalar::default_epsilon()
+                        .max(x.magnitude() * y.magnitude() * epsilon),
+                "`a`, `b`, and `c` must be perpendicular to each other"
+            );
+        }
+
+        Self { center, a, b, c }
+    }
+
+    /// Construct a `Torus` from a center point and its radii
+    ///
+    /// The axis of the torus points towards positive z.
+    pub fn from_center_and_radii(
+        center: impl Into<Point<3>>,
+        major_radius: impl Into<Scalar>,
+        minor_radius: impl Into<Scalar>,
+    ) -> Self {
+        let major_radius = major_radius.into();
+        let minor_radius = minor_radius.into();
+
+        Self::new(
+            center,
+            [major_radius, Scalar::ZERO, Scalar::ZERO],
+            [Scalar::ZERO, major_radius, Scalar::ZERO],
+            [Scalar::ZERO, Scalar::ZERO, minor_radius],
+        )
+    }
+
+    /// Access the center point of the torus
+    pub fn center(&self) -> Point<3> {
+        self.center
+    }
+
+    /// Access the major radius of the torus
+    ///
+    /// This is the radius of the circle that runs through the middle of the
+    /// tube.
+    pub fn major_radius(&self) -> Scalar {
+        self.a.magnitude()
+    }
+
+    /// Access the minor radius of the torus
+    ///
+    /// This is the radius of the tube.
+    pub fn minor_radius(&self) -> Scalar {
+        self.c.magnitude()
+    }
+
+    /// Access the vector that points to the zero coordinate of the first angle
+    pub fn a(&self) -> Vector<3> {
+        self.a
+    }
+
+    /// Access the vector that defines the direction of the first angle
+    pub fn b(&self) -> Vector<3> {
+        self.b
+    }
+
+    /// Access the vector that points along the axis of the torus
+    pub fn c(&self) -> Vector<3> {
+        self.c
+    }
+
+    /// Convert a point to torus coordinates
+    ///
+    /// Converts the provided point into two angles between `0.` (inclusive)
+    /// and `PI * 2.` (exclusive).
+    ///
+    /// Callers are advised to be careful about the points they pass, as the
+    /// point not being on the torus, intentional or not, will not result in an
+    /// error.
+    pub fn point_to_torus_coords(
+        &self,
+        point: impl Into<Point<3>>,
+    ) -> Point<2> {
+        let vector = point.into() - self.center;
+        let [x, y, z] =
+            [self.a, self.b, self.c].map(|axis| vector.dot(&axis.normalize()));
+
+        let distance_from_axis = Vector::from([x, y]).magnitude();
+
+        let [u, v] = [
+            Scalar::atan2(y, x),
+            Scalar::atan2(z, distance_from_axis - self.major_radius()),
+        ]
+        .map(|angle| {
+            if angle >= Scalar::ZERO {
+                angle
+            } else {
+                angle + Scalar::TAU
+            }
+        });
+
+        Point::from([u, v])
+    }
+
+    /// Convert a point in torus coordinates into a 3-dimensional point
+    pub fn point_from_torus_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        self.center + self.vector_from_torus_coords(point.into().coords)
+    }
+
+    /// Convert a vector in torus coordinates into a 3-dimensional vector
+    ///
+    /// The resulting vector points from the center of the torus to the point
+    /// at the given angles.
+    pub fn vector_from_torus_coords(
+        &self,
+        vector: impl Into<Vector<2>>,
+    ) -> Vector<3> {
+        let vector = vector.into();
+        let (sin_u, cos_u) = vector.u.sin_cos();
+        let (sin_v, cos_v) = vector.v.sin_cos();
+
+        let ratio = self.minor_radius() / self.major_radius();
+
+        (self.a * cos_u + self.b * sin_u) * (Scalar::ONE + ratio * cos_v)
+            + self.c * sin_v
+    }
+
+    /// Calculate an AABB for the torus
+    pub fn aabb(&self) -> Aabb<3> {
+        let center_to_min_max =
+            Vector::from_component(self.major_radius() + self.minor_radius());
+
+        Aabb {
+            min: self.center() - center_to_min_max,
+            max: self.center() + center_to_min_max,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Point, Scalar};
+
+    use super::Torus;
+
+    #[test]
+    fn point_from_torus_coords() {
+        let torus = Torus::from_center_and_radii([0., 0., 1.], 3., 1.);
+
+        assert_eq!(
+            torus.point_from_torus_coords([0., 0.]),
+            Point::from([4., 0., 1.])
+        );
+        assert_eq!(
+            torus.point_from_torus_coords([0., FRAC_PI_2]),
+            Point::from([3., 0., 2.])
+        );
+        assert_abs_diff_eq!(
+            torus.point_from_torus_coords([FRAC_PI_2, PI]),
+            Point::from([0., 2., 1.]),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+
+    #[test]
+    fn point_to_torus_coords_round_trip() {
+        let torus =
+            Torus::new([1., 2., 3.], [0., 3., 0.], [0., 0., 3.], [1., 0., 0.]);
+
+        for point in [[0.5, 0.25], [3., 5.], [FRAC_PI_2, PI]] {
+            let point = Point::from(point);
+            let global = torus.point_from_torus_coords(point);
+
+            assert_abs_diff_eq!(
+                torus.point_to_torus_coords(global),
+                point,
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+}
@@ -2,7 +2,10 @@ use std::ops;
 
 use nalgebra::Perspective3;
 
-use crate::{Circle, Line, Scalar};
+use crate::{
+    Circle, Cone, Ellipse, Helix, Line, NurbsCurve, NurbsSurface, Scalar,
+    Sphere, Torus,
+};
 
 use super::{Aabb, Point, Segment, Triangle, Vector};
 
@@ -40,6 +43,23 @@ impl Transform {
         ))
     }
 
+    /// Construct a reflection
+    ///
+    /// Mirrors across the plane that passes through the origin, perpendicular
+    /// to the provided normal. The length of the normal doesn't matter.
+    ///
+    /// A reflection reverses the handedness of any coordinate system that it
+    /// is applied to.
+    pub fn reflection(normal: impl Into<Vector<3>>) -> Self {
+        let normal = normal.into().normalize().to_na();
+        let matrix =
+            nalgebra::Matrix3::identity() - normal * normal.transpose() * 2.;
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            matrix.to_homogeneous(),
+        ))
+    }
+
     /// Construct a scaling
     pub fn scale(scaling_factor: f64) -> Self {
         Self(nalgebra::Transform::from_matrix_unchecked(
@@ -95,6 +115,70 @@ impl Transform {
         )
     }
 
+    /// Transform the given ellipse
+    pub fn transform_ellipse(&self, ellipse: &Ellipse<3>) -> Ellipse<3> {
+        Ellipse::new(
+            self.transform_point(&ellipse.center()),
+            self.transform_vector(&ellipse.a()),
+            self.transform_vector(&ellipse.b()),
+        )
+    }
+
+    /// Transform the given helix
+    pub fn transform_helix(&self, helix: &Helix) -> Helix {
+        Helix::new(
+            self.transform_circle(&helix.circle()),
+            self.transform_vector(&helix.pitch()),
+        )
+    }
+
+    /// Transform the given NURBS curve
+    ///
+    /// NURBS curves are invariant under affine transformations, so this is
+    /// just a matter of transforming their control points.
+    pub fn transform_nurbs_curve(
+        &self,
+        curve: &NurbsCurve<3>,
+    ) -> NurbsCurve<3> {
+        curve.map_control_points(|point| self.transform_point(&point))
+    }
+
+    /// Transform the given NURBS surface
+    pub fn transform_nurbs_surface(
+        &self,
+        surface: &NurbsSurface,
+    ) -> NurbsSurface {
+        surface.map_control_points(|point| self.transform_point(&point))
+    }
+
+    /// Transform the given sphere
+    pub fn transform_sphere(&self, sphere: &Sphere) -> Sphere {
+        Sphere::new(
+            self.transform_point(&sphere.center()),
+            self.transform_vector(&sphere.a()),
+            self.transform_vector(&sphere.b()),
+            self.transform_vector(&sphere.c()),
+        )
+    }
+
+    /// Transform the given cone
+    pub fn transform_cone(&self, cone: &Cone) -> Cone {
+        Cone::new(
+            self.transform_circle(&cone.circle()),
+            self.transform_point(&cone.apex()),
+        )
+    }
+
+    /// Transform the given torus
+    pub fn transform_torus(&self, torus: &Torus) -> Torus {
+        Torus::new(
+            self.transform_point(&torus.center()),
+            self.transform_vector(&torus.a()),
+            self.transform_vector(&torus.b()),
+            self.transform_vector(&torus.c()),
+        )
+    }
+
     /// Inverse transform
     pub fn inverse(&self) -> Self {
         Self(self.0.inverse())
@@ -194,6 +278,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reflection() {
+        let transform = Transform::reflection([1., 1., 0.]);
+
+        assert_abs_diff_eq!(
+            transform.transform_point(&Point::from([1., 0., 2.])),
+            Point::from([0., -1., 2.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert_abs_diff_eq!(
+            transform.transform_vector(&Vector::from([1., 1., 0.])),
+            Vector::from([-1., -1., 0.]),
+            epsilon = Scalar::from(1e-8),
+        );
+    }
+
     #[test]
     fn extract_rotation_translation() {
         let rotation =
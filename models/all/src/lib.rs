@@ -2,7 +2,7 @@ use fj::{
     core::{
         algorithms::transform::TransformObject,
         objects::Solid,
-        operations::{Insert, Merge},
+        operations::{Insert, Union},
         services::Services,
         storage::Handle,
     },
@@ -28,5 +28,13 @@ pub fn model(services: &mut Services) -> Handle<Solid> {
         .translate(offset * 3., services)
         .rotate(axis * angle_rad * 3., services);
 
-    cuboid.merge(&spacer).merge(&star).insert(services)
+    // The models are placed apart from each other. The tolerance is only used
+    // to check that they don't touch.
+    let tolerance = 0.001;
+
+    cuboid
+        .union(&spacer, tolerance, services)
+        .and_then(|solid| solid.union(&star, tolerance, services))
+        .expect("Models don't touch, so their union is supported")
+        .insert(services)
 }
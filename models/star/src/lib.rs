@@ -3,15 +3,14 @@ use std::f64::consts::PI;
 use fj::{
     core::{
         algorithms::sweep::Sweep,
-        objects::{Cycle, Region, Sketch, Solid},
+        objects::{Region, Sketch, Solid},
         operations::{
-            BuildCycle, BuildRegion, BuildSketch, Insert, Reverse,
-            UpdateRegion, UpdateSketch,
+            BuildRegion, BuildSketch, Insert, Pattern, UnionAll, UpdateSketch,
         },
         services::Services,
         storage::Handle,
     },
-    math::Vector,
+    math::{Line, Point, Vector},
 };
 
 pub fn model(
@@ -21,12 +20,11 @@ pub fn model(
     h: f64,
     services: &mut Services,
 ) -> Handle<Solid> {
-    let num_vertices = num_points * 2;
-    let vertex_iter = (0..num_vertices).map(|i| {
-        let angle_rad = 2. * PI / num_vertices as f64 * i as f64;
-        let radius = if i % 2 == 0 { r1 } else { r2 };
-        (angle_rad, radius)
-    });
+    // The star is made up of copies of one of its points. That point reaches
+    // out to `r1`, and is bounded by the vertices at `r2` on both sides of it.
+    let angle_between = PI / num_points as f64;
+    let vertex_iter =
+        [(-angle_between, r2), (0., r1), (angle_between, r2)].into_iter();
 
     let mut outer_points = Vec::new();
     let mut inner_points = Vec::new();
@@ -41,17 +39,34 @@ pub fn model(
         inner_points.push([x / 2., y / 2.]);
     }
 
+    let points = outer_points
+        .into_iter()
+        .chain(inner_points.into_iter().rev())
+        .collect::<Vec<_>>();
     let sketch = Sketch::empty()
-        .add_region(
-            Region::polygon(outer_points, services)
-                .add_interiors([Cycle::polygon(inner_points, services)
-                    .reverse(services)
-                    .insert(services)])
-                .insert(services),
-        )
+        .add_region(Region::polygon(points, services).insert(services))
         .insert(services);
 
     let surface = services.objects.surfaces.xy_plane();
     let path = Vector::from([0., 0., h]);
-    (sketch, surface).sweep(path, services)
+    let point = (sketch, surface).sweep(path, services);
+
+    let axis =
+        Line::from_origin_and_direction(Point::origin(), Vector::unit_z());
+
+    // Neighboring points share the vertices at `r2`. Copying the point around
+    // the axis puts those vertices in almost, but not exactly, the same
+    // position, which is why they need to be merged within a tolerance.
+    let tolerance = r1 * 1e-9;
+
+    point
+        .circular_pattern(
+            num_points as usize,
+            axis,
+            angle_between * 2.,
+            services,
+        )
+        .union_all(tolerance, services)
+        .expect("Union of planar faces with straight edges is supported")
+        .insert(services)
 }